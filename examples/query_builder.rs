@@ -9,7 +9,7 @@
 use anyhow::Result;
 use chrono::FixedOffset;
 use lorm::ToLOrm;
-use lorm::predicates::Where;
+use lorm::predicates::{OrderBy, Where};
 use sqlx::{FromRow, SqlitePool};
 use uuid::Uuid;
 
@@ -96,8 +96,7 @@ async fn main() -> Result<()> {
     println!("2. Products between $50 and $500:");
     let mid_range = Product::select()
         .where_between_price(50, 500)
-        .order_by_price()
-        .asc()
+        .order_by_price(OrderBy::Asc)
         .build(&pool)
         .await?;
     for p in &mid_range {
@@ -109,8 +108,7 @@ async fn main() -> Result<()> {
     println!("3. Products under $100:");
     let affordable = Product::select()
         .where_price(Where::LesserThan, 100)
-        .order_by_price()
-        .desc()
+        .order_by_price(OrderBy::Desc)
         .build(&pool)
         .await?;
     for p in &affordable {
@@ -121,8 +119,7 @@ async fn main() -> Result<()> {
     // Example 4: Pagination
     println!("4. Products page 1 (limit 3):");
     let page1 = Product::select()
-        .order_by_name()
-        .asc()
+        .order_by_name(OrderBy::Asc)
         .limit(3)
         .build(&pool)
         .await?;
@@ -133,8 +130,7 @@ async fn main() -> Result<()> {
 
     println!("5. Products page 2 (limit 3, offset 3):");
     let page2 = Product::select()
-        .order_by_name()
-        .asc()
+        .order_by_name(OrderBy::Asc)
         .limit(3)
         .offset(3)
         .build(&pool)
@@ -148,8 +144,7 @@ async fn main() -> Result<()> {
     println!("6. Expensive items (price > $100):");
     let expensive = Product::select()
         .where_price(Where::GreaterThan, 100)
-        .order_by_price()
-        .desc()
+        .order_by_price(OrderBy::Desc)
         .build(&pool)
         .await?;
     for p in &expensive {
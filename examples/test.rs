@@ -8,7 +8,7 @@
 
 use anyhow::Result;
 use lorm::ToLOrm;
-use lorm::predicates::Where;
+use lorm::predicates::{OrderBy, Where};
 use sqlx::{FromRow, SqlitePool};
 use uuid::Uuid;
 
@@ -48,8 +48,7 @@ async fn main() -> Result<()> {
     let s = User::select()
         .where_id(Where::Eq, &id)
         .where_email(Where::Eq, &user.email)
-        .order_by_email()
-        .asc()
+        .order_by_email(OrderBy::Asc)
         .build(&pool)
         .await?;
 
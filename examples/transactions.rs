@@ -43,9 +43,9 @@ async fn transfer(pool: &SqlitePool, from_id: &Uuid, to_id: &Uuid, amount: i64)
     // Start a transaction
     let mut tx = pool.begin().await?;
 
-    // Get accounts within transaction
-    let mut from_account = Account::by_id(&mut *tx, from_id).await?;
-    let mut to_account = Account::by_id(&mut *tx, to_id).await?;
+    // Load and lock the accounts within the transaction (FOR UPDATE on PostgreSQL and MySQL)
+    let mut from_account = Account::by_pk_for_update(&mut *tx, from_id).await?;
+    let mut to_account = Account::by_pk_for_update(&mut *tx, to_id).await?;
 
     // Check balance
     if from_account.balance < amount {
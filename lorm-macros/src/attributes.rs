@@ -1,4 +1,6 @@
-use crate::utils::is_option_wrapped;
+use crate::utils::{
+    is_auto_increment_type, is_option_wrapped, is_signed_integer_type, is_uuid_type, now_expression,
+};
 use darling::FromField;
 use darling::FromMeta;
 use darling::util::Callable;
@@ -36,6 +38,9 @@ pub(crate) struct TableAttributes {
     #[darling(rename = "pk_selector")]
     pub(crate) pk_selector: Option<String>,
 
+    /// Alias used in `FROM {table} {alias}` by the select builder, which then qualifies its columns.
+    pub(crate) table_alias: Option<String>,
+
     #[darling(rename = "has_many", multiple, default, with = "parse_has_many_spec")]
     pub(crate) has_many_specs: Vec<HasRelSpec>,
 
@@ -271,36 +276,51 @@ impl HasRelSpec {
         self
     }
 
+    /// Self-referential relations cannot infer their FK column nor a method name that would not
+    /// clash with the struct's own methods, both must be given explicitly.
+    fn check_self_ref(self, span: &impl quote::ToTokens) -> darling::Result<Self> {
+        if matches!(self.target, RelationTarget::SelfRef)
+            && (self.fk.is_none() || self.method_name.is_none())
+        {
+            return Err(darling::Error::custom(
+                "self-referential relations require `fk` and `as`, e.g. #[lorm(has_many(Self, fk = \"parent_id\", as = \"children\"))]",
+            )
+            .with_span(span));
+        }
+        Ok(self)
+    }
+
     fn from_meta_without_cardinality(item: &syn::Meta) -> darling::Result<Self> {
-        match item {
+        let spec = match item {
             // `has_many = Post`
             syn::Meta::NameValue(nv) => {
                 let target = RelationTarget::from_expr(&nv.value)?;
-                Ok(Self {
+                Self {
                     target,
                     fk: None,
                     method_name: None,
                     // Caller sets it.
                     cardinality: Cardinality::HasMany,
-                })
+                }
             }
             // `has_many(Post, fk = "col", as = "name")`
             syn::Meta::List(list) => {
                 let args: HasRelSpecArgs = syn::parse2(list.tokens.clone())
                     .map_err(|e| darling::Error::custom(e.to_string()).with_span(item))?;
-                Ok(Self {
+                Self {
                     target: args.target,
                     fk: args.fk,
                     method_name: args.method_name,
                     // Caller sets it.
                     cardinality: Cardinality::HasMany,
-                })
+                }
             }
-            _ => Err(darling::Error::custom(
+            _ => return Err(darling::Error::custom(
                 "expected name-value or list, e.g. #[lorm(has_many = Post)] or #[lorm(has_many(Post, fk = \"col\"))]",
             )
             .with_span(item)),
-        }
+        };
+        spec.check_self_ref(item)
     }
 }
 
@@ -311,13 +331,14 @@ impl darling::FromMeta for HasRelSpec {
 
     fn from_expr(expr: &syn::Expr) -> darling::Result<Self> {
         let target = RelationTarget::from_expr(expr)?;
-        Ok(Self {
+        Self {
             target,
             fk: None,
             method_name: None,
             // Caller sets it.
             cardinality: Cardinality::HasMany,
-        })
+        }
+        .check_self_ref(expr)
     }
 }
 
@@ -338,6 +359,14 @@ struct ColumnPropertyAttrs {
     generate_by: Flag,
     #[darling(rename = "readonly")]
     readonly: Flag,
+    /// Column with a unique constraint, gets `upsert_on_{field}()`.
+    unique: Flag,
+    /// Column written by the database only (defaults, triggers), read back after every write.
+    db_managed: Flag,
+    /// Nullable timestamp set by `delete()` instead of deleting the row.
+    soft_delete: Flag,
+    /// Integer counter checked and incremented by `save()` for optimistic locking.
+    version: Flag,
     #[darling(rename = "created_at")]
     is_created_at: Flag,
     #[darling(rename = "updated_at")]
@@ -352,6 +381,15 @@ struct ColumnPropertyAttrs {
 
     #[darling(rename = "belongs_to")]
     belongs_to_target: Option<RelationTarget>,
+
+    #[darling(rename = "store_as")]
+    store_as: Option<String>,
+
+    /// Field that is never stored, initialized by `default` (or `Default::default()`) when a row
+    /// is read. Lorm then implements `sqlx::FromRow` for the model.
+    transient: Flag,
+    #[darling(rename = "default")]
+    default_expression: Option<Expr>,
 }
 
 #[derive(Debug, Clone)]
@@ -363,16 +401,34 @@ pub struct ColumnProperties {
     /// Readonly fields will not be included in insert or update statements, but will be deserialized when selecting.
     pub readonly: bool,
 
+    /// Whether the value is set by the database (a default or a trigger), which implies readonly.
+    /// Specified by `#[lorm(db_managed)]`.
+    pub db_managed: bool,
+
     /// Whether the field is (part of) the primary key.
     pub primary_key: bool,
 
+    /// Whether the field is an integer primary key without a `new` attribute. When the key is
+    /// generated, the database assigns it (auto-increment) as if it were `readonly`.
+    pub auto_increment: bool,
+
     /// Whether `by_*`, `with_*` and selector methods should be generated for this field.
     pub generate_by: bool,
 
+    /// Whether the column has a unique constraint, making it a conflict target for
+    /// `upsert_on_{field}()`. Specified by `#[lorm(unique)]`.
+    pub unique: bool,
+
     /// Whether the field is the `created_at` field.
     pub created_at: bool,
     /// Whether the field is the `updated_at` field.
     pub updated_at: bool,
+    /// Whether the field marks soft-deleted rows: `delete()` sets it to now, and lookups skip the
+    /// rows where it is not NULL. Specified by `#[lorm(soft_delete)]`.
+    pub soft_delete: bool,
+    /// Whether the field is the optimistic locking counter: `save()` only updates the row holding
+    /// the same value and increments it. Specified by `#[lorm(version)]`.
+    pub version: bool,
 
     /// The expression to use to generate a new value for the field. Used when generating a new primary key or the `created_at` and `updated_at` fields.
     pub new_expression: Expr,
@@ -385,6 +441,10 @@ pub struct ColumnProperties {
 
     pub use_json: bool,
 
+    /// Whether the value is bound as its decimal string, for integers the backends can't store
+    /// natively (e.g. `i128`). Specified by `#[lorm(store_as = "text")]`.
+    pub store_as_text: bool,
+
     #[allow(dead_code)]
     pub belongs_to_target: Option<RelationTarget>,
 }
@@ -426,6 +486,44 @@ impl FieldAttributes {
         self.attrs.skip.is_present()
     }
 
+    /// The initializer of a `#[lorm(transient)]` field, `None` for a stored field.
+    pub(crate) fn transient_initializer(&self, field: &Field) -> syn::Result<Option<Expr>> {
+        let props = &self.field_properties;
+        if !props.transient.is_present() {
+            if props.default_expression.is_some() {
+                return Err(syn::Error::new(
+                    field.span(),
+                    "The `default` attribute only makes sense on #[lorm(transient)] fields.",
+                ));
+            }
+            return Ok(None);
+        }
+        let is_stored = props.is_primary_key.is_present()
+            || props.generate_by.is_present()
+            || props.readonly.is_present()
+            || props.unique.is_present()
+            || props.db_managed.is_present()
+            || props.soft_delete.is_present()
+            || props.version.is_present()
+            || props.is_created_at.is_present()
+            || props.is_updated_at.is_present()
+            || props.belongs_to_target.is_some()
+            || props.flattened_fields.is_some()
+            || props.store_as.is_some();
+        if is_stored {
+            return Err(syn::Error::new(
+                field.span(),
+                "A #[lorm(transient)] field is never stored and cannot hold any other lorm attribute than `default`.",
+            ));
+        }
+        Ok(Some(
+            props
+                .default_expression
+                .clone()
+                .unwrap_or_else(default_new_expression),
+        ))
+    }
+
     pub(crate) fn is_primary_key(&self) -> bool {
         self.field_properties.is_primary_key.is_present()
     }
@@ -438,6 +536,14 @@ impl FieldAttributes {
         self.field_properties.is_updated_at.is_present()
     }
 
+    pub(crate) fn is_soft_delete_field(&self) -> bool {
+        self.field_properties.soft_delete.is_present()
+    }
+
+    pub(crate) fn is_version_field(&self) -> bool {
+        self.field_properties.version.is_present()
+    }
+
     pub(crate) fn flatten_generate_by(&self) -> bool {
         self.field_properties.generate_by.is_present()
     }
@@ -462,15 +568,16 @@ impl ColumnProperties {
         value: ColumnPropertyAttrs,
         sqlx: SqlxColumnAttributes,
     ) -> syn::Result<Self> {
-        // new_expression only makes sense on the primary key field or the created_at and updated_at fields
+        // new_expression only makes sense on the primary key field or the timestamp fields
         if (!value.is_primary_key.is_present())
             && !value.is_updated_at.is_present()
             && !value.is_created_at.is_present()
+            && !value.soft_delete.is_present()
             && value.new_expression.is_some()
         {
             return Err(syn::Error::new(
                 field.span(),
-                "The `new` attribute only makes sense on primary key, created_at or updated_at fields.",
+                "The `new` attribute only makes sense on primary key, created_at, updated_at or soft_delete fields.",
             ));
         }
         // is_set_expression only makes sense on the primary key field
@@ -513,16 +620,93 @@ impl ColumnProperties {
             ));
         }
 
+        if value.db_managed.is_present() && value.is_primary_key.is_present() {
+            return Err(syn::Error::new(
+                field.span(),
+                "The `db_managed` attribute cannot be used on primary key fields, use `readonly` for keys generated by the database.",
+            ));
+        }
+        let readonly = value.readonly.is_present() || value.db_managed.is_present();
+
+        if value.soft_delete.is_present()
+            && (value.is_primary_key.is_present() || !is_option_wrapped(&field.ty))
+        {
+            return Err(syn::Error::new(
+                field.span(),
+                "The `soft_delete` attribute requires a non primary key `Option` field, NULL marking the rows that are not deleted.",
+            ));
+        }
+
+        if value.version.is_present()
+            && (value.is_primary_key.is_present()
+                || readonly
+                || sqlx.is_json.is_present()
+                || !is_signed_integer_type(&field.ty))
+        {
+            return Err(syn::Error::new(
+                field.span(),
+                "The `version` attribute requires a written non primary key `i16`, `i32` or `i64` field.",
+            ));
+        }
+
+        if value.unique.is_present() && (value.is_primary_key.is_present() || readonly) {
+            return Err(syn::Error::new(
+                field.span(),
+                "The `unique` attribute only makes sense on written non primary key fields, `save()` already upserts on the primary key.",
+            ));
+        }
+
+        let store_as_text = match value.store_as.as_deref() {
+            None => false,
+            Some("text") => true,
+            Some(other) => {
+                return Err(syn::Error::new(
+                    field.span(),
+                    format!(
+                        "Unsupported `store_as = \"{other}\"`, the only supported value is \"text\"."
+                    ),
+                ));
+            }
+        };
+        if store_as_text && (is_option_wrapped(&field.ty) || sqlx.is_json.is_present()) {
+            return Err(syn::Error::new(
+                field.span(),
+                "`store_as = \"text\"` is not supported on `Option` or #[sqlx(json)] fields.",
+            ));
+        }
+
         Ok(ColumnProperties {
             skip: sqlx.skip.is_present(),
-            readonly: value.readonly.is_present(),
+            readonly,
+            db_managed: value.db_managed.is_present(),
             primary_key: value.is_primary_key.is_present(),
+            auto_increment: value.is_primary_key.is_present()
+                && value.new_expression.is_none()
+                && is_auto_increment_type(&field.ty),
             generate_by: value.generate_by.is_present(),
+            unique: value.unique.is_present(),
             created_at: value.is_created_at.is_present(),
             updated_at: value.is_updated_at.is_present(),
-            new_expression: value.new_expression.unwrap_or_else(default_new_expression),
+            soft_delete: value.soft_delete.is_present(),
+            version: value.version.is_present(),
+            new_expression: value.new_expression.unwrap_or_else(|| {
+                // Timestamps default to "now" rather than to the epoch, Uuid keys to a random v4
+                // rather than to the nil Uuid.
+                let is_timestamp = value.is_created_at.is_present()
+                    || value.is_updated_at.is_present()
+                    || value.soft_delete.is_present();
+                if is_timestamp && let Some(now) = now_expression(&field.ty) {
+                    now
+                } else if value.is_primary_key.is_present() && is_uuid_type(&field.ty) {
+                    let ty = &field.ty;
+                    syn::parse_quote! { <#ty>::new_v4() }
+                } else {
+                    default_new_expression()
+                }
+            }),
             is_set_expression: value.is_set_expression,
             use_json: sqlx.is_json.is_present(),
+            store_as_text,
             belongs_to_target: value.belongs_to_target,
         })
     }
@@ -530,6 +714,7 @@ impl ColumnProperties {
     pub fn is_set(&self, base: TokenStream, ty: &Type) -> TokenStream {
         match &self.is_set_expression {
             Some(callable) => quote! { (#callable)(#base) },
+            None if is_uuid_type(ty) => quote! { (#base).is_nil() },
             None => quote! { (|val: &#ty| val == &<#ty as Default>::default())(#base) },
         }
     }
@@ -610,6 +795,26 @@ mod tests {
         assert_eq!(attrs.pk_selector_name(&["a", "b"]), "by_key");
     }
 
+    #[test]
+    fn self_referential_relations_require_fk_and_as() {
+        for attr in [
+            "#[lorm(has_many = Self)]",
+            r#"#[lorm(has_many(Self, fk = "parent_id"))]"#,
+            r#"#[lorm(has_one(Self, as = "child"))]"#,
+        ] {
+            let input: syn::DeriveInput = parse_str(&format!("{attr} struct Node {{ }}")).unwrap();
+            assert!(
+                TableAttributes::from_derive_input(&input).is_err(),
+                "{attr} must be rejected"
+            );
+        }
+        let input: syn::DeriveInput = parse_str(
+            r#"#[lorm(has_many(Self, fk = "parent_id", as = "children"))] struct Node { }"#,
+        )
+        .unwrap();
+        assert!(TableAttributes::from_derive_input(&input).is_ok());
+    }
+
     #[test]
     fn pk_selector_name_uses_override() {
         let input: syn::DeriveInput =
@@ -714,6 +919,43 @@ mod tests {
         assert!(fa.is_skip()); // mutant: false
     }
 
+    #[test]
+    fn field_attributes_transient_initializer() {
+        use darling::FromField;
+        let s: syn::ItemStruct = parse_str(
+            r#"
+            struct S {
+                #[lorm(transient, default = "Cache::empty()")]
+                pub cache: Cache,
+                #[lorm(transient)]
+                pub tmp: String,
+                #[lorm(default = "1")]
+                pub orphan: i32,
+                #[lorm(transient, by)]
+                pub stored: i32,
+            }
+        "#,
+        )
+        .unwrap();
+        let initializer = |i: usize| {
+            let field = s.fields.iter().nth(i).unwrap();
+            FieldAttributes::from_field(field)
+                .unwrap()
+                .transient_initializer(field)
+                .map(|e| e.map(|e| quote::ToTokens::to_token_stream(&e).to_string()))
+        };
+        assert_eq!(
+            initializer(0).unwrap().as_deref(),
+            Some("Cache :: empty ()")
+        );
+        assert_eq!(
+            initializer(1).unwrap().as_deref(),
+            Some("Default :: default ()")
+        );
+        assert!(initializer(2).is_err());
+        assert!(initializer(3).is_err());
+    }
+
     #[test]
     fn field_attributes_by_annotation() {
         use darling::FromField;
@@ -785,6 +1027,176 @@ mod tests {
         assert!(result.is_err(), "is_set on non-pk field must be rejected");
     }
 
+    #[test]
+    fn column_properties_rejects_unique_on_pk_or_readonly_field() {
+        use darling::FromField;
+        let s: syn::ItemStruct = parse_str(
+            r#"
+            struct S {
+                #[lorm(pk, unique)]
+                pub id: Uuid,
+                #[lorm(readonly, unique)]
+                pub email: String,
+                #[lorm(unique)]
+                pub login: String,
+            }
+        "#,
+        )
+        .unwrap();
+        let results = s
+            .fields
+            .iter()
+            .map(|field| FieldProperties::from(field, FieldAttributes::from_field(field).unwrap()))
+            .collect::<Vec<_>>();
+        assert!(results[0].is_err(), "unique on the pk must be rejected");
+        assert!(
+            results[1].is_err(),
+            "unique on a readonly field must be rejected"
+        );
+        assert!(results[2].as_ref().unwrap().column_properties.unique);
+    }
+
+    #[test]
+    fn column_properties_db_managed_implies_readonly() {
+        use darling::FromField;
+        let s: syn::ItemStruct = parse_str(
+            r#"
+            struct S {
+                #[lorm(pk, db_managed)]
+                pub id: Uuid,
+                #[lorm(updated_at, db_managed)]
+                pub updated_at: DateTime<Utc>,
+            }
+        "#,
+        )
+        .unwrap();
+        let results = s
+            .fields
+            .iter()
+            .map(|field| FieldProperties::from(field, FieldAttributes::from_field(field).unwrap()))
+            .collect::<Vec<_>>();
+        assert!(results[0].is_err(), "db_managed on the pk must be rejected");
+        let props = &results[1].as_ref().unwrap().column_properties;
+        assert!(props.db_managed);
+        assert!(props.readonly);
+    }
+
+    #[test]
+    fn column_properties_rejects_soft_delete_on_pk_or_non_option_field() {
+        use darling::FromField;
+        let s: syn::ItemStruct = parse_str(
+            r#"
+            struct S {
+                #[lorm(pk, soft_delete)]
+                pub id: Option<Uuid>,
+                #[lorm(soft_delete)]
+                pub removed_at: DateTime<Utc>,
+                #[lorm(soft_delete)]
+                pub deleted_at: Option<DateTime<Utc>>,
+            }
+        "#,
+        )
+        .unwrap();
+        let results = s
+            .fields
+            .iter()
+            .map(|field| FieldProperties::from(field, FieldAttributes::from_field(field).unwrap()))
+            .collect::<Vec<_>>();
+        assert!(
+            results[0].is_err(),
+            "soft_delete on the pk must be rejected"
+        );
+        assert!(
+            results[1].is_err(),
+            "soft_delete on a non Option field must be rejected"
+        );
+        let props = &results[2].as_ref().unwrap().column_properties;
+        assert!(props.soft_delete);
+        assert_eq!(
+            quote::ToTokens::to_token_stream(&props.new_expression).to_string(),
+            "Some (chrono :: Utc :: now ())"
+        );
+    }
+
+    #[test]
+    fn column_properties_version_requires_written_integer_field() {
+        use darling::FromField;
+        let s: syn::ItemStruct = parse_str(
+            r#"
+            struct S {
+                #[lorm(pk, version)]
+                pub id: i64,
+                #[lorm(version)]
+                pub revision: Option<i64>,
+                #[lorm(version)]
+                pub label: String,
+                #[lorm(readonly, version)]
+                pub counter: i32,
+                #[lorm(version)]
+                pub version: i64,
+                #[lorm(version)]
+                pub unsigned: u64,
+            }
+        "#,
+        )
+        .unwrap();
+        let results = s
+            .fields
+            .iter()
+            .map(|field| FieldProperties::from(field, FieldAttributes::from_field(field).unwrap()))
+            .collect::<Vec<_>>();
+        assert!(results[0].is_err(), "version on the pk must be rejected");
+        assert!(results[1].is_err(), "version on an Option must be rejected");
+        assert!(results[2].is_err(), "version on a String must be rejected");
+        assert!(
+            results[3].is_err(),
+            "version on a readonly field must be rejected"
+        );
+        let props = &results[4].as_ref().unwrap().column_properties;
+        assert!(props.version);
+        assert!(!props.readonly);
+        assert!(
+            results[5].is_err(),
+            "version on an unsigned field must be rejected"
+        );
+    }
+
+    #[test]
+    fn column_properties_integer_pk_without_new_is_auto_increment() {
+        use darling::FromField;
+        let s: syn::ItemStruct = parse_str(
+            r#"
+            struct S {
+                #[lorm(pk)]
+                pub id: i64,
+                #[lorm(pk, new = "next_id()")]
+                pub key: i64,
+                #[lorm(pk)]
+                pub uuid: Uuid,
+                #[lorm(pk)]
+                pub unsigned: u64,
+            }
+        "#,
+        )
+        .unwrap();
+        let props = s
+            .fields
+            .iter()
+            .map(|field| {
+                FieldProperties::from(field, FieldAttributes::from_field(field).unwrap())
+                    .unwrap()
+                    .column_properties
+            })
+            .collect::<Vec<_>>();
+        assert!(props[0].auto_increment);
+        assert!(
+            !props[1].auto_increment,
+            "an explicit `new` generates the key"
+        );
+        assert!(!props[2].auto_increment);
+        assert!(props[3].auto_increment);
+    }
+
     #[test]
     fn column_properties_allows_new_on_created_at() {
         // Ensures the || logic is correct (not && mutation): new is allowed on created_at
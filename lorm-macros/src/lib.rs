@@ -28,21 +28,38 @@ mod utils;
 /// `#[lorm(skip)]`
 ///  Ignore field for persistence operations. Using sqlx::FromRow, skip needs `#[lorm(skip)]` and `#[sqlx(skip)]`
 ///
+/// `#[lorm(transient, default="expr")]`
+///  Field that is never stored, initialized with `expr` (or `Default::default()`) when a row is read.
+///  Lorm then implements `sqlx::FromRow` for the struct, which must not derive it.
+///
 /// `#[lorm(readonly)]`
 ///  readonly attribute. Cannot be updated not inserted.
 ///  Special cases to consider:
 ///   - If applied to the primary key, key generation is left to the database. No update possible as it is the primary key.
+///     An integer primary key (`i16`, `i32`, `i64`) without `#[lorm(new)]` is treated this way as well.
 ///   - If applied to create_at or updated_at field, timestamp generation is left to the database. No update possible.
 ///
 /// `#[lorm(by)]`
 ///  Generates query methods for this field:
 ///  - `by_<field>(executor, value)` - Find single record by field value
 ///  - `with_<field>(executor, value)` - Find all records matching field value
+///  - `delete_by_<field>(executor, value)` - Delete all records matching field value, returning the number of deleted rows
 ///  - `where_<field>(Where, value)` - Filter in select() query builder
 ///  - `where_between_<field>(start, end)` - Range filter in select() query builder
-///  - `order_by_<field>()` - Order results by this field (chain with `.asc()` or `.desc()`)
+///  - `order_by_<field>(OrderBy)` - Order results by this field in the given direction
 ///  - `group_by_<field>()` - Group results by this field
 ///
+/// `#[lorm(unique)]`
+///  Marks a column holding a unique constraint. Generates `upsert_on_<field>(&self, executor)`,
+///  inserting the row or updating the one holding the same value (`ON CONFLICT (column) DO UPDATE`,
+///  `ON DUPLICATE KEY UPDATE` on MySQL). Cannot be combined with `pk` or `readonly`.
+///  With `by`, `by_<field>()` returns the only matching row and `by_<field>_or_insert()` is an
+///  atomic find-or-create, also generated for the primary key. Inserting a value held by another row fails with `Error::UniqueViolation`.
+///
+/// `#[lorm(db_managed)]`
+///  Marks a column set by the database, with a default or a trigger. Implies `readonly`, and
+///  `save()` returns the value stored once the triggers ran. Cannot be used on the primary key.
+///
 /// `#[lorm(created_at)]`
 ///  Add the `#[lorm(created_at)]` annotation to mark the field as the `created_at` field.
 ///  - If `#[lorm(new)]` is specified, it will use its method to update the time upon insertion
@@ -53,6 +70,16 @@ mod utils;
 ///  - If `#[lorm(new)]` is specified, it will use its method to update the time upon insertion and update
 ///  - If `#[lorm(readonly)]` is specified, it will ignore is_set `#[lorm(new)]` and let the database handles the field
 ///
+/// `#[lorm(soft_delete)]`
+///  Marks a nullable timestamp field as the deletion time: `delete()` sets it to now instead of
+///  deleting the row and `hard_delete()` deletes it. Lookups and `select()` skip the deleted rows,
+///  `select().with_deleted()` includes them.
+///
+/// `#[lorm(version)]`
+///  Marks an `i16`, `i32` or `i64` field as the optimistic locking counter: `save()` updates the
+///  row only while it holds the same version, and increments it. A stale instance gets
+///  `Error::OptimisticLockConflict`. Requires a generated primary key.
+///
 /// `#[lorm(new="module::path::class::new_custom()")]`
 ///  Add the `#[lorm(new="module::path::class::new_custom()")]` annotation to use a custom creation method.
 ///  - The function call is expected to return an instance
@@ -5,12 +5,12 @@ use crate::attributes::PrimaryKeyType;
 use crate::attributes::TableAttributes;
 use crate::orm::column::Column;
 use crate::orm::relations::RelationInfo;
-use crate::utils::is_option_wrapped;
+use crate::utils::{aliased_table, is_option_wrapped, qualify_column};
 use darling::FromDeriveInput;
 use darling::FromField;
 use quote::ToTokens;
 use quote::quote;
-use syn::parse;
+use syn::parse2;
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
 use syn::token::Comma;
@@ -45,15 +45,55 @@ impl<'a> PrimaryKey<'a> {
     }
 }
 
+/// One entry of a `SELECT` list: a column or an SQL expression, optionally aliased.
+pub(crate) struct SelectItem {
+    pub(crate) expression: String,
+    pub(crate) alias: Option<String>,
+}
+
+impl SelectItem {
+    pub(crate) fn column(column_name: String) -> Self {
+        SelectItem {
+            expression: column_name,
+            alias: None,
+        }
+    }
+
+    /// Renders `expression AS alias`, or just `expression` without an alias.
+    pub(crate) fn to_sql(&self) -> String {
+        match &self.alias {
+            Some(alias) => format!("{} AS {alias}", self.expression),
+            None => self.expression.clone(),
+        }
+    }
+}
+
+/// Joins the rendered items into a `SELECT` list.
+pub(crate) fn render_select_list(items: &[SelectItem]) -> String {
+    items
+        .iter()
+        .map(SelectItem::to_sql)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 pub(crate) struct OrmModel<'a> {
     pub(crate) struct_name: &'a Ident,
     pub(crate) struct_visibility: &'a Visibility,
     pub(crate) table_name: String,
+    pub(crate) table_alias: Option<String>,
     pub(crate) columns: Vec<Column<'a>>,
 
     pub(crate) primary_key: PrimaryKey<'a>,
     pub(crate) pk_selector_name: String,
     pub(crate) relations: Vec<RelationInfo>,
+
+    /// Fields that are not columns (`#[sqlx(skip)]` or `#[lorm(transient)]`), with the expression
+    /// initializing them when a row is read.
+    pub(crate) transient_fields: Vec<(&'a Field, syn::Expr)>,
+    /// Whether a `#[lorm(transient)]` field or a `store_as = "text"` column makes Lorm implement
+    /// `sqlx::FromRow` for the model.
+    pub(crate) implements_from_row: bool,
 }
 
 impl<'a> OrmModel<'a> {
@@ -66,11 +106,15 @@ impl<'a> OrmModel<'a> {
         let struct_name = &input.ident;
         let struct_visibility = &input.vis;
         let table_name = top_level_attributes.table_name(input);
+        let table_alias = top_level_attributes.table_alias.clone();
 
         let mut columns = Vec::new();
+        let mut transient_fields = Vec::new();
+        let mut implements_from_row = false;
 
         for field in fields.iter() {
-            process_struct_field(field, &mut columns)?;
+            implements_from_row |=
+                process_struct_field(field, &mut columns, &mut transient_fields)?;
         }
 
         let created_at_columns = columns
@@ -94,6 +138,45 @@ impl<'a> OrmModel<'a> {
             ));
         }
 
+        let soft_delete_columns = columns
+            .iter()
+            .filter(|c| c.column_properties.soft_delete)
+            .count();
+        if soft_delete_columns > 1 {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "Only one field can hold the #[lorm(soft_delete)] attribute",
+            ));
+        }
+
+        let version_columns = columns
+            .iter()
+            .filter(|c| c.column_properties.version)
+            .count();
+        if version_columns > 1 {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "Only one field can hold the #[lorm(version)] attribute",
+            ));
+        }
+        // `save()` of a manual key upserts, there is no UPDATE to guard with the version.
+        if version_columns > 0 && top_level_attributes.pk_type == PrimaryKeyType::Manual {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "The #[lorm(version)] attribute requires a generated primary key",
+            ));
+        }
+
+        // A generated integer key without `new` is left to the database.
+        if top_level_attributes.pk_type == PrimaryKeyType::Generated {
+            for column in columns
+                .iter_mut()
+                .filter(|c| c.column_properties.auto_increment)
+            {
+                column.column_properties.readonly = true;
+            }
+        }
+
         let mut pk_columns = columns
             .iter()
             .filter(|c| c.column_properties.primary_key)
@@ -164,10 +247,13 @@ impl<'a> OrmModel<'a> {
             struct_name,
             struct_visibility,
             table_name,
+            table_alias,
             columns,
             primary_key,
             pk_selector_name,
             relations,
+            transient_fields,
+            implements_from_row,
         })
     }
 
@@ -177,12 +263,37 @@ impl<'a> OrmModel<'a> {
             .filter(|c| c.should_generate_query_function(self.primary_key.is_generated()))
     }
 
-    pub(crate) fn full_column_select(&self) -> String {
+    /// The model columns as `SELECT` items, qualified by the table alias when `qualified` is set.
+    pub(crate) fn select_items(&self, qualified: bool) -> Vec<SelectItem> {
         self.columns
             .iter()
-            .map(|c| c.column_name.clone())
-            .collect::<Vec<_>>()
-            .join(", ")
+            .map(|c| {
+                SelectItem::column(if qualified {
+                    self.qualified_column(&c.column_name)
+                } else {
+                    c.column_name.clone()
+                })
+            })
+            .collect()
+    }
+
+    pub(crate) fn full_column_select(&self) -> String {
+        render_select_list(&self.select_items(false))
+    }
+
+    /// Table reference for a `FROM` clause: `users u` when a `table_alias` is set, `users` otherwise.
+    pub(crate) fn table_reference(&self) -> String {
+        aliased_table(&self.table_name, self.table_alias.as_deref())
+    }
+
+    /// Prefixes a column name with the table alias, if any.
+    pub(crate) fn qualified_column(&self, column_name: &str) -> String {
+        qualify_column(self.table_alias.as_deref(), column_name)
+    }
+
+    /// Same as `full_column_select()` with every column qualified by the table alias.
+    pub(crate) fn qualified_column_select(&self) -> String {
+        render_select_list(&self.select_items(true))
     }
 
     pub(crate) fn primary_key(&self) -> &PrimaryKey<'a> {
@@ -197,10 +308,59 @@ impl<'a> OrmModel<'a> {
         self.columns.iter().find(|c| c.column_properties.updated_at)
     }
 
-    pub(crate) fn update_columns(&self) -> impl Iterator<Item = &Column<'a>> {
+    pub(crate) fn soft_delete(&self) -> Option<&Column<'a>> {
         self.columns
             .iter()
-            .filter(|c| !c.column_properties.readonly && !c.column_properties.primary_key)
+            .find(|c| c.column_properties.soft_delete)
+    }
+
+    /// The `#[lorm(version)]` column, checked and incremented by `save()`.
+    pub(crate) fn version(&self) -> Option<&Column<'a>> {
+        self.columns.iter().find(|c| c.column_properties.version)
+    }
+
+    /// The assignment incrementing the version, `version = version + 1`, for the statements
+    /// updating rows. `None` without a `#[lorm(version)]` column.
+    pub(crate) fn version_increment(&self) -> Option<String> {
+        self.version()
+            .map(|c| format!("{0} = {0} + 1", c.column_name))
+    }
+
+    /// The condition keeping the rows that are not soft-deleted, `deleted_at IS NULL AND `, to
+    /// put first in a `WHERE` clause. Empty without a `#[lorm(soft_delete)]` column.
+    pub(crate) fn not_deleted_and(&self) -> String {
+        self.soft_delete()
+            .map(|c| format!("{} IS NULL AND ", c.column_name))
+            .unwrap_or_default()
+    }
+
+    /// Same as `not_deleted_and()`, as the whole `WHERE` clause of a statement without conditions.
+    pub(crate) fn where_not_deleted(&self) -> String {
+        self.soft_delete()
+            .map(|c| format!(" WHERE {} IS NULL", c.column_name))
+            .unwrap_or_default()
+    }
+
+    /// The `#[lorm(unique)]` columns, conflict targets of `upsert_on_{field}()`.
+    pub(crate) fn unique_columns(&self) -> impl Iterator<Item = &Column<'a>> {
+        self.columns.iter().filter(|c| c.column_properties.unique)
+    }
+
+    /// Whether a `#[lorm(db_managed)]` column is set by the database on writes.
+    pub(crate) fn has_db_managed_columns(&self) -> bool {
+        self.columns.iter().any(|c| c.column_properties.db_managed)
+    }
+
+    /// The columns bound by the statements updating rows. The version is incremented in place
+    /// rather than bound, see `version_increment()`. The soft-delete column is only written by
+    /// `delete()`, so that saving a copy read before the deletion does not bring the row back.
+    pub(crate) fn update_columns(&self) -> impl Iterator<Item = &Column<'a>> {
+        self.columns.iter().filter(|c| {
+            !c.column_properties.readonly
+                && !c.column_properties.primary_key
+                && !c.column_properties.version
+                && !c.column_properties.soft_delete
+        })
     }
 
     pub(crate) fn insert_columns(&self) -> impl Iterator<Item = &Column<'a>> {
@@ -215,13 +375,28 @@ impl<'a> OrmModel<'a> {
             PrimaryKey::Manual(cols) => Box::new(cols.iter()),
         };
 
-        pk_columns.chain(self.update_columns())
+        pk_columns
+            .chain(self.update_columns())
+            .chain(self.soft_delete())
+            .chain(self.version())
     }
 }
 
-fn process_struct_field<'a>(field: &'a Field, columns: &mut Vec<Column<'a>>) -> syn::Result<()> {
+/// Adds the columns of `field`, or the field to `transient_fields` when it is not stored.
+/// Returns whether the field needs Lorm's `sqlx::FromRow`: `#[lorm(transient)]` fields and
+/// `store_as = "text"` columns.
+fn process_struct_field<'a>(
+    field: &'a Field,
+    columns: &mut Vec<Column<'a>>,
+    transient_fields: &mut Vec<(&'a Field, syn::Expr)>,
+) -> syn::Result<bool> {
     let field_attrs = FieldAttributes::from_field(field)?;
 
+    if let Some(initializer) = field_attrs.transient_initializer(field)? {
+        transient_fields.push((field, initializer));
+        return Ok(true);
+    }
+
     let has_sqlx_flatten = field_attrs.has_sqlx_flatten();
     let has_lorm_flattened = field_attrs.has_lorm_flattened();
 
@@ -259,9 +434,23 @@ fn process_struct_field<'a>(field: &'a Field, columns: &mut Vec<Column<'a>>) ->
                 "A flattened field cannot be #[lorm(updated_at)].",
             ));
         }
+        if field_attrs.is_soft_delete_field() {
+            return Err(syn::Error::new(
+                field.span(),
+                "A flattened field cannot be #[lorm(soft_delete)].",
+            ));
+        }
+        if field_attrs.is_version_field() {
+            return Err(syn::Error::new(
+                field.span(),
+                "A flattened field cannot be #[lorm(version)].",
+            ));
+        }
 
         if field_attrs.is_skip() {
-            return Ok(()); // Parent skipped → skip all nested fields
+            // Parent skipped → skip all nested fields
+            transient_fields.push((field, syn::parse_quote! { Default::default() }));
+            return Ok(false);
         }
 
         let generate_by = field_attrs.flatten_generate_by();
@@ -278,13 +467,19 @@ fn process_struct_field<'a>(field: &'a Field, columns: &mut Vec<Column<'a>>) ->
             let col_props = ColumnProperties {
                 skip: false,
                 readonly,
+                db_managed: false,
                 primary_key: false,
+                auto_increment: false,
                 generate_by,
+                unique: false,
                 created_at: false,
                 updated_at: false,
+                soft_delete: false,
+                version: false,
                 new_expression: syn::parse_str("Default::default()").unwrap(),
                 is_set_expression: None,
                 use_json: false,
+                store_as_text: false,
                 belongs_to_target: None,
             };
 
@@ -299,15 +494,17 @@ fn process_struct_field<'a>(field: &'a Field, columns: &mut Vec<Column<'a>>) ->
             });
         }
 
-        return Ok(());
+        return Ok(false);
     }
 
     let properties = FieldProperties::from(field, field_attrs)?;
 
     if properties.column_properties.skip {
-        return Ok(());
+        transient_fields.push((field, syn::parse_quote! { Default::default() }));
+        return Ok(false);
     }
 
+    let store_as_text = properties.column_properties.store_as_text;
     let logical_fields: Box<dyn Iterator<Item = Column<'a>>> = {
         let column_name = properties.column_name.clone();
         // move out column_properties once, then extract belongs_to from it
@@ -317,7 +514,7 @@ fn process_struct_field<'a>(field: &'a Field, columns: &mut Vec<Column<'a>>) ->
         let logical_field = Column {
             base_field: field,
             field: field.ident.clone().unwrap(),
-            ty: parse((&field.ty).into_token_stream().into())?,
+            ty: parse2((&field.ty).into_token_stream())?,
             is_flattened: false,
             column_name,
             column_properties: col_props,
@@ -331,7 +528,7 @@ fn process_struct_field<'a>(field: &'a Field, columns: &mut Vec<Column<'a>>) ->
         columns.push(logical_field);
     }
 
-    Ok(())
+    Ok(store_as_text)
 }
 
 #[cfg(test)]
@@ -401,4 +598,74 @@ mod tests {
         let fa = FieldAttributes::from_field(field).unwrap();
         assert!(fa.is_primary_key());
     }
+
+    #[test]
+    fn test_column_select_lists() {
+        let (input, fields) = parse_model(
+            r#"
+            #[lorm(table_alias = "u")]
+            struct User {
+                #[lorm(pk)]
+                pub id: u32,
+                #[sqlx(rename = "e_mail")]
+                pub email: String,
+            }
+        "#,
+        );
+        let model = super::OrmModel::from_fields(&input, &fields).unwrap();
+        assert_eq!(model.full_column_select(), "id, e_mail");
+        assert_eq!(model.qualified_column_select(), "u.id, u.e_mail");
+    }
+
+    #[test]
+    fn test_serde_attributes_do_not_rename_columns() {
+        let (input, fields) = parse_model(
+            r#"
+            #[serde(rename_all = "camelCase")]
+            struct User {
+                #[lorm(pk)]
+                #[serde(rename = "userId")]
+                pub id: u32,
+                #[serde(rename = "mail", skip_serializing_if = "String::is_empty")]
+                pub email: String,
+                #[sqlx(rename = "name")]
+                #[serde(default)]
+                pub display_name: String,
+            }
+        "#,
+        );
+        let model = super::OrmModel::from_fields(&input, &fields).unwrap();
+        assert_eq!(model.table_name, "users");
+        assert_eq!(model.full_column_select(), "id, email, name");
+    }
+
+    #[test]
+    fn test_schema_qualified_table_name() {
+        let (input, fields) = parse_model(
+            r#"
+            #[lorm(rename = "public.users", table_alias = "u")]
+            struct User {
+                #[lorm(pk)]
+                pub id: u32,
+            }
+        "#,
+        );
+        let model = super::OrmModel::from_fields(&input, &fields).unwrap();
+        assert_eq!(model.table_name, "public.users");
+        assert_eq!(model.table_reference(), "public.users u");
+    }
+
+    #[test]
+    fn test_select_item_renders_alias() {
+        use super::{SelectItem, render_select_list};
+
+        let items = [
+            SelectItem::column("id".to_string()),
+            SelectItem {
+                expression: "LOWER(email)".to_string(),
+                alias: Some("email_key".to_string()),
+            },
+        ];
+        assert_eq!(render_select_list(&items), "id, LOWER(email) AS email_key");
+    }
 }
@@ -1,7 +1,5 @@
 use crate::models::OrmModel;
-use crate::utils::{
-    db_placeholder, get_bind_param_type_and_usage, get_bind_type_where_constraint, to_column_type,
-};
+use crate::utils::{db_placeholder, is_uuid_type, max_bind_parameters, to_column_type};
 use quote::{__private::TokenStream, format_ident, quote};
 
 pub fn generate_by(
@@ -13,6 +11,9 @@ pub fn generate_by(
     let struct_name = model.struct_name;
     let struct_visibility = model.struct_visibility;
     let table_name = &model.table_name;
+    // Soft-deleted rows are left out of every lookup.
+    let not_deleted = model.not_deleted_and();
+    let where_not_deleted = model.where_not_deleted();
 
     let stream: Vec<(TokenStream, TokenStream)> = model
         .query_columns()
@@ -23,19 +24,22 @@ pub fn generate_by(
             let lifetime = quote! {'a};
             let parameter = quote! {value};
             let (param_type, param_value) =
-                get_bind_param_type_and_usage(&parameter, &column.ty, &lifetime).unwrap();
+                column.bind_param(&parameter, &lifetime).unwrap();
             let by_fn = format_ident!("by_{}", field_name);
+            let by_unique_fn = format_ident!("by_{}_unique", field_name);
 
             let columns = model.full_column_select();
             let placeholder = db_placeholder(column.base_field, 1).unwrap();
             let sql_ident =
-                format!("SELECT {columns} FROM {table_name} WHERE {column_name} = {placeholder}");
+                format!("SELECT {columns} FROM {table_name} WHERE {not_deleted}{column_name} = {placeholder}");
+            let sql_unique_ident = format!("{sql_ident} LIMIT 2");
+            let multiple_rows_message = format!("{by_unique_fn}() matched more than one row");
 
             let field_type_constraints = if column.column_properties.use_json {
                 let base_type = to_column_type(&column.ty).unwrap();
                 quote! { #base_type: serde::Serialize }
             } else {
-                get_bind_type_where_constraint(&column.ty, database_type, &lifetime).unwrap()
+                column.bind_constraint(database_type, &lifetime).unwrap()
             };
 
             let bind_value = if column.column_properties.use_json {
@@ -48,8 +52,170 @@ pub fn generate_by(
                 async fn #by_fn<#lifetime>(executor: E, #parameter: #param_type) -> lorm::errors::Result<#struct_name> where #field_type_constraints
             };
 
+            let exists_by_fn = format_ident!("exists_by_{}", field_name);
+            let exists_sql = format!("SELECT EXISTS(SELECT 1 FROM {table_name} WHERE {not_deleted}{column_name} = {placeholder})");
+            let exists_fetch = exists_fetch(&exists_sql, &quote! { .bind(#bind_value) });
+            let exists_signature = quote! {
+                async fn #exists_by_fn<#lifetime>(executor: E, #parameter: #param_type) -> lorm::errors::Result<bool> where #field_type_constraints
+            };
+            let try_by_fn = format_ident!("try_by_{}", field_name);
+            let try_signature = quote! {
+                async fn #try_by_fn<#lifetime>(executor: E, #parameter: #param_type) -> lorm::errors::Result<Option<#struct_name>> where #field_type_constraints
+            };
+            let delete_by_fn = format_ident!("delete_by_{}", field_name);
+            let hard_delete_sql = format!("DELETE FROM {table_name} WHERE {column_name} = {placeholder}");
+            let delete_signature = quote! {
+                async fn #delete_by_fn<#lifetime>(executor: E, #parameter: #param_type) -> lorm::errors::Result<u64> where #field_type_constraints
+            };
+            // With a soft_delete column, delete_by_{field}() stamps the rows not deleted yet, as
+            // delete() does, and hard_delete_by_{field}() removes them.
+            let (delete_code, hard_delete_trait_code, hard_delete_impl_code) = match model.soft_delete() {
+                Some(soft_delete) => {
+                    let soft_sql = format!(
+                        "UPDATE {table_name} SET {0} = {1} WHERE {0} IS NULL AND {column_name} = {2}",
+                        soft_delete.column_name,
+                        db_placeholder(soft_delete.base_field, 1).unwrap(),
+                        db_placeholder(column.base_field, 2).unwrap()
+                    );
+                    let new_method = &soft_delete.column_properties.new_expression;
+                    let hard_delete_by_fn = format_ident!("hard_delete_by_{}", field_name);
+                    let hard_delete_signature = quote! {
+                        async fn #hard_delete_by_fn<#lifetime>(executor: E, #parameter: #param_type) -> lorm::errors::Result<u64> where #field_type_constraints
+                    };
+                    (
+                        quote! {
+                            sqlx::query(#soft_sql)
+                                .bind(#new_method)
+                                .bind(#bind_value)
+                                .execute(executor).await?
+                        },
+                        quote! {
+                            /// Removes every matching row for good, soft-deleted or not, and returns
+                            /// the number of removed rows.
+                            #hard_delete_signature;
+                        },
+                        quote! {
+                            #hard_delete_signature {
+                                let r = sqlx::query(#hard_delete_sql)
+                                    .bind(#bind_value)
+                                    .execute(executor).await?;
+                                Ok(r.rows_affected())
+                            }
+                        },
+                    )
+                }
+                None => (
+                    quote! {
+                        sqlx::query(#hard_delete_sql)
+                            .bind(#bind_value)
+                            .execute(executor).await?
+                    },
+                    quote! {},
+                    quote! {},
+                ),
+            };
+            let lookup_trait_code = quote! {
+                /// Same as `by_{field}()`, returning `None` instead of failing when no row matches.
+                #try_signature;
+                /// Whether a row matches, without fetching it.
+                #exists_signature;
+                /// Deletes every matching row and returns the number of deleted rows. With a
+                /// `soft_delete` field, marks the matching rows as deleted instead.
+                #delete_signature;
+                #hard_delete_trait_code
+            };
+            let lookup_impl_code = quote! {
+                #try_signature {
+                    let r = sqlx::query_as::<_, #struct_name>(#sql_ident)
+                        .bind(#bind_value)
+                        .fetch_optional(executor).await?;
+                    Ok(r)
+                }
+
+                #exists_signature {
+                    let r = #exists_fetch;
+                    Ok(r)
+                }
+
+                #delete_signature {
+                    let r = #delete_code;
+                    Ok(r.rows_affected())
+                }
+
+                #hard_delete_impl_code
+            };
+
+            // The primary key is unique already, by_{pk}() is enough.
+            if column.column_properties.primary_key {
+                return (
+                    quote! {
+                        #signature;
+                        #lookup_trait_code
+                    },
+                    quote! {
+                        #signature {
+                            let r = sqlx::query_as::<_, #struct_name>(#sql_ident)
+                                .bind(#bind_value)
+                                .fetch_one(executor).await?;
+                            Ok(r)
+                        }
+
+                        #lookup_impl_code
+                    },
+                );
+            }
+
+            let unique_signature = quote! {
+                async fn #by_unique_fn<#lifetime>(executor: E, #parameter: #param_type) -> lorm::errors::Result<#struct_name> where #field_type_constraints
+            };
+
+            // Per-value counts need a value that sqlx can decode, as pluck_{field} does.
+            let (count_grouped_trait_code, count_grouped_impl_code) =
+                if column.is_decodable() && !column.column_properties.use_json {
+                    let count_grouped_fn = format_ident!("count_grouped_by_{}", field_name);
+                    let ty = &column.ty;
+                    // The alias keeps ORDER BY unambiguous for a column named `count` on PostgreSQL.
+                    let sql = format!(
+                        "SELECT {column_name}, COUNT(*) AS lorm_count FROM {table_name}{where_not_deleted} GROUP BY {column_name} ORDER BY {column_name}"
+                    );
+                    let count_signature = quote! {
+                        async fn #count_grouped_fn(executor: E) -> lorm::errors::Result<Vec<(#ty, i64)>>
+                    };
+                    (
+                        quote! {
+                            /// The number of rows per distinct value of the column, ordered by value.
+                            #count_signature;
+                        },
+                        quote! {
+                            #count_signature {
+                                let r = sqlx::query_as::<_, (#ty, i64)>(#sql)
+                                    .fetch_all(executor).await?;
+                                Ok(r)
+                            }
+                        },
+                    )
+                } else {
+                    (quote! {}, quote! {})
+                };
+
+            // A unique column matches at most one row, there is no first row to pick.
+            let by_doc = if column.column_properties.unique {
+                quote! {
+                    /// Returns the row holding `value`: the column is unique, so at most one row
+                    /// matches.
+                }
+            } else {
+                quote! {}
+            };
+
             let trait_code = quote! {
+                #by_doc
                 #signature;
+                /// Same as `by_{field}()`, failing with `MultipleRowsError` instead of returning
+                /// the first row when several rows match.
+                #unique_signature;
+                #lookup_trait_code
+                #count_grouped_trait_code
             };
 
             let impl_code = quote! {
@@ -59,6 +225,21 @@ pub fn generate_by(
                         .fetch_one(executor).await?;
                     Ok(r)
                 }
+
+                #unique_signature {
+                    let mut r = sqlx::query_as::<_, #struct_name>(#sql_unique_ident)
+                        .bind(#bind_value)
+                        .fetch_all(executor).await?;
+                    match r.len() {
+                        0 => Err(sqlx::Error::RowNotFound.into()),
+                        1 => Ok(r.remove(0)),
+                        _ => Err(lorm::errors::Error::MultipleRowsError(#multiple_rows_message.to_string())),
+                    }
+                }
+
+                #lookup_impl_code
+
+                #count_grouped_impl_code
             };
             (trait_code, impl_code)
         })
@@ -80,30 +261,15 @@ pub fn generate_by(
             let selector_ident = format_ident!("{}", model.pk_selector_name);
             let lifetime = quote! {'a};
 
-            let mut where_parts: Vec<String> = Vec::new();
-            let mut param_decls: Vec<TokenStream> = Vec::new();
-            let mut binds: Vec<TokenStream> = Vec::new();
-            let mut constraints: Vec<TokenStream> = Vec::new();
-
-            for (i, col) in pk_fields.iter().enumerate() {
-                let param_ident = &col.field;
-                let param_expr = quote! { #param_ident };
-                let (param_type, param_use) =
-                    get_bind_param_type_and_usage(&param_expr, &col.ty, &lifetime).unwrap();
-                let constraint =
-                    get_bind_type_where_constraint(&col.ty, database_type, &lifetime).unwrap();
-
-                param_decls.push(quote! { #param_ident: #param_type });
-                binds.push(quote! { .bind(#param_use) });
-                constraints.push(constraint);
-
-                let placeholder = db_placeholder(col.base_field, i + 1).unwrap();
-                where_parts.push(format!("{} = {}", col.column_name, placeholder));
-            }
-
-            let where_clause = where_parts.join(" AND ");
+            let PkBindings {
+                where_clause,
+                params: param_decls,
+                binds,
+                constraints,
+            } = pk_bindings(model, database_type, &lifetime, 1)?;
             let columns = model.full_column_select();
-            let sql_ident = format!("SELECT {columns} FROM {table_name} WHERE {where_clause}");
+            let sql_ident =
+                format!("SELECT {columns} FROM {table_name} WHERE {not_deleted}{where_clause}");
 
             let signature = quote! {
                 async fn #selector_ident<#lifetime>(executor: E, #(#param_decls),*) -> lorm::errors::Result<#struct_name> where #(#constraints),*
@@ -123,6 +289,286 @@ pub fn generate_by(
         }
     }
 
+    // exists_by_pk is always generated, whatever the kind of primary key.
+    {
+        let lifetime = quote! {'a};
+        let PkBindings {
+            where_clause,
+            params,
+            binds,
+            constraints,
+        } = pk_bindings(model, database_type, &lifetime, 1)?;
+        let sql =
+            format!("SELECT EXISTS(SELECT 1 FROM {table_name} WHERE {not_deleted}{where_clause})");
+        let fetch = exists_fetch(&sql, &quote! { #(#binds)* });
+
+        let signature = quote! {
+            async fn exists_by_pk<#lifetime>(executor: E, #(#params),*) -> lorm::errors::Result<bool> where #(#constraints),*
+        };
+        trait_tokens.push(quote! {
+            #signature;
+        });
+        impl_tokens.push(quote! {
+            #signature {
+                let r = #fetch;
+                Ok(r)
+            }
+        });
+    }
+
+    // by_pk_for_update is always generated too. SQLite has no row locks: a write transaction
+    // locks the whole database, so the lock is left out there.
+    {
+        let lifetime = quote! {'a};
+        let PkBindings {
+            where_clause,
+            params,
+            binds,
+            constraints,
+        } = pk_bindings(model, database_type, &lifetime, 1)?;
+        let lock = if cfg!(feature = "sqlite") {
+            ""
+        } else {
+            " FOR UPDATE"
+        };
+        let sql = format!(
+            "SELECT {} FROM {table_name} WHERE {not_deleted}{where_clause}{lock}",
+            model.full_column_select()
+        );
+
+        let signature = quote! {
+            async fn by_pk_for_update<#lifetime>(executor: E, #(#params),*) -> lorm::errors::Result<#struct_name> where #(#constraints),*
+        };
+        trait_tokens.push(quote! {
+            /// Fetches the row by primary key and locks it with `FOR UPDATE` until the end of the
+            /// transaction, for load-and-lock updates. Run it inside a transaction.
+            /// SQLite has no row locks, the row is only fetched.
+            #signature;
+        });
+        impl_tokens.push(quote! {
+            #signature {
+                let r = sqlx::query_as::<_, #struct_name>(#sql)
+                    #(#binds)*
+                    .fetch_one(executor).await?;
+                Ok(r)
+            }
+        });
+    }
+
+    // pluck_pks needs keys that sqlx can decode, as pluck_{a}_{b} does.
+    let pk_fields = model.primary_key.fields();
+    if pk_fields.iter().all(|col| col.is_decodable()) {
+        let pk_columns = pk_fields
+            .iter()
+            .map(|col| col.column_name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let sql = format!(
+            "SELECT {pk_columns} FROM {table_name}{where_not_deleted} ORDER BY {pk_columns}"
+        );
+        let pk_types = pk_fields.iter().map(|col| &col.ty).collect::<Vec<_>>();
+        let (pk_type, fetch) = if let [ty] = pk_types.as_slice() {
+            (
+                quote! { #ty },
+                quote! { sqlx::query_scalar::<_, #ty>(#sql) },
+            )
+        } else {
+            (
+                quote! { (#(#pk_types),*) },
+                quote! { sqlx::query_as::<_, (#(#pk_types),*)>(#sql) },
+            )
+        };
+
+        let signature = quote! {
+            async fn pluck_pks(executor: E) -> lorm::errors::Result<Vec<#pk_type>>
+        };
+        trait_tokens.push(quote! {
+            #signature;
+        });
+        impl_tokens.push(quote! {
+            #signature {
+                let r = #fetch.fetch_all(executor).await?;
+                Ok(r)
+            }
+        });
+    }
+
+    // max_pk/min_pk on single keys; PostgreSQL has no MAX()/MIN() for uuid.
+    if let [pk_col] = pk_fields
+        && pk_col.is_decodable()
+        && !is_uuid_type(&pk_col.ty)
+    {
+        let ty = &pk_col.ty;
+        for (fn_name, function) in [("max_pk", "MAX"), ("min_pk", "MIN")] {
+            let fn_ident = format_ident!("{}", fn_name);
+            let sql = format!(
+                "SELECT {function}({}) FROM {table_name}{where_not_deleted}",
+                pk_col.column_name
+            );
+            let signature = quote! {
+                async fn #fn_ident(executor: E) -> lorm::errors::Result<Option<#ty>>
+            };
+            trait_tokens.push(quote! {
+                /// Returns `None` when the table is empty.
+                #signature;
+            });
+            impl_tokens.push(quote! {
+                #signature {
+                    let r = sqlx::query_scalar::<_, Option<#ty>>(#sql).fetch_one(executor).await?;
+                    Ok(r)
+                }
+            });
+        }
+    }
+
+    // reload_many() selects the rows of every item by pk and replaces the items in place, in a
+    // trait of its own over `sqlx::Acquire` to run one query per chunk of items on one connection.
+    let reload_many_code = {
+        let reload_trait_ident = format_ident!("{}ReloadManyTrait", struct_name);
+        // Every item binds one parameter per primary key field.
+        let max_items_per_query = (max_bind_parameters() / pk_fields.len()).max(1);
+        let item_var = quote! {item};
+        let row_var = quote! {row};
+        // A single key is matched with IN, a composite one with an OR of conditions per item.
+        let pk_filter = if let [pk_col] = pk_fields {
+            let value = pk_col.bind_value(pk_col.accessor_on(&item_var));
+            let in_head = format!("{} IN (", pk_col.column_name);
+            quote! {
+                builder.push(#in_head);
+                let mut separated = builder.separated(", ");
+                for item in items.iter() {
+                    separated.push_bind(#value);
+                }
+                builder.push(")");
+            }
+        } else {
+            let pk_conditions = pk_fields.iter().enumerate().map(|(i, col)| {
+                let head = if i == 0 {
+                    format!("{} = ", col.column_name)
+                } else {
+                    format!(" AND {} = ", col.column_name)
+                };
+                let value = col.bind_value(col.accessor_on(&item_var));
+                quote! {
+                    builder.push(#head);
+                    builder.push_bind(#value);
+                }
+            });
+            quote! {
+                for (i, item) in items.iter().enumerate() {
+                    builder.push(if i == 0 { "((" } else { " OR (" });
+                    #(#pk_conditions)*
+                    builder.push(")");
+                }
+                builder.push(")");
+            }
+        };
+        let pk_matches = pk_fields.iter().map(|col| {
+            let item_value = col.accessor_on(&item_var);
+            let row_value = col.accessor_on(&row_var);
+            quote! { (#item_value) == (#row_value) }
+        });
+        let head = format!(
+            "SELECT {} FROM {table_name} WHERE {not_deleted}",
+            model.full_column_select()
+        );
+
+        let signature = quote! {
+            async fn reload_many(conn: A, items: &mut [#struct_name]) -> lorm::errors::Result<usize>
+        };
+        quote! {
+            #struct_visibility trait #reload_trait_ident<'c, A: sqlx::Acquire<'c, Database = #database_type> + Send>: Sized {
+                /// Reads the rows of `items` back, with a query per chunk of items, replacing each
+                /// item by its row. Items whose row no longer exists, or is soft-deleted, are left
+                /// unchanged. Returns the number of reloaded items.
+                #signature;
+            }
+
+            #[automatically_derived]
+            impl<'c, A: sqlx::Acquire<'c, Database = #database_type> + Send> #reload_trait_ident<'c, A> for #struct_name {
+                #signature {
+                    if items.is_empty() {
+                        return Ok(0);
+                    }
+                    let mut conn = conn.acquire().await?;
+                    let mut reloaded = 0;
+                    for items in items.chunks_mut(#max_items_per_query) {
+                        let mut rows = {
+                            let mut builder = sqlx::QueryBuilder::new(#head);
+                            #pk_filter
+                            builder.build_query_as::<#struct_name>().fetch_all(&mut *conn).await?
+                        };
+                        for item in items.iter_mut() {
+                            if let Some(position) = rows.iter().position(|row| #(#pk_matches)&&*) {
+                                *item = rows.swap_remove(position);
+                                reloaded += 1;
+                            }
+                        }
+                    }
+                    Ok(reloaded)
+                }
+            }
+        }
+    };
+
+    // Composite keys also get a {Struct}Key value, fetched with by_composite_key().
+    let mut key_code = quote! {};
+    if pk_fields.len() > 1 && pk_fields.iter().all(|col| !col.is_flattened) {
+        let key_ident = format_ident!("{}Key", struct_name);
+        let key_doc = format!(" Composite primary key of [`{struct_name}`].");
+        let key_fields = pk_fields.iter().map(|col| {
+            let field = &col.field;
+            let ty = &col.ty;
+            quote! { pub #field: #ty }
+        });
+        let key_inits = pk_fields.iter().map(|col| {
+            let field = &col.field;
+            let accessor = col.self_accessor();
+            quote! { #field: (#accessor).clone() }
+        });
+        let PkBindings { where_clause, .. } = pk_bindings(model, database_type, &quote! {'a}, 1)?;
+        let sql = format!(
+            "SELECT {} FROM {table_name} WHERE {not_deleted}{where_clause}",
+            model.full_column_select()
+        );
+        let binds = pk_fields.iter().map(|col| {
+            let value = col.bind_value(col.accessor_on(&quote! {key}));
+            quote! { .bind(#value) }
+        });
+
+        let signature = quote! {
+            async fn by_composite_key(executor: E, key: &#key_ident) -> lorm::errors::Result<#struct_name>
+        };
+        trait_tokens.push(quote! {
+            #signature;
+        });
+        impl_tokens.push(quote! {
+            #signature {
+                let r = sqlx::query_as::<_, #struct_name>(#sql)
+                    #(#binds)*
+                    .fetch_one(executor).await?;
+                Ok(r)
+            }
+        });
+        key_code = quote! {
+            #[doc = #key_doc]
+            #[derive(Debug, Clone, PartialEq)]
+            #struct_visibility struct #key_ident {
+                #(#key_fields),*
+            }
+
+            #[automatically_derived]
+            impl #struct_name {
+                /// The composite primary key of this row.
+                #struct_visibility fn key(&self) -> #key_ident {
+                    #key_ident {
+                        #(#key_inits),*
+                    }
+                }
+            }
+        };
+    }
+
     Ok(quote! {
         #struct_visibility trait #trait_ident<'e, E: #executor_type>: Sized {
             #(#trait_tokens)*
@@ -132,5 +578,60 @@ pub fn generate_by(
         impl<'e, E: #executor_type> #trait_ident<'e, E> for #struct_name {
             #(#impl_tokens)*
         }
+
+        #reload_many_code
+
+        #key_code
+    })
+}
+
+/// Runs a `SELECT EXISTS(...)` query with its binds, evaluating to a `bool`.
+fn exists_fetch(sql: &str, binds: &TokenStream) -> TokenStream {
+    // Postgres has a real boolean, SQLite and MySQL return an integer.
+    if cfg!(feature = "postgres") {
+        quote! { sqlx::query_scalar::<_, bool>(#sql) #binds .fetch_one(executor).await? }
+    } else {
+        quote! { sqlx::query_scalar::<_, i64>(#sql) #binds .fetch_one(executor).await? != 0 }
+    }
+}
+
+/// Primary key parameters, binds and `WHERE` clause shared by the methods addressing a row by key.
+pub(crate) struct PkBindings {
+    pub(crate) where_clause: String,
+    pub(crate) params: Vec<TokenStream>,
+    pub(crate) binds: Vec<TokenStream>,
+    pub(crate) constraints: Vec<TokenStream>,
+}
+
+/// Builds one parameter per primary key field, numbering placeholders from `first_placeholder`.
+pub(crate) fn pk_bindings(
+    model: &OrmModel,
+    database_type: &TokenStream,
+    lifetime: &TokenStream,
+    first_placeholder: usize,
+) -> syn::Result<PkBindings> {
+    let mut where_parts: Vec<String> = Vec::new();
+    let mut params: Vec<TokenStream> = Vec::new();
+    let mut binds: Vec<TokenStream> = Vec::new();
+    let mut constraints: Vec<TokenStream> = Vec::new();
+
+    for (i, col) in model.primary_key.fields().iter().enumerate() {
+        let param_ident = &col.field;
+        let param_expr = quote! { #param_ident };
+        let (param_type, param_use) = col.bind_param(&param_expr, lifetime)?;
+
+        params.push(quote! { #param_ident: #param_type });
+        binds.push(quote! { .bind(#param_use) });
+        constraints.push(col.bind_constraint(database_type, lifetime)?);
+
+        let placeholder = db_placeholder(col.base_field, first_placeholder + i)?;
+        where_parts.push(format!("{} = {}", col.column_name, placeholder));
+    }
+
+    Ok(PkBindings {
+        where_clause: where_parts.join(" AND "),
+        params,
+        binds,
+        constraints,
     })
 }
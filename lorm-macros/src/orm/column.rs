@@ -1,11 +1,14 @@
 use crate::attributes::ColumnProperties;
-use crate::utils::is_option_wrapped;
+use crate::utils::{
+    get_bind_param_type_and_usage, get_bind_type_where_constraint, is_option_wrapped,
+    is_primitive_type, is_shared_str_type, to_column_type,
+};
 use quote::__private::TokenStream;
 use quote::{ToTokens, quote};
 use syn::Field;
 use syn::Ident;
 use syn::Type;
-use syn::parse;
+use syn::parse2;
 
 pub(crate) struct Column<'a> {
     pub(crate) base_field: &'a Field,
@@ -20,16 +23,80 @@ pub(crate) struct Column<'a> {
 impl<'a> Column<'a> {
     /// Generate the token stream to access the field on `self`.
     pub(crate) fn self_accessor(&self) -> TokenStream {
+        self.accessor_on(&quote! {self})
+    }
+
+    /// Generate the token stream to access the field on an arbitrary instance expression.
+    pub(crate) fn accessor_on(&self, instance: &TokenStream) -> TokenStream {
         let base_ident = self.base_field.ident.as_ref().unwrap();
         if self.is_flattened {
             let field_ident = &self.field;
             if is_option_wrapped(&self.base_field.ty) {
-                quote! {self.#base_ident.as_ref().map(|base| &base.#field_ident)}
+                quote! {#instance.#base_ident.as_ref().map(|base| &base.#field_ident)}
             } else {
-                quote! {&self.#base_ident.#field_ident}
+                quote! {&#instance.#base_ident.#field_ident}
             }
         } else {
-            quote! {&self.#base_ident}
+            quote! {&#instance.#base_ident}
+        }
+    }
+
+    /// Turns an expression borrowing the field value (e.g. an accessor) into the value to bind.
+    pub(crate) fn bind_value(&self, value: TokenStream) -> TokenStream {
+        if self.column_properties.store_as_text {
+            quote! { (#value).to_string() }
+        } else if is_shared_str_type(&self.ty) {
+            if is_option_wrapped(&self.ty) {
+                quote! { (#value).as_ref().map(|v| v.to_string()) }
+            } else {
+                quote! { (#value).to_string() }
+            }
+        } else {
+            value
+        }
+    }
+
+    /// Whether sqlx can decode the column into the field type on its own, e.g. to pluck it.
+    /// Values stored as text and shared strings are decoded by the model's `FromRow` only.
+    pub(crate) fn is_decodable(&self) -> bool {
+        !self.column_properties.store_as_text && !is_shared_str_type(&self.ty)
+    }
+
+    /// Same as [get_bind_param_type_and_usage], binding the decimal string of `store_as = "text"` columns.
+    pub(crate) fn bind_param(
+        &self,
+        param: &TokenStream,
+        encode_lifetime: &TokenStream,
+    ) -> syn::Result<(TokenStream, TokenStream)> {
+        let (param_type, param_use) =
+            get_bind_param_type_and_usage(param, &self.ty, encode_lifetime)?;
+        if !self.column_properties.store_as_text {
+            return Ok((param_type, param_use));
+        }
+        let base_type = to_column_type(&self.ty)?;
+        let value_type = if is_primitive_type(&base_type) {
+            quote! { #base_type }
+        } else {
+            quote! { &#base_type }
+        };
+        Ok((
+            param_type,
+            quote! { { let v: #value_type = #param_use; v.to_string() } },
+        ))
+    }
+
+    /// Same as [get_bind_type_where_constraint], constraining `String` for `store_as = "text"` columns.
+    pub(crate) fn bind_constraint(
+        &self,
+        database_type: &TokenStream,
+        encode_lifetime: &TokenStream,
+    ) -> syn::Result<TokenStream> {
+        if self.column_properties.store_as_text {
+            Ok(
+                quote! { String: sqlx::Encode<#encode_lifetime, #database_type> + sqlx::Type<#database_type> },
+            )
+        } else {
+            get_bind_type_where_constraint(&self.ty, database_type, encode_lifetime)
         }
     }
 
@@ -54,7 +121,7 @@ impl<'a> Clone for Column<'a> {
         Column {
             base_field: self.base_field,
             field: self.field.clone(),
-            ty: parse((&self.ty).into_token_stream().into()).unwrap(),
+            ty: parse2((&self.ty).into_token_stream()).unwrap(),
             column_name: self.column_name.clone(),
             is_flattened: self.is_flattened,
             column_properties: self.column_properties.clone(),
@@ -88,13 +155,19 @@ mod tests {
         ColumnProperties {
             skip: false,
             readonly: false,
+            db_managed: false,
             primary_key: false,
+            auto_increment: false,
             generate_by: false,
+            unique: false,
             created_at: false,
             updated_at: false,
+            soft_delete: false,
+            version: false,
             new_expression: parse_str("Default::default()").unwrap(),
             is_set_expression: None,
             use_json: false,
+            store_as_text: false,
             belongs_to_target: None,
         }
     }
@@ -1,40 +1,413 @@
 use crate::models::OrmModel;
-use crate::utils::db_placeholder;
+use crate::orm::select::{generate_where_exists, generate_where_methods, generate_where_support};
+use crate::utils::{db_placeholder, is_primitive_type, max_bind_parameters, to_column_type};
 use quote::{__private::TokenStream, format_ident, quote};
 
-pub fn generate_delete(executor_type: &TokenStream, model: &OrmModel) -> syn::Result<TokenStream> {
+pub fn generate_delete(
+    executor_type: &TokenStream,
+    database_type: &TokenStream,
+    model: &OrmModel,
+) -> syn::Result<TokenStream> {
     let trait_ident = format_ident!("{}DeleteTrait", model.struct_name);
+    let where_trait_ident = format_ident!("{}DeleteWhereTrait", model.struct_name);
+    let builder_struct_ident = format_ident!("{}DeleteBuilder", model.struct_name);
     let struct_name = model.struct_name;
     let struct_visibility = model.struct_visibility;
     let table_name = &model.table_name;
 
-    // Primary key(s)
+    // Primary key(s), numbered after the deletion time of a soft delete.
     let pk_fields = model.primary_key.fields();
+    let soft_delete = model.soft_delete();
+    let first_placeholder = if soft_delete.is_some() { 2 } else { 1 };
     let mut where_parts = Vec::new();
     let mut bind_values = Vec::new();
 
     for (i, pk_col) in pk_fields.iter().enumerate() {
-        let placeholder = db_placeholder(pk_col.base_field, i + 1)?;
+        let placeholder = db_placeholder(pk_col.base_field, first_placeholder + i)?;
         where_parts.push(format!("{} = {}", pk_col.column_name, placeholder));
-        let accessor = pk_col.self_accessor();
-        bind_values.push(quote! { .bind(#accessor) });
+        let value = pk_col.bind_value(pk_col.self_accessor());
+        bind_values.push(quote! { .bind(#value) });
     }
 
     let where_clause = where_parts.join(" AND ");
     let sql_ident = format!("DELETE FROM {table_name} WHERE {where_clause}");
 
+    // With a soft_delete column, delete() stamps the row and hard_delete() removes it. A row
+    // deleted already keeps its first deletion time.
+    let (delete_code, hard_delete_trait_code, hard_delete_impl_code) = match soft_delete {
+        Some(column) => {
+            let hard_where_clause = pk_fields
+                .iter()
+                .enumerate()
+                .map(|(i, pk_col)| {
+                    Ok(format!(
+                        "{} = {}",
+                        pk_col.column_name,
+                        db_placeholder(pk_col.base_field, i + 1)?
+                    ))
+                })
+                .collect::<syn::Result<Vec<_>>>()?
+                .join(" AND ");
+            let hard_sql = format!("DELETE FROM {table_name} WHERE {hard_where_clause}");
+            let soft_sql = format!(
+                "UPDATE {table_name} SET {0} = {1} WHERE {where_clause} AND {0} IS NULL",
+                column.column_name,
+                db_placeholder(column.base_field, 1)?
+            );
+            let new_method = &column.column_properties.new_expression;
+            (
+                quote! {
+                    sqlx::query(#soft_sql)
+                    .bind(#new_method)
+                    #(#bind_values)*
+                    .execute(executor).await?;
+                    Ok(())
+                },
+                quote! {
+                    /// Removes the row for good, where `delete()` only marks it as deleted.
+                    async fn hard_delete(&self, executor: E) -> lorm::errors::Result<()>;
+                },
+                quote! {
+                    async fn hard_delete(&self, executor: E) -> lorm::errors::Result<()> {
+                        sqlx::query(#hard_sql)
+                        #(#bind_values)*
+                        .execute(executor).await?;
+                        Ok(())
+                    }
+                },
+            )
+        }
+        None => (
+            quote! {
+                sqlx::query(#sql_ident)
+                #(#bind_values)*
+                .execute(executor).await?;
+                Ok(())
+            },
+            quote! {},
+            quote! {},
+        ),
+    };
+    let delete_head = format!("DELETE FROM {table_name}");
+
+    let lifetime = quote! {'a};
+
+    // With a soft_delete column, delete_where() stamps the matching rows not deleted yet and
+    // hard_delete_where() returns a builder removing them.
+    let (hard_field, hard_init, hard_where_trait_code, hard_where_impl_code, delete_where_execute) =
+        match soft_delete {
+            Some(column) => {
+                let soft_head = format!("UPDATE {table_name} SET {} = ", column.column_name);
+                let soft_where = format!(" WHERE {} IS NULL", column.column_name);
+                let new_method = &column.column_properties.new_expression;
+                (
+                    quote! { hard: bool, },
+                    quote! { hard: false, },
+                    quote! {
+                        /// Same as `delete_where()`, removing the matching rows for good, soft-deleted
+                        /// or not.
+                        fn hard_delete_where() -> #builder_struct_ident<#lifetime>;
+                    },
+                    quote! {
+                        fn hard_delete_where() -> #builder_struct_ident<#lifetime> {
+                            let mut builder = #builder_struct_ident::new();
+                            builder.hard = true;
+                            builder
+                        }
+                    },
+                    quote! {
+                        let mut builder = if self.hard {
+                            let mut builder = sqlx::QueryBuilder::new(#delete_head);
+                            for fragment in self.fragments {
+                                fragment(&mut builder);
+                            }
+                            builder
+                        } else {
+                            let mut builder = sqlx::QueryBuilder::new(#soft_head);
+                            builder.push_bind(#new_method);
+                            builder.push(#soft_where);
+                            if self.is_where {
+                                // The conditions are grouped after the soft-delete one, in place of
+                                // the ` WHERE` fragment pushed before the first of them.
+                                builder.push(" AND (");
+                                for fragment in self.fragments.into_iter().skip(1) {
+                                    fragment(&mut builder);
+                                }
+                                builder.push(" )");
+                            }
+                            builder
+                        };
+                    },
+                )
+            }
+            None => (
+                quote! {},
+                quote! {},
+                quote! {},
+                quote! {},
+                quote! {
+                    let mut builder = sqlx::QueryBuilder::new(#delete_head);
+                    for fragment in self.fragments {
+                        fragment(&mut builder);
+                    }
+                },
+            ),
+        };
+
+    // The DELETE statement never uses the table alias, columns are left unqualified.
+    let where_tokens = generate_where_methods(model, database_type, false)?;
+    let where_support = generate_where_support(struct_visibility, "delete builder", "execute()");
+    let exists_tokens = generate_where_exists(struct_visibility, executor_type, table_name);
+
+    // TRUNCATE ... CASCADE is PostgreSQL only.
+    let (truncate_trait_code, truncate_impl_code) = if cfg!(feature = "postgres") {
+        let truncate_sql = format!("TRUNCATE TABLE {table_name} CASCADE");
+        let signature = quote! {
+            async fn truncate_cascade(executor: E) -> lorm::errors::Result<()>
+        };
+        (
+            quote! {
+                /// Empties the table with `TRUNCATE ... CASCADE`. Destructive: every table with a
+                /// foreign key to this one is truncated as well, whatever its rows reference.
+                #signature;
+            },
+            quote! {
+                #signature {
+                    sqlx::query(#truncate_sql).execute(executor).await?;
+                    Ok(())
+                }
+            },
+        )
+    } else {
+        (quote! {}, quote! {})
+    };
+
+    // delete_many() takes the keys themselves, which only single primary keys have. It runs one
+    // statement per chunk of keys, in a trait of its own over `sqlx::Acquire` for the transaction.
+    let delete_many_code = match pk_fields {
+        [pk_col] if !pk_col.is_flattened => {
+            let base_type = to_column_type(&pk_col.ty)?;
+            let is_primitive = is_primitive_type(&base_type);
+            let item_type = if is_primitive {
+                quote! { #base_type }
+            } else {
+                quote! { impl std::borrow::Borrow<#base_type> }
+            };
+            let item_value = if pk_col.column_properties.store_as_text {
+                quote! { id.to_string() }
+            } else if is_primitive {
+                quote! { *id }
+            } else {
+                quote! { id.borrow() }
+            };
+            let constraints = pk_col.bind_constraint(database_type, &lifetime)?;
+            let delete_many_trait_ident = format_ident!("{}DeleteManyTrait", struct_name);
+            let hard_head = format!("DELETE FROM {table_name} WHERE {} IN (", pk_col.column_name);
+            let delete_many_signature = quote! {
+                async fn delete_many<#lifetime>(conn: A, ids: &#lifetime [#item_type]) -> lorm::errors::Result<u64> where #constraints
+            };
+            let hard_delete_many_signature = quote! {
+                async fn hard_delete_many<#lifetime>(conn: A, ids: &#lifetime [#item_type]) -> lorm::errors::Result<u64> where #constraints
+            };
+            // Runs a statement per chunk of keys inside one transaction, every key binding one
+            // parameter after those of `head`.
+            let chunked_body = |head: TokenStream, head_binds: usize| {
+                let max_ids_per_statement = max_bind_parameters() - head_binds;
+                quote! {
+                    if ids.is_empty() {
+                        return Ok(0);
+                    }
+                    let mut tx = conn.begin().await?;
+                    let mut deleted = 0;
+                    for ids in ids.chunks(#max_ids_per_statement) {
+                        #head
+                        let mut separated = query.separated(", ");
+                        for id in ids {
+                            separated.push_bind(#item_value);
+                        }
+                        query.push(")");
+                        deleted += query.build().execute(&mut *tx).await?.rows_affected();
+                    }
+                    tx.commit().await?;
+                    Ok(deleted)
+                }
+            };
+            let hard_body = chunked_body(
+                quote! { let mut query = sqlx::QueryBuilder::new(#hard_head); },
+                0,
+            );
+            // With a soft_delete column, delete_many() stamps the rows not deleted yet, as delete()
+            // does, and hard_delete_many() removes them.
+            let (
+                delete_many_doc,
+                delete_many_body,
+                hard_delete_many_trait_code,
+                hard_delete_many_impl_code,
+            ) = match soft_delete {
+                Some(column) => {
+                    let soft_head = format!("UPDATE {table_name} SET {} = ", column.column_name);
+                    let soft_where = format!(
+                        " WHERE {0} IS NULL AND {1} IN (",
+                        column.column_name, pk_col.column_name
+                    );
+                    let new_method = &column.column_properties.new_expression;
+                    let soft_body = chunked_body(
+                        quote! {
+                            let mut query = sqlx::QueryBuilder::new(#soft_head);
+                            query.push_bind(#new_method);
+                            query.push(#soft_where);
+                        },
+                        1,
+                    );
+                    (
+                        quote! {
+                            /// Marks the rows with these primary keys as deleted and returns the
+                            /// number of rows marked. Runs a statement per chunk of keys inside one
+                            /// transaction. An empty slice does not query the database.
+                        },
+                        soft_body,
+                        quote! {
+                            /// Removes the rows with these primary keys for good, soft-deleted or
+                            /// not, and returns the number of removed rows.
+                            #hard_delete_many_signature;
+                        },
+                        quote! {
+                            #hard_delete_many_signature {
+                                #hard_body
+                            }
+                        },
+                    )
+                }
+                None => (
+                    quote! {
+                        /// Deletes the rows with these primary keys and returns the number of deleted
+                        /// rows. Runs a statement per chunk of keys inside one transaction, so either
+                        /// every row is deleted or none is. An empty slice does not query the database.
+                    },
+                    hard_body,
+                    quote! {},
+                    quote! {},
+                ),
+            };
+            quote! {
+                #struct_visibility trait #delete_many_trait_ident<'c, A: sqlx::Acquire<'c, Database = #database_type> + Send>: Sized {
+                    #delete_many_doc
+                    #delete_many_signature;
+                    #hard_delete_many_trait_code
+                }
+
+                #[automatically_derived]
+                impl<'c, A: sqlx::Acquire<'c, Database = #database_type> + Send> #delete_many_trait_ident<'c, A> for #struct_name {
+                    #delete_many_signature {
+                        #delete_many_body
+                    }
+
+                    #hard_delete_many_impl_code
+                }
+            }
+        }
+        _ => quote! {},
+    };
+
     Ok(quote! {
         #struct_visibility trait #trait_ident<'e, E: #executor_type>: Sized {
             async fn delete(&self, executor: E) -> lorm::errors::Result<()>;
+            #hard_delete_trait_code
+            #truncate_trait_code
         }
 
         #[automatically_derived]
         impl<'e, E: #executor_type> #trait_ident<'e, E> for #struct_name {
             async fn delete(&self, executor: E) -> lorm::errors::Result<()> {
-                sqlx::query(#sql_ident)
-                #(#bind_values)*
-                .execute(executor).await?;
-                Ok(())
+                #delete_code
+            }
+
+            #hard_delete_impl_code
+
+            #truncate_impl_code
+        }
+
+        #delete_many_code
+
+        #struct_visibility trait #where_trait_ident<#lifetime> {
+            fn delete_where() -> #builder_struct_ident<#lifetime>;
+            #hard_where_trait_code
+        }
+
+        #[automatically_derived]
+        impl<#lifetime> #where_trait_ident<#lifetime> for #struct_name {
+            fn delete_where() -> #builder_struct_ident<#lifetime> {
+                #builder_struct_ident::new()
+            }
+
+            #hard_where_impl_code
+        }
+
+        #struct_visibility struct #builder_struct_ident<#lifetime> {
+            fragments: Vec<lorm::query::SqlFragment<#lifetime, #database_type>>,
+            is_where: bool,
+            group_depth: i32,
+            pending_groups: u32,
+            is_or: bool,
+            preparation_error: Option<String>,
+            delete_all: bool,
+            #hard_field
+        }
+
+        #[automatically_derived]
+        impl<#lifetime> #builder_struct_ident<#lifetime> {
+            fn new() -> Self {
+                Self {
+                    fragments: Vec::new(),
+                    is_where: false,
+                    group_depth: 0,
+                    pending_groups: 0,
+                    is_or: false,
+                    preparation_error: None,
+                    delete_all: false,
+                    #hard_init
+                }
+            }
+
+            fn push(&mut self, sql: impl std::fmt::Display) {
+                self.fragments.push(lorm::query::sql_fragment(sql.to_string()));
+            }
+
+            fn push_bind<T>(&mut self, value: T)
+            where
+                T: sqlx::Encode<#lifetime, #database_type> + sqlx::Type<#database_type> + std::fmt::Debug + Clone + Send + Sync + #lifetime,
+            {
+                self.fragments.push(lorm::query::bind_fragment(value));
+            }
+
+            #where_support
+
+            #(#where_tokens)*
+
+            #exists_tokens
+
+            /// Allows `execute()` without any condition, deleting every row of the table.
+            #struct_visibility fn all(mut self) -> Self {
+                self.delete_all = true;
+                self
+            }
+
+            /// Runs the `DELETE` and returns the number of deleted rows, or marks the rows as
+            /// deleted with a `soft_delete` field. Refuses to run without any condition unless
+            /// `all()` was called.
+            #struct_visibility async fn execute<'e, E: #executor_type>(self, executor: E) -> lorm::errors::Result<u64> {
+                if let Some(e) = self.preparation_error {
+                    return Err(lorm::errors::Error::QueryPreparationError(e));
+                }
+                self.check_balanced_groups()?;
+                if !self.is_where && !self.delete_all {
+                    return Err(lorm::errors::Error::QueryPreparationError(
+                        "delete_where() without any condition, call all() to delete every row".to_string(),
+                    ));
+                }
+                #delete_where_execute
+                let r = builder.build().execute(executor).await?;
+                Ok(r.rows_affected())
             }
         }
     })
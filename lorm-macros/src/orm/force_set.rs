@@ -0,0 +1,87 @@
+use crate::models::OrmModel;
+use crate::orm::by::{PkBindings, pk_bindings};
+use crate::utils::{db_placeholder, to_column_type};
+use quote::{__private::TokenStream, format_ident, quote};
+
+/// Generates `force_set_{field}(executor, pk..., value)` for every readonly non-pk column.
+///
+/// Readonly columns are never written by `save()`; these methods are the explicit escape hatch
+/// (e.g. to backfill a `created_at`) and issue a direct `UPDATE` by primary key.
+pub fn generate_force_set(
+    executor_type: &TokenStream,
+    database_type: &TokenStream,
+    model: &OrmModel,
+) -> syn::Result<TokenStream> {
+    let trait_ident = format_ident!("{}ForceSetTrait", model.struct_name);
+    let struct_name = model.struct_name;
+    let struct_visibility = model.struct_visibility;
+    let table_name = &model.table_name;
+    let lifetime = quote! {'a};
+
+    let readonly_columns = model
+        .columns
+        .iter()
+        .filter(|c| c.column_properties.readonly && !c.column_properties.primary_key)
+        .collect::<Vec<_>>();
+    if readonly_columns.is_empty() {
+        return Ok(quote! {});
+    }
+
+    let PkBindings {
+        where_clause,
+        params: pk_params,
+        binds: pk_binds,
+        constraints: pk_constraints,
+    } = pk_bindings(model, database_type, &lifetime, 2)?;
+
+    let mut trait_tokens = Vec::new();
+    let mut impl_tokens = Vec::new();
+    for column in readonly_columns {
+        let force_set_fn = format_ident!("force_set_{}", column.field);
+        let parameter = quote! {value};
+        let (param_type, param_use) = column.bind_param(&parameter, &lifetime)?;
+        let (value_constraint, bind_value) = if column.column_properties.use_json {
+            let base_type = to_column_type(&column.ty)?;
+            (
+                quote! { #base_type: serde::Serialize },
+                quote! { sqlx::types::Json(#param_use) },
+            )
+        } else {
+            (column.bind_constraint(database_type, &lifetime)?, param_use)
+        };
+
+        let sql = format!(
+            "UPDATE {table_name} SET {} = {} WHERE {where_clause}",
+            column.column_name,
+            db_placeholder(column.base_field, 1)?
+        );
+
+        let signature = quote! {
+            async fn #force_set_fn<#lifetime>(executor: E, #(#pk_params,)* #parameter: #param_type) -> lorm::errors::Result<u64>
+            where #value_constraint, #(#pk_constraints),*
+        };
+        trait_tokens.push(quote! {
+            #signature;
+        });
+        impl_tokens.push(quote! {
+            #signature {
+                let r = sqlx::query(#sql)
+                    .bind(#bind_value)
+                    #(#pk_binds)*
+                    .execute(executor).await?;
+                Ok(r.rows_affected())
+            }
+        });
+    }
+
+    Ok(quote! {
+        #struct_visibility trait #trait_ident<'e, E: #executor_type>: Sized {
+            #(#trait_tokens)*
+        }
+
+        #[automatically_derived]
+        impl<'e, E: #executor_type> #trait_ident<'e, E> for #struct_name {
+            #(#impl_tokens)*
+        }
+    })
+}
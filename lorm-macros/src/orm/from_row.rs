@@ -0,0 +1,123 @@
+use crate::models::OrmModel;
+use crate::utils::{is_option_wrapped, is_shared_str_type, to_column_type};
+use quote::{__private::TokenStream, quote};
+
+/// Implements `sqlx::FromRow` for models with `#[lorm(transient)]` fields, which sqlx's derive
+/// can only skip when their type implements `Default`, or with `store_as = "text"` columns, which
+/// it cannot parse.
+///
+/// Columns are read by name, transient and `#[sqlx(skip)]` fields are set to their initializer.
+pub fn generate_from_row(
+    database_type: &TokenStream,
+    model: &OrmModel,
+) -> syn::Result<TokenStream> {
+    if !model.implements_from_row {
+        return Ok(quote! {});
+    }
+    if model.columns.iter().any(|c| c.is_flattened) {
+        return Err(syn::Error::new(
+            model.struct_name.span(),
+            "#[lorm(transient)] fields and `store_as = \"text\"` columns cannot be combined with #[sqlx(flatten)] fields, implement `sqlx::FromRow` by hand instead.",
+        ));
+    }
+
+    let struct_name = model.struct_name;
+    let field_inits = field_initializers(model, |column_name| quote! { #column_name })?;
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl<'r> sqlx::FromRow<'r, <#database_type as sqlx::Database>::Row> for #struct_name {
+            fn from_row(row: &'r <#database_type as sqlx::Database>::Row) -> Result<Self, sqlx::Error> {
+                use sqlx::Row;
+                Ok(Self {
+                    #(#field_inits)*
+                })
+            }
+        }
+    })
+}
+
+/// Implements `lorm::query::JoinTarget`, reading the columns under the prefix of the join.
+///
+/// Models with flattened fields are left out, they cannot be joined.
+pub fn generate_join_target(
+    database_type: &TokenStream,
+    model: &OrmModel,
+) -> syn::Result<TokenStream> {
+    if model.columns.iter().any(|c| c.is_flattened) {
+        return Ok(quote! {});
+    }
+
+    let struct_name = model.struct_name;
+    let table_name = &model.table_name;
+    let primary_key = &model.primary_key().generated_column().column_name;
+    let column_names = model.columns.iter().map(|c| &c.column_name);
+    let field_inits = field_initializers(
+        model,
+        |column_name| quote! { format!("{prefix}{}", #column_name).as_str() },
+    )?;
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl lorm::query::JoinTarget<#database_type> for #struct_name {
+            const TABLE_NAME: &'static str = #table_name;
+            const PRIMARY_KEY: &'static str = #primary_key;
+            const COLUMNS: &'static [&'static str] = &[#(#column_names),*];
+
+            fn from_prefixed_row(row: &<#database_type as sqlx::Database>::Row, prefix: &str) -> Result<Self, sqlx::Error> {
+                use sqlx::Row;
+                Ok(Self {
+                    #(#field_inits)*
+                })
+            }
+        }
+    })
+}
+
+/// The field initializers of the model, reading each column from `row` under the name given by
+/// `column_index`, followed by the transient fields.
+fn field_initializers(
+    model: &OrmModel,
+    column_index: impl Fn(&String) -> TokenStream,
+) -> syn::Result<Vec<TokenStream>> {
+    let mut inits = model
+        .columns
+        .iter()
+        .map(|column| {
+            let field = &column.field;
+            let column_name = &column.column_name;
+            let index = column_index(column_name);
+            let value = if column.column_properties.use_json {
+                let base_type = to_column_type(&column.ty)?;
+                if is_option_wrapped(&column.ty) {
+                    quote! { row.try_get::<Option<sqlx::types::Json<#base_type>>, _>(#index)?.map(|v| v.0) }
+                } else {
+                    quote! { row.try_get::<sqlx::types::Json<#base_type>, _>(#index)?.0 }
+                }
+            } else if column.column_properties.store_as_text {
+                quote! {
+                    row.try_get::<String, _>(#index)?
+                        .parse()
+                        .map_err(|e| sqlx::Error::ColumnDecode {
+                            index: #column_name.to_string(),
+                            source: Box::new(e),
+                        })?
+                }
+            } else if is_shared_str_type(&column.ty) {
+                if is_option_wrapped(&column.ty) {
+                    quote! { row.try_get::<Option<String>, _>(#index)?.map(Into::into) }
+                } else {
+                    quote! { row.try_get::<String, _>(#index)?.into() }
+                }
+            } else {
+                quote! { row.try_get(#index)? }
+            };
+            Ok(quote! { #field: #value, })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+    inits.extend(model.transient_fields.iter().map(|(field, initializer)| {
+        let ident = &field.ident;
+        quote! { #ident: #initializer, }
+    }));
+    Ok(inits)
+}
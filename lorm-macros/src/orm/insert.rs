@@ -0,0 +1,89 @@
+use crate::models::OrmModel;
+use crate::orm::upsert::{InsertRows, insert_rows, select_by_keys};
+use crate::utils::max_bind_parameters;
+use quote::{__private::TokenStream, format_ident, quote};
+
+/// Generates `insert_many(conn, &[Self])`, inserting a whole slice with multi-row
+/// `INSERT ... VALUES (...), (...)` statements inside one transaction and returning the stored rows.
+///
+/// Rows are split into statements under the backend's bind parameter limit. MySQL has no
+/// `RETURNING` and reads the rows back by key, so nothing is generated there when the primary key
+/// is left to the database (`readonly`).
+pub fn generate_insert(database_type: &TokenStream, model: &OrmModel) -> syn::Result<TokenStream> {
+    let primary_key = model.primary_key();
+    let is_db_generated =
+        primary_key.is_generated() && primary_key.generated_column().column_properties.readonly;
+    if cfg!(feature = "mysql") && is_db_generated {
+        return Ok(quote! {});
+    }
+
+    let insert_columns = model
+        .insert_columns()
+        .map(|col| col.column_name.as_str())
+        .collect::<Vec<_>>();
+    if insert_columns.is_empty() {
+        return Ok(quote! {});
+    }
+
+    let trait_ident = format_ident!("{}InsertManyTrait", model.struct_name);
+    let struct_name = model.struct_name;
+    let struct_visibility = model.struct_visibility;
+    let table_name = &model.table_name;
+
+    // Every row binds one parameter per inserted column.
+    let max_rows_per_statement = (max_bind_parameters() / insert_columns.len()).max(1);
+    let insert_head = format!("INSERT INTO {table_name} ({}) ", insert_columns.join(","));
+
+    let InsertRows {
+        keys_code,
+        rows_iter,
+        row_pattern,
+        row_binds,
+    } = insert_rows(model);
+
+    let fetch_code = if cfg!(feature = "mysql") {
+        let select_code = select_by_keys(model, &rows_iter, &row_pattern);
+        quote! {
+            insert.build().execute(&mut *tx).await?;
+            #select_code
+            select.build_query_as::<#struct_name>().fetch_all(&mut *tx).await?
+        }
+    } else {
+        let returning = format!(" RETURNING {}", model.full_column_select());
+        quote! {
+            insert.push(#returning);
+            insert.build_query_as::<#struct_name>().fetch_all(&mut *tx).await?
+        }
+    };
+
+    Ok(quote! {
+        #struct_visibility trait #trait_ident<'c, A: sqlx::Acquire<'c, Database = #database_type> + Send>: Sized {
+            /// Inserts every item, generating the primary key and timestamps of each row as
+            /// `save()` does, and returns the stored rows. Runs inside one transaction, so either
+            /// every row is inserted or none is.
+            async fn insert_many(conn: A, items: &[Self]) -> lorm::errors::Result<Vec<#struct_name>>;
+        }
+
+        #[automatically_derived]
+        impl<'c, A: sqlx::Acquire<'c, Database = #database_type> + Send> #trait_ident<'c, A> for #struct_name {
+            async fn insert_many(conn: A, items: &[Self]) -> lorm::errors::Result<Vec<#struct_name>> {
+                if items.is_empty() {
+                    return Ok(Vec::new());
+                }
+                let mut tx = conn.begin().await?;
+                let mut inserted = Vec::with_capacity(items.len());
+                for items in items.chunks(#max_rows_per_statement) {
+                    #keys_code
+                    let mut insert = sqlx::QueryBuilder::new(#insert_head);
+                    insert.push_values(#rows_iter, |mut b, #row_pattern| {
+                        #(#row_binds)*
+                    });
+                    let r = { #fetch_code };
+                    inserted.extend(r);
+                }
+                tx.commit().await?;
+                Ok(inserted)
+            }
+        }
+    })
+}
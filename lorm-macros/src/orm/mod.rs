@@ -1,9 +1,17 @@
 mod by;
 pub mod column;
 mod delete;
+mod force_set;
+mod from_row;
+mod insert;
+mod patch;
 pub mod relations;
 mod save;
 mod select;
+mod stream;
+mod tracked;
+mod update;
+mod upsert;
 mod with;
 
 use crate::models::OrmModel;
@@ -61,18 +69,38 @@ pub fn expand_derive_to_orm_struct(
     let with_code = with::generate_with(&executor_type, &database_type, &model)?;
     let by_code = by::generate_by(&executor_type, &database_type, &model)?;
     let select_code = select::generate_select(&executor_type, &database_type, &model)?;
-    let delete_code = delete::generate_delete(&executor_type, &model)?;
-    let save_code = save::generate_save(&executor_type, &model)?;
+    let select_columns_code = select::generate_select_columns(&model);
+    let stream_code = stream::generate_stream(&executor_type, &model);
+    let delete_code = delete::generate_delete(&executor_type, &database_type, &model)?;
+    let save_code = save::generate_save(&executor_type, &database_type, &model)?;
+    let update_code = update::generate_update(&executor_type, &database_type, &model)?;
+    let patch_code = patch::generate_patch(&executor_type, &database_type, &model)?;
+    let tracked_code = tracked::generate_tracked(&executor_type, &database_type, &model)?;
+    let upsert_code = upsert::generate_upsert(&executor_type, &database_type, &model)?;
+    let insert_code = insert::generate_insert(&database_type, &model)?;
+    let force_set_code = force_set::generate_force_set(&executor_type, &database_type, &model)?;
     let belongs_to_code = relations::generate_belongs_to(&model);
     let has_relations_code = relations::generate_has_relations(&model);
+    let from_row_code = from_row::generate_from_row(&database_type, &model)?;
+    let join_target_code = from_row::generate_join_target(&database_type, &model)?;
 
     Ok(TokenStream::from(quote! {
         #with_code
         #by_code
         #select_code
+        #select_columns_code
+        #stream_code
         #delete_code
         #save_code
+        #update_code
+        #patch_code
+        #tracked_code
+        #upsert_code
+        #insert_code
+        #force_set_code
         #belongs_to_code
         #has_relations_code
+        #from_row_code
+        #join_target_code
     }))
 }
@@ -0,0 +1,143 @@
+use crate::models::OrmModel;
+use crate::orm::by::{PkBindings, pk_bindings};
+use crate::utils::is_option_wrapped;
+use quote::{__private::TokenStream, format_ident, quote};
+
+/// Generates the `{Struct}Patch` companion struct, holding an `Option` per updatable column, and
+/// `apply_patch(executor, pk..., patch)` which updates only the columns set to `Some`.
+///
+/// Nullable columns are wrapped twice so that a patch can reset them to NULL with `Some(None)`.
+/// `created_at` is never written, `updated_at` and the version are bumped, as `save()` does.
+/// Flattened columns are left out of the patch.
+pub fn generate_patch(
+    executor_type: &TokenStream,
+    database_type: &TokenStream,
+    model: &OrmModel,
+) -> syn::Result<TokenStream> {
+    let trait_ident = format_ident!("{}PatchTrait", model.struct_name);
+    let patch_ident = format_ident!("{}Patch", model.struct_name);
+    let struct_name = model.struct_name;
+    let struct_visibility = model.struct_visibility;
+    let table_name = &model.table_name;
+    let lifetime = quote! {'a};
+
+    let columns = model
+        .update_columns()
+        .filter(|c| {
+            !c.is_flattened && !c.column_properties.created_at && !c.column_properties.updated_at
+        })
+        .collect::<Vec<_>>();
+    if columns.is_empty() {
+        return Ok(quote! {});
+    }
+
+    let patch_fields = columns.iter().map(|column| {
+        let field = &column.field;
+        let ty = &column.ty;
+        quote! { pub #field: Option<#ty>, }
+    });
+
+    let assignments = columns.iter().map(|column| {
+        let field = &column.field;
+        let assignment = format!("{} = ", column.column_name);
+        let value = quote! {value};
+        let bind_value = if column.column_properties.use_json {
+            if is_option_wrapped(&column.ty) {
+                quote! { #value.as_ref().map(sqlx::types::Json) }
+            } else {
+                quote! { sqlx::types::Json(#value) }
+            }
+        } else {
+            column.bind_value(value.clone())
+        };
+        quote! {
+            if let Some(#value) = &patch.#field {
+                if assignments > 0 {
+                    builder.push(", ");
+                }
+                builder.push(#assignment);
+                builder.push_bind(#bind_value);
+                assignments += 1;
+            }
+        }
+    });
+
+    let updated_at_code = match model.updated_at() {
+        Some(column) if !column.column_properties.readonly => {
+            let head = format!(", {} = ", column.column_name);
+            let new_method = &column.column_properties.new_expression;
+            quote! {
+                builder.push(#head);
+                builder.push_bind(#new_method);
+            }
+        }
+        _ => quote! {},
+    };
+    let version_code = match model.version_increment() {
+        Some(increment) => {
+            let assignment = format!(", {increment}");
+            quote! { builder.push(#assignment); }
+        }
+        None => quote! {},
+    };
+
+    let PkBindings {
+        params: pk_params,
+        constraints: pk_constraints,
+        ..
+    } = pk_bindings(model, database_type, &lifetime, 1)?;
+    let pk_conditions = model
+        .primary_key
+        .fields()
+        .iter()
+        .enumerate()
+        .map(|(i, column)| {
+            let param_ident = &column.field;
+            let (_, param_use) = column.bind_param(&quote! { #param_ident }, &lifetime)?;
+            let head = if i == 0 {
+                format!(" WHERE {} = ", column.column_name)
+            } else {
+                format!(" AND {} = ", column.column_name)
+            };
+            Ok(quote! {
+                builder.push(#head);
+                builder.push_bind(#param_use);
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+    let update_head = format!("UPDATE {table_name} SET ");
+
+    let signature = quote! {
+        async fn apply_patch<#lifetime>(executor: E, #(#pk_params,)* patch: &#lifetime #patch_ident) -> lorm::errors::Result<u64>
+        where #(#pk_constraints),*
+    };
+
+    Ok(quote! {
+        /// A partial update of the model, see `apply_patch()`.
+        #[derive(Default)]
+        #struct_visibility struct #patch_ident {
+            #(#patch_fields)*
+        }
+
+        #struct_visibility trait #trait_ident<'e, E: #executor_type>: Sized {
+            #signature;
+        }
+
+        #[automatically_derived]
+        impl<'e, E: #executor_type> #trait_ident<'e, E> for #struct_name {
+            #signature {
+                let mut builder = sqlx::QueryBuilder::<#database_type>::new(#update_head);
+                let mut assignments = 0usize;
+                #(#assignments)*
+                if assignments == 0 {
+                    return Ok(0);
+                }
+                #updated_at_code
+                #version_code
+                #(#pk_conditions)*
+                let r = builder.build().execute(executor).await?;
+                Ok(r.rows_affected())
+            }
+        }
+    })
+}
@@ -16,6 +16,18 @@ pub(crate) struct RelationInfo {
     pub(crate) cardinality: crate::attributes::Cardinality,
 }
 
+/// Name of the method of a `belongs_to` relation: the explicit one, or the snake_case name of the
+/// target (`parent` for `belongs_to = Self`).
+pub(crate) fn belongs_to_method_name(relation: &RelationInfo) -> String {
+    if !relation.method_name.is_empty() {
+        return relation.method_name.clone();
+    }
+    match &relation.target {
+        RelationTarget::Path(path) => default_belongs_to_method(path),
+        RelationTarget::SelfRef => "parent".to_string(),
+    }
+}
+
 /// Generate `belongs_to` relation methods for the given model.
 ///
 /// For each `belongs_to` relation on the model, emits a method like:
@@ -40,23 +52,7 @@ pub(crate) fn generate_belongs_to(model: &OrmModel) -> TokenStream {
         };
         let fk_field_ident = &fk_col.field;
 
-        let method_name_str: String = match &relation.target {
-            RelationTarget::Path(path) => {
-                if relation.method_name.is_empty() {
-                    default_belongs_to_method(path)
-                } else {
-                    relation.method_name.clone()
-                }
-            }
-            RelationTarget::SelfRef => {
-                if relation.method_name.is_empty() {
-                    "parent".to_string()
-                } else {
-                    relation.method_name.clone()
-                }
-            }
-        };
-        let method_ident = format_ident!("{}", method_name_str);
+        let method_ident = format_ident!("{}", belongs_to_method_name(relation));
 
         let builder_tokens: TokenStream = match &relation.target {
             RelationTarget::Path(path) => {
@@ -1,9 +1,13 @@
 use crate::models::OrmModel;
 use crate::orm::column::Column;
-use crate::utils::db_placeholder;
+use crate::utils::{db_placeholder, is_option_wrapped, to_column_type};
 use quote::{__private::TokenStream, format_ident, quote};
 
-pub fn generate_save(executor_type: &TokenStream, model: &OrmModel) -> syn::Result<TokenStream> {
+pub fn generate_save(
+    executor_type: &TokenStream,
+    database_type: &TokenStream,
+    model: &OrmModel,
+) -> syn::Result<TokenStream> {
     let save_trait_ident = format_ident!("{}SaveTrait", model.struct_name);
     let struct_name = model.struct_name;
     let struct_visibility = model.struct_visibility;
@@ -34,26 +38,20 @@ pub fn generate_save(executor_type: &TokenStream, model: &OrmModel) -> syn::Resu
         (is_set, code)
     };
 
-    // --- WHERE clause and binds for UPDATE (appended after SET ...) ---
-    let update_col_count = model.update_columns().count();
-    let is_full_key = update_col_count == 0;
+    // --- Binds for UPDATE, following the placeholder order of `update_statement()` ---
+    let is_full_key = model.update_columns().count() == 0;
     let pk_fields = primary_key.fields();
-    let pk_update_where: String = pk_fields
-        .iter()
-        .enumerate()
-        .map(|(i, col)| {
-            Ok::<_, syn::Error>(format!(
-                "{} = {}",
-                col.column_name,
-                db_placeholder(col.base_field, update_col_count + i + 1)?
-            ))
-        })
-        .collect::<syn::Result<Vec<_>>>()?
-        .join(" AND ");
 
     // For UPDATE WHERE: always use self_accessor (existing pk values)
-    let pk_update_bind_accessors: Vec<TokenStream> =
-        pk_fields.iter().map(|col| col.self_accessor()).collect();
+    let pk_update_bind_accessors: Vec<TokenStream> = pk_fields
+        .iter()
+        .map(|col| col.bind_value(col.self_accessor()))
+        .chain(
+            model
+                .version()
+                .map(|col| col.bind_value(col.self_accessor())),
+        )
+        .collect();
 
     // --- WHERE clause for SELECT by pk (MySQL fetch after INSERT/UPDATE) ---
     let pk_select_where: String = pk_fields
@@ -72,14 +70,32 @@ pub fn generate_save(executor_type: &TokenStream, model: &OrmModel) -> syn::Resu
     // For SELECT after INSERT in MySQL manual pk path: use self_accessor
     // For generated non-readonly pk: bind primary_key_var (the locally generated value)
     let pk_select_bind_accessors_insert: Vec<TokenStream> = if is_manual {
-        pk_fields.iter().map(|col| col.self_accessor()).collect()
+        pk_fields
+            .iter()
+            .map(|col| col.bind_value(col.self_accessor()))
+            .collect()
     } else {
-        vec![quote! { #primary_key_var }]
+        vec![
+            primary_key
+                .generated_column()
+                .bind_value(quote! { #primary_key_var }),
+        ]
     };
 
+    // For the SELECT of save_and_reload(): the pk of the saved row.
+    let saved_var = quote! {saved};
+    let pk_select_bind_accessors_saved: Vec<TokenStream> = pk_fields
+        .iter()
+        .map(|col| col.bind_value(col.accessor_on(&saved_var)))
+        .collect();
+    let reload_sql =
+        format!("SELECT {full_select_columns} FROM {table_name} WHERE {pk_select_where}");
+
     // For SELECT after UPDATE in MySQL: use self_accessor (existing pk values)
-    let pk_select_bind_accessors_update: Vec<TokenStream> =
-        pk_fields.iter().map(|col| col.self_accessor()).collect();
+    let pk_select_bind_accessors_update: Vec<TokenStream> = pk_fields
+        .iter()
+        .map(|col| col.bind_value(col.self_accessor()))
+        .collect();
 
     // Created at
     let created_at_var = quote! {created_at};
@@ -114,21 +130,21 @@ pub fn generate_save(executor_type: &TokenStream, model: &OrmModel) -> syn::Resu
     };
 
     // For generated pk: use `primary_key_var` (the locally generated value).
-    // For manual pk: fall through to self_accessor() since pk_code is empty and
+    // For manual pk: fall through to the accessor on `instance` since pk_code is empty and
     // there is no local `primary_key` variable.
-    let column_value = |column: &Column, use_created_at_var: bool| {
+    let column_value = |column: &Column, instance: &TokenStream, use_created_at_var: bool| {
         if column.column_properties.created_at && use_created_at_var {
             created_at_var.clone()
         } else if column.column_properties.updated_at {
             updated_at_var.clone()
         } else if column.column_properties.primary_key && !is_manual {
-            primary_key_var.clone()
+            column.bind_value(primary_key_var.clone())
         } else {
-            let accessor = column.self_accessor();
+            let accessor = column.accessor_on(instance);
             if column.column_properties.use_json {
                 quote! { sqlx::types::Json(#accessor) }
             } else {
-                accessor
+                column.bind_value(accessor)
             }
         }
     };
@@ -138,7 +154,7 @@ pub fn generate_save(executor_type: &TokenStream, model: &OrmModel) -> syn::Resu
         create_insert_placeholders(&model.insert_columns().collect::<Vec<_>>());
     let insert_values = model
         .insert_columns()
-        .map(|col| column_value(col, true))
+        .map(|col| column_value(col, &quote! {self}, true))
         .collect::<Vec<_>>();
     let insert_columns = model
         .insert_columns()
@@ -147,24 +163,23 @@ pub fn generate_save(executor_type: &TokenStream, model: &OrmModel) -> syn::Resu
         .join(",");
 
     // find `updatable` fields
-    let update_value_placeholders =
-        create_update_placeholders(&model.update_columns().collect::<Vec<_>>());
+    let update_statement = update_statement(
+        table_name,
+        &model.update_columns().collect::<Vec<_>>(),
+        pk_fields,
+        model.version(),
+    )?;
     let update_values = model
         .update_columns()
-        .map(|col| column_value(col, false))
+        .map(|col| column_value(col, &quote! {self}, false))
         .collect::<Vec<_>>();
 
     let insert_sql_returning = format!(
         "INSERT INTO {table_name} ({insert_columns}) VALUES ({insert_value_placeholders}) RETURNING {full_select_columns}"
     );
-    let update_sql_returning = format!(
-        "UPDATE {table_name} SET {update_value_placeholders} WHERE {pk_update_where} RETURNING {full_select_columns}"
-    );
 
     let insert_sql_no_returning =
         format!("INSERT INTO {table_name} ({insert_columns}) VALUES ({insert_value_placeholders})");
-    let update_sql_no_returning =
-        format!("UPDATE {table_name} SET {update_value_placeholders} WHERE {pk_update_where}");
     let select_by_pk_sql =
         format!("SELECT {full_select_columns} from {table_name} WHERE {pk_select_where}");
 
@@ -202,7 +217,9 @@ pub fn generate_save(executor_type: &TokenStream, model: &OrmModel) -> syn::Resu
             )*
             .execute(executor).await?;
             let r = sqlx::query_as::<_, #struct_name>(#select_by_pk_sql)
-                .bind(#primary_key_var)
+                #(
+                    .bind(#pk_select_bind_accessors_insert)
+                )*
                 .fetch_one(executor).await?;
         }
     };
@@ -222,27 +239,96 @@ pub fn generate_save(executor_type: &TokenStream, model: &OrmModel) -> syn::Resu
         .collect::<Vec<_>>()
         .join(", ");
 
+    // Without any writable column there is nothing to update, the row is only read back.
+    let update_code = match update_statement {
+        None => quote! {
+            let r = sqlx::query_as::<_, #struct_name>(#select_by_pk_sql)
+            #(
+                .bind(#pk_select_bind_accessors_update)
+            )*
+            .fetch_one(executor).await?;
+            Ok(r)
+        },
+        // A versioned row updates nothing once another write bumped the version.
+        Some(update_sql) if cfg!(feature = "mysql") && model.version().is_some() => quote! {
+            let r = sqlx::query(#update_sql)
+            #(
+                .bind(#update_values)
+            )*
+            #(
+                .bind(#pk_update_bind_accessors)
+            )*
+            .execute(executor).await?;
+            if r.rows_affected() == 0 {
+                return Err(lorm::errors::Error::OptimisticLockConflict);
+            }
+            let r = sqlx::query_as::<_, #struct_name>(#select_by_pk_sql)
+            #(
+                .bind(#pk_select_bind_accessors_update)
+            )*
+            .fetch_one(executor).await?;
+            Ok(r)
+        },
+        Some(update_sql) if cfg!(feature = "mysql") => quote! {
+            sqlx::query(#update_sql)
+            #(
+                .bind(#update_values)
+            )*
+            #(
+                .bind(#pk_update_bind_accessors)
+            )*
+            .execute(executor).await?;
+            let r = sqlx::query_as::<_, #struct_name>(#select_by_pk_sql)
+            #(
+                .bind(#pk_select_bind_accessors_update)
+            )*
+            .fetch_one(executor).await?;
+            Ok(r)
+        },
+        Some(update_sql) if model.version().is_some() => {
+            let update_sql_returning = format!("{update_sql} RETURNING {full_select_columns}");
+            quote! {
+                let r = sqlx::query_as::<_, #struct_name>(#update_sql_returning)
+                #(
+                    .bind(#update_values)
+                )*
+                #(
+                    .bind(#pk_update_bind_accessors)
+                )*
+                .fetch_optional(executor).await?;
+                r.ok_or(lorm::errors::Error::OptimisticLockConflict)
+            }
+        }
+        Some(update_sql) => {
+            let update_sql_returning = format!("{update_sql} RETURNING {full_select_columns}");
+            quote! {
+                let r = sqlx::query_as::<_, #struct_name>(#update_sql_returning)
+                #(
+                    .bind(#update_values)
+                )*
+                #(
+                    .bind(#pk_update_bind_accessors)
+                )*
+                .fetch_one(executor).await?;
+                Ok(r)
+            }
+        }
+    };
+
     let (executor_bound, save_body) = if is_manual {
         if cfg!(feature = "mysql") {
-            let (upsert_sql, select_sql) = if is_full_key {
-                (
-                    format!(
-                        "INSERT IGNORE INTO {table_name} ({insert_columns}) VALUES ({insert_value_placeholders})"
-                    ),
-                    format!(
-                        "SELECT {full_select_columns} from {table_name} WHERE {pk_select_where}"
-                    ),
-                )
+            // A full key has nothing to update: a no-op assignment keeps the existing row, where
+            // INSERT IGNORE would also turn NOT NULL or foreign key errors into warnings.
+            let on_duplicate = if is_full_key {
+                mysql_no_op_update(model)
             } else {
-                (
-                    format!(
-                        "INSERT INTO {table_name} ({insert_columns}) VALUES ({insert_value_placeholders}) ON DUPLICATE KEY UPDATE {mysql_updates}"
-                    ),
-                    format!(
-                        "SELECT {full_select_columns} from {table_name} WHERE {pk_select_where}"
-                    ),
-                )
+                mysql_updates.clone()
             };
+            let upsert_sql = format!(
+                "INSERT INTO {table_name} ({insert_columns}) VALUES ({insert_value_placeholders}) ON DUPLICATE KEY UPDATE {on_duplicate}"
+            );
+            let select_sql =
+                format!("SELECT {full_select_columns} from {table_name} WHERE {pk_select_where}");
 
             let mysql_upsert_fetch = quote! {
                 sqlx::query(#upsert_sql)
@@ -322,20 +408,7 @@ pub fn generate_save(executor_type: &TokenStream, model: &OrmModel) -> syn::Resu
                         Ok(r)
                     },
                     false => {
-                        sqlx::query(#update_sql_no_returning)
-                        #(
-                            .bind(#update_values)
-                        )*
-                        #(
-                            .bind(#pk_update_bind_accessors)
-                        )*
-                        .execute(executor).await?;
-                        let r = sqlx::query_as::<_, #struct_name>(#select_by_pk_sql)
-                        #(
-                            .bind(#pk_select_bind_accessors_update)
-                        )*
-                        .fetch_one(executor).await?;
-                        Ok(r)
+                        #update_code
                     }
                 }
             },
@@ -357,24 +430,311 @@ pub fn generate_save(executor_type: &TokenStream, model: &OrmModel) -> syn::Resu
                         Ok(r)
                     },
                     false => {
-                        let r = sqlx::query_as::<_, #struct_name>(#update_sql_returning)
+                        #update_code
+                    }
+                }
+            },
+        )
+    };
+
+    // SQLite's RETURNING yields the row before AFTER triggers ran, so `db_managed` columns
+    // maintained by a trigger are read back once the write is done.
+    let (executor_bound, save_body) = if cfg!(feature = "sqlite") && model.has_db_managed_columns()
+    {
+        (
+            quote! { E: #executor_type + Copy },
+            quote! {
+                let #saved_var: lorm::errors::Result<#struct_name> = async { #save_body }.await;
+                let #saved_var = #saved_var?;
+                let r = sqlx::query_as::<_, #struct_name>(#reload_sql)
+                #(
+                    .bind(#pk_select_bind_accessors_saved)
+                )*
+                .fetch_one(executor).await?;
+                Ok(r)
+            },
+        )
+    } else {
+        (executor_bound, save_body)
+    };
+
+    // --- by_{field}_or_insert(): look up, else insert the row built by the closure ---
+    let or_insert_trait_ident = format_ident!("{}ByOrInsertTrait", model.struct_name);
+    let item_var = quote! {item};
+    // Generates the pk of `instance` when it is unset, keeps it otherwise.
+    let resolve_pk = |instance: &TokenStream| {
+        if is_manual || primary_key.generated_column().column_properties.readonly {
+            quote! {}
+        } else {
+            let pk_col = primary_key.generated_column();
+            let pk_accessor = pk_col.accessor_on(instance);
+            let is_unset = pk_col
+                .column_properties
+                .is_set(pk_accessor.clone(), &pk_col.ty);
+            let new_method = &pk_col.column_properties.new_expression;
+            quote! {
+                let #primary_key_var = if #is_unset { #new_method } else { (#pk_accessor).clone() };
+            }
+        }
+    };
+    let item_pk_code = resolve_pk(&item_var);
+    let item_insert_values = model
+        .insert_columns()
+        .map(|col| column_value(col, &item_var, true))
+        .collect::<Vec<_>>();
+    let lifetime = quote! {'a};
+    // Only a unique value identifies the row to find or create, and the inserted row must hold it:
+    // a key assigned by the database cannot be looked up this way.
+    let (or_insert_trait_tokens, or_insert_impl_tokens): (Vec<TokenStream>, Vec<TokenStream>) = model
+        .query_columns()
+        .filter(|column| {
+            (column.column_properties.unique || column.column_properties.primary_key)
+                && !column.column_properties.readonly
+        })
+        .map(|column| {
+            let or_insert_fn = format_ident!("by_{}_or_insert", column.field);
+            let parameter = quote! {value};
+            let (param_type, param_value) = column.bind_param(&parameter, &lifetime)?;
+            let base_type = to_column_type(&column.ty)?;
+            let (constraints, bind_value, looked_up) = if column.column_properties.use_json {
+                (
+                    quote! { #base_type: serde::Serialize },
+                    quote! { sqlx::types::Json(#param_value) },
+                    quote! { #parameter.0 },
+                )
+            } else {
+                (
+                    column.bind_constraint(database_type, &lifetime)?,
+                    param_value,
+                    quote! { #parameter },
+                )
+            };
+            let select_sql = format!(
+                "SELECT {full_select_columns} FROM {table_name} WHERE {}{} = {} LIMIT 1",
+                model.not_deleted_and(),
+                column.column_name,
+                db_placeholder(column.base_field, 1)?
+            );
+            // The value held by the row built by `f`, as an `Option<&_>`.
+            let item_value = column.accessor_on(&item_var);
+            let item_value = match (
+                column.is_flattened && is_option_wrapped(&column.base_field.ty),
+                is_option_wrapped(&column.ty),
+            ) {
+                (false, false) => quote! { Some(#item_value) },
+                (false, true) => quote! { (#item_value).as_ref() },
+                (true, false) => item_value,
+                (true, true) => quote! { (#item_value).and_then(|v| v.as_ref()) },
+            };
+            let holds_value = if column.column_properties.store_as_text {
+                quote! { #item_value.map(|v| v.to_string()).as_deref() == Some(#parameter.as_str()) }
+            } else {
+                quote! {
+                    #item_value.map(|v| <_ as std::borrow::Borrow<#base_type>>::borrow(v))
+                        == Some(<_ as std::borrow::Borrow<#base_type>>::borrow(&#looked_up))
+                }
+            };
+            let mismatch_message = format!(
+                "the row built for {or_insert_fn}() does not hold the {} looked up",
+                column.field
+            );
+            let signature = quote! {
+                async fn #or_insert_fn<#lifetime, F>(executor: E, #parameter: #param_type, f: F) -> lorm::errors::Result<#struct_name>
+                where F: FnOnce() -> #struct_name + Send, #constraints
+            };
+            let trait_code = quote! {
+                /// Returns the row whose column equals `value`, else inserts the row built by `f`,
+                /// which must hold `value` in that column: nothing is written otherwise and
+                /// `Error::MismatchedRow` is returned. Atomic: a concurrent insert of the same
+                /// value makes this one conflict and its row is returned. A conflict on another
+                /// unique column returns `Error::UniqueViolation`. The executor runs several
+                /// queries, hence `Copy`: `&pool`, not a transaction.
+                #signature;
+            };
+            let impl_code = quote! {
+                #signature {
+                    // A nested function, as the bounds on `value` would tie the row binds to `'a`.
+                    async fn insert<'e, E: #executor_type>(executor: E, #item_var: #struct_name) -> lorm::errors::Result<()> {
+                        #item_pk_code
+                        #created_at_code
+                        #updated_at_code
+                        sqlx::query(#insert_sql_no_returning)
+                        #(
+                            .bind(#item_insert_values)
+                        )*
+                        .execute(executor).await?;
+                        Ok(())
+                    }
+
+                    let #parameter = #bind_value;
+                    let r = sqlx::query_as::<_, #struct_name>(#select_sql)
+                        .bind(#parameter.clone())
+                        .fetch_optional(executor).await?;
+                    if let Some(r) = r {
+                        return Ok(r);
+                    }
+                    let #item_var = f();
+                    if !(#holds_value) {
+                        return Err(lorm::errors::Error::MismatchedRow(#mismatch_message.to_string()));
+                    }
+                    if let Err(e) = insert(executor, #item_var).await {
+                        // Only a row holding `value` inserted in the meantime is a lost race, the
+                        // other conflicts are returned as is.
+                        if !matches!(e, lorm::errors::Error::UniqueViolation { .. }) {
+                            return Err(e);
+                        }
+                        let r = sqlx::query_as::<_, #struct_name>(#select_sql)
+                            .bind(#parameter)
+                            .fetch_optional(executor).await?;
+                        return r.ok_or(e);
+                    }
+                    let r = sqlx::query_as::<_, #struct_name>(#select_sql)
+                        .bind(#parameter)
+                        .fetch_one(executor).await?;
+                    Ok(r)
+                }
+            };
+            Ok((trait_code, impl_code))
+        })
+        .collect::<syn::Result<Vec<_>>>()?
+        .into_iter()
+        .unzip();
+    let or_insert_code = if or_insert_trait_tokens.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            #struct_visibility trait #or_insert_trait_ident<'e, E: #executor_type + Copy>: Sized {
+                #(#or_insert_trait_tokens)*
+            }
+
+            #[automatically_derived]
+            impl<'e, E: #executor_type + Copy> #or_insert_trait_ident<'e, E> for #struct_name {
+                #(#or_insert_impl_tokens)*
+            }
+        }
+    };
+
+    // --- upsert_on_{field}(): insert, or update the row holding the same unique value ---
+    let upsert_on_trait_ident = format_ident!("{}UpsertOnTrait", model.struct_name);
+    let self_var = quote! {self};
+    let self_pk_code = resolve_pk(&self_var);
+    let (upsert_on_bound, upsert_on_tokens): (TokenStream, Vec<(TokenStream, TokenStream)>) = {
+        // created_at keeps the value already stored for existing rows.
+        let conflict_columns = model
+            .update_columns()
+            .filter(|col| !col.column_properties.created_at)
+            .map(|col| col.column_name.as_str())
+            .collect::<Vec<_>>();
+        let tokens = model
+            .unique_columns()
+            .map(|column| {
+                let upsert_on_fn = format_ident!("upsert_on_{}", column.field);
+                let column_name = &column.column_name;
+                // With nothing else to update, a no-op assignment still returns the existing row.
+                let conflict_columns = if conflict_columns.is_empty() {
+                    vec![column_name.as_str()]
+                } else {
+                    conflict_columns.clone()
+                };
+                let signature = quote! {
+                    async fn #upsert_on_fn(&self, executor: E) -> lorm::errors::Result<#struct_name>
+                };
+                let body = if cfg!(feature = "mysql") {
+                    let updates = conflict_columns
+                        .iter()
+                        .map(|c| format!("{c} = VALUES({c})"))
+                        .chain(model.version_increment())
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    let upsert_sql = format!(
+                        "INSERT INTO {table_name} ({insert_columns}) VALUES ({insert_value_placeholders}) ON DUPLICATE KEY UPDATE {updates}"
+                    );
+                    let select_sql = format!(
+                        "SELECT {full_select_columns} FROM {table_name} WHERE {column_name} = {}",
+                        db_placeholder(column.base_field, 1)?
+                    );
+                    let unique_value = column_value(column, &self_var, true);
+                    quote! {
+                        sqlx::query(#upsert_sql)
                         #(
-                            .bind(#update_values)
+                            .bind(#insert_values)
                         )*
+                        .execute(executor).await?;
+                        let r = sqlx::query_as::<_, #struct_name>(#select_sql)
+                            .bind(#unique_value)
+                            .fetch_one(executor).await?;
+                    }
+                } else {
+                    let updates = conflict_columns
+                        .iter()
+                        .map(|c| format!("{c} = EXCLUDED.{c}"))
+                        .chain(model.version_increment())
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    let upsert_sql = format!(
+                        "INSERT INTO {table_name} ({insert_columns}) VALUES ({insert_value_placeholders}) ON CONFLICT ({column_name}) DO UPDATE SET {updates} RETURNING {full_select_columns}"
+                    );
+                    quote! {
+                        let r = sqlx::query_as::<_, #struct_name>(#upsert_sql)
                         #(
-                            .bind(#pk_update_bind_accessors)
+                            .bind(#insert_values)
                         )*
                         .fetch_one(executor).await?;
-                        Ok(r)
                     }
-                }
-            },
-        )
+                };
+                Ok((
+                    quote! {
+                        /// Inserts the row, or updates the row already holding the same value in
+                        /// this unique column, and returns the stored row. The primary key and
+                        /// `created_at` of an existing row are kept.
+                        #signature;
+                    },
+                    quote! {
+                        #signature {
+                            #self_pk_code
+                            #created_at_code
+                            #updated_at_code
+                            #body
+                            Ok(r)
+                        }
+                    },
+                ))
+            })
+            .collect::<syn::Result<Vec<_>>>()?;
+        let bound = if cfg!(feature = "mysql") {
+            quote! { E: #executor_type + Copy }
+        } else {
+            quote! { E: #executor_type }
+        };
+        (bound, tokens)
+    };
+    let upsert_on_code = if upsert_on_tokens.is_empty() {
+        quote! {}
+    } else {
+        let (trait_tokens, impl_tokens): (Vec<_>, Vec<_>) = upsert_on_tokens.into_iter().unzip();
+        quote! {
+            #struct_visibility trait #upsert_on_trait_ident<'e, #upsert_on_bound>: Sized {
+                #(#trait_tokens)*
+            }
+
+            #[automatically_derived]
+            impl<'e, #upsert_on_bound> #upsert_on_trait_ident<'e, E> for #struct_name {
+                #(#impl_tokens)*
+            }
+        }
     };
 
     Ok(quote! {
         #struct_visibility trait #save_trait_ident<'e, #executor_bound>: Sized {
             async fn save(&self, executor: E) -> lorm::errors::Result<#struct_name>;
+
+            /// Same as `save()`, reading the stored row back on `read_executor`. With read
+            /// replicas, `save()` returns what the primary stored while a later read on a lagging
+            /// replica may not see it yet: pass the primary as `read_executor` for read-after-write
+            /// consistency, or a replica to check what it serves.
+            async fn save_and_reload<'r, R>(&self, write_executor: E, read_executor: R) -> lorm::errors::Result<#struct_name>
+            where
+                R: sqlx::Executor<'r, Database = #database_type>;
         }
 
         #[automatically_derived]
@@ -384,10 +744,36 @@ pub fn generate_save(executor_type: &TokenStream, model: &OrmModel) -> syn::Resu
             {
                 #save_body
             }
+
+            async fn save_and_reload<'r, R>(&self, write_executor: E, read_executor: R) -> lorm::errors::Result<#struct_name>
+            where
+                R: sqlx::Executor<'r, Database = #database_type>,
+            {
+                let #saved_var = self.save(write_executor).await?;
+                let r = sqlx::query_as::<_, #struct_name>(#reload_sql)
+                #(
+                    .bind(#pk_select_bind_accessors_saved)
+                )*
+                .fetch_one(read_executor).await?;
+                Ok(r)
+            }
         }
+
+        #or_insert_code
+
+        #upsert_on_code
     })
 }
 
+/// The MySQL `ON DUPLICATE KEY UPDATE` assignment leaving a conflicting row as it is,
+/// `id = id` on the first primary key column.
+fn mysql_no_op_update(model: &OrmModel) -> String {
+    format!(
+        "{0} = {0}",
+        model.primary_key.generated_column().column_name
+    )
+}
+
 /// Creates SQL placeholders for INSERT statements.
 ///
 /// Generates database-specific placeholders: `"$1, $2, $3"` for PostgreSQL/SQLite or `"?, ?, ?"` for MySQL.
@@ -419,6 +805,53 @@ pub(crate) fn create_update_placeholders<'a>(fields: &[&Column<'a>]) -> String {
         .join(",")
 }
 
+/// Builds the `UPDATE` of `save()`: the SET clause numbers its placeholders from 1 in column
+/// order, then the pk predicate continues the numbering, which is also the order of the binds.
+/// A `version` column is incremented and its current value, bound last, checked.
+///
+/// Returns `None` when there is no column to set, `SET` cannot be empty.
+pub(crate) fn update_statement<'a>(
+    table_name: &str,
+    update_columns: &[&Column<'a>],
+    pk_fields: &[Column<'a>],
+    version: Option<&Column<'a>>,
+) -> syn::Result<Option<String>> {
+    if update_columns.is_empty() && version.is_none() {
+        return Ok(None);
+    }
+    let pk_where = pk_fields
+        .iter()
+        .enumerate()
+        .map(|(i, col)| {
+            Ok(format!(
+                "{} = {}",
+                col.column_name,
+                db_placeholder(col.base_field, update_columns.len() + i + 1)?
+            ))
+        })
+        .collect::<syn::Result<Vec<_>>>()?
+        .join(" AND ");
+    let mut assignments = create_update_placeholders(update_columns);
+    let mut conditions = pk_where;
+    if let Some(version) = version {
+        if !assignments.is_empty() {
+            assignments.push(',');
+        }
+        assignments.push_str(&format!("{0} = {0} + 1", version.column_name));
+        conditions.push_str(&format!(
+            " AND {} = {}",
+            version.column_name,
+            db_placeholder(
+                version.base_field,
+                update_columns.len() + pk_fields.len() + 1
+            )?
+        ));
+    }
+    Ok(Some(format!(
+        "UPDATE {table_name} SET {assignments} WHERE {conditions}"
+    )))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -441,13 +874,19 @@ mod tests {
             column_properties: crate::attributes::ColumnProperties {
                 skip: false,
                 readonly: false,
+                db_managed: false,
                 primary_key: false,
+                auto_increment: false,
                 generate_by: false,
+                unique: false,
                 created_at: false,
                 updated_at: false,
+                soft_delete: false,
+                version: false,
                 new_expression: parse_str("Default::default()").unwrap(),
                 is_set_expression: None,
                 use_json: false,
+                store_as_text: false,
                 belongs_to_target: None,
             },
             belongs_to: None,
@@ -500,4 +939,59 @@ mod tests {
             "name = $1,email = $2,age = $3"
         );
     }
+
+    #[test]
+    fn test_update_statement_numbers_pk_after_set_columns() {
+        let name = create_test_column("name", "String");
+        let email = create_test_column("email", "String");
+        let id = create_test_column("id", "i32");
+        let sql = update_statement("users", &[&name, &email], std::slice::from_ref(&id), None)
+            .unwrap()
+            .unwrap();
+        assert_eq!(sql, "UPDATE users SET name = $1,email = $2 WHERE id = $3");
+    }
+
+    #[test]
+    fn test_update_statement_numbers_composite_pk() {
+        let assigned_at = create_test_column("assigned_at", "String");
+        let pk = vec![
+            create_test_column("user_id", "String"),
+            create_test_column("role_id", "String"),
+        ];
+        let sql = update_statement("user_roles", &[&assigned_at], &pk, None)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            sql,
+            "UPDATE user_roles SET assigned_at = $1 WHERE user_id = $2 AND role_id = $3"
+        );
+    }
+
+    #[test]
+    fn test_update_statement_without_columns() {
+        let id = create_test_column("id", "i32");
+        let sql = update_statement("users", &[], std::slice::from_ref(&id), None).unwrap();
+        assert_eq!(sql, None);
+    }
+
+    #[test]
+    fn test_update_statement_checks_and_increments_version() {
+        let body = create_test_column("body", "String");
+        let version = create_test_column("version", "i64");
+        let id = create_test_column("id", "i32");
+        let sql = update_statement("pages", &[&body], std::slice::from_ref(&id), Some(&version))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            sql,
+            "UPDATE pages SET body = $1,version = version + 1 WHERE id = $2 AND version = $3"
+        );
+        let sql = update_statement("pages", &[], std::slice::from_ref(&id), Some(&version))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            sql,
+            "UPDATE pages SET version = version + 1 WHERE id = $1 AND version = $2"
+        );
+    }
 }
@@ -1,5 +1,9 @@
+use crate::attributes::{Cardinality, RelationTarget};
 use crate::models::OrmModel;
-use crate::utils::{get_bind_param_type_and_usage, get_bind_type_where_constraint};
+use crate::orm::relations::belongs_to_method_name;
+use crate::utils::{
+    is_option_wrapped, is_primitive_type, is_shared_str_type, to_column_type, utc_normalization,
+};
 use quote::{__private::TokenStream, format_ident, quote};
 
 pub fn generate_select(
@@ -13,170 +17,707 @@ pub fn generate_select(
     let struct_visibility = model.struct_visibility;
 
     let lifetime = quote! {'a};
+    let pk_order = format!(
+        " ORDER BY {}",
+        model
+            .primary_key
+            .fields()
+            .iter()
+            .map(|column| model.qualified_column(&column.column_name))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+
+    // Decimal strings do not sort as the numbers they hold: `store_as = "text"` columns are
+    // neither ordered by nor aggregated.
+    let (ordered_trait_tokens, ordered_impl_tokens): (Vec<TokenStream>, Vec<TokenStream>) = model
+        .query_columns()
+        .filter(|c| !c.column_properties.store_as_text)
+        .map(|column| {
+            let all_ordered_by_fn = format_ident!("all_ordered_by_{}", column.field);
+            let order_by_fn = format_ident!("order_by_{}", column.field);
+            let signature = quote! {
+                async fn #all_ordered_by_fn<'e, E: #executor_type>(executor: E, order: lorm::predicates::OrderBy) -> lorm::errors::Result<Vec<#struct_name>>
+            };
+            (
+                quote! { #signature; },
+                quote! {
+                    #signature {
+                        Self::select().#order_by_fn(order).build(executor).await
+                    }
+                },
+            )
+        })
+        .unzip();
 
     let impl_tokens: Vec<TokenStream> = model.query_columns().map(|column| (|| -> syn::Result<_> {
         let field_name = &column.field;
-        let column_name = &column.column_name;
+        let column_name = model.qualified_column(&column.column_name);
 
-        let constraints = get_bind_type_where_constraint(&column.ty, database_type, &lifetime)?;
+        let constraints = column.bind_constraint(database_type, &lifetime)?;
         let parameter = quote! {value};
-        let (param_type, param_use) = get_bind_param_type_and_usage(&parameter, &column.ty, &lifetime)?;
+        let (param_type, param_use) = column.bind_param(&parameter, &lifetime)?;
         let param = quote! {#parameter: #param_type};
 
-        let where_between_fn = format_ident!("where_between_{}", field_name);
-        let where_fn = format_ident!("where_{}", field_name);
         let having_fn = format_ident!("having_{}", field_name);
         let order_by_fn = format_ident!("order_by_{}", field_name);
+        let order_by_nulls_fn = format_ident!("order_by_{}_nulls", field_name);
         let group_by_fn = format_ident!("group_by_{}", field_name);
+        let seek_after_fn = format_ident!("seek_after_{}", field_name);
+
+        // NULL placement on nullable columns; MySQL has no NULLS FIRST/LAST and orders on IS NULL first.
+        let order_by_nulls_code = if is_option_wrapped(&column.ty) {
+            let nulls_stmt = if cfg!(feature = "mysql") {
+                quote! {
+                    match nulls {
+                        lorm::predicates::Nulls::First => format!(" ({} IS NULL) DESC, {} {}", #column_name, #column_name, order),
+                        lorm::predicates::Nulls::Last => format!(" ({} IS NULL) ASC, {} {}", #column_name, #column_name, order),
+                    }
+                }
+            } else {
+                quote! { format!(" {} {} {}", #column_name, order, nulls) }
+            };
+            quote! {
+                /// Same as `order_by_{field}()`, placing the NULL values first or last.
+                #struct_visibility fn #order_by_nulls_fn(mut self, order: lorm::predicates::OrderBy, nulls: lorm::predicates::Nulls) -> Self {
+                    self.complete_group_by();
+                    if self.is_order_by == false {
+                        self.push(" ORDER BY");
+                        self.is_order_by = true;
+                    } else {
+                        self.push(",");
+                    }
+                    let stmt = #nulls_stmt;
+                    self.push(stmt);
+                    self
+                }
+            }
+        } else {
+            quote! {}
+        };
+
+        let group_by_code = quote! {
+            #struct_visibility fn #group_by_fn(mut self) -> Self {
+                self.grouped_columns.push(#column_name.to_string());
+                if self.is_group_by == false {
+                    self.push(" GROUP BY");
+                    self.is_group_by = true;
+                } else {
+                    self.push(",");
+                }
+                let stmt = format!(" {}", #column_name).to_string();
+                self.push(stmt);
+                self
+            }
+        };
+        if column.column_properties.store_as_text {
+            return Ok(group_by_code);
+        }
 
-        let (left_type, left_use) = get_bind_param_type_and_usage(&quote! {left}, &column.ty, &lifetime)?;
-        let (right_type, right_use) = get_bind_param_type_and_usage(&quote! {right}, &column.ty, &lifetime)?;
         let code = quote! {
             #struct_visibility fn #having_fn(mut self, op: lorm::predicates::Having, fun: lorm::predicates::Function, #param) -> Self where #constraints {
                 self.complete_group_by();
                 if self.is_having == false {
-                    self.builder.push(" HAVING");
+                    self.push(" HAVING");
                     self.is_having = true;
                 } else {
-                    self.builder.push(" AND");
+                    self.push(" AND");
                 }
                 let stmt = match fun {
                     lorm::predicates::Function::Null => format!(" {} {} ", #column_name, op).to_string(),
                     lorm::predicates::Function::Count { is_distinct } if is_distinct == true => format!(" {}(DISTINCT {}) {} ", fun, #column_name, op).to_string(),
                     _ => format!(" {}({}) {} ", fun, #column_name, op).to_string()
                 };
-                self.builder.push(stmt);
-                self.builder.push_bind(#param_use);
-                self
-            }
-
-            #struct_visibility fn #where_fn(mut self, op: lorm::predicates::Where, #param) -> Self where #constraints {
-                if self.is_where == false {
-                    self.builder.push(" WHERE");
-                    self.is_where = true;
-                } else {
-                    self.builder.push(" AND");
-                }
-                let stmt = format!(" {} {} ", #column_name, op).to_string();
-                    self.builder.push(stmt);
-                    self.builder.push_bind(#param_use);
-                self
-            }
-
-            #struct_visibility fn #where_between_fn(mut self, left: #left_type, right: #right_type) -> Self where #constraints {
-                if self.is_where == false {
-                    self.builder.push(" WHERE");
-                    self.is_where = true;
-                } else {
-                    self.builder.push(" AND");
-                }
-                let stmt = format!(" {} BETWEEN ", #column_name).to_string();
-                self.builder.push(stmt);
-                self.builder.push_bind(#left_use);
-                self.builder.push(" AND ");
-                self.builder.push_bind(#right_use);
+                self.push(stmt);
+                self.push_bind(#param_use);
                 self
             }
 
-            #struct_visibility fn #order_by_fn(mut self) -> Self {
+            /// Each call adds this column with its own direction, after the columns already ordered by.
+            #struct_visibility fn #order_by_fn(mut self, order: lorm::predicates::OrderBy) -> Self {
                 self.complete_group_by();
                 if self.is_order_by == false {
-                    self.builder.push(" ORDER BY");
+                    self.push(" ORDER BY");
                     self.is_order_by = true;
                 } else {
-                    self.builder.push(",");
+                    self.push(",");
                 }
-                let stmt = format!(" {}", #column_name).to_string();
-                self.builder.push(stmt);
+                let stmt = format!(" {} {}", #column_name, order);
+                self.push(stmt);
                 self
             }
 
-            #struct_visibility fn #group_by_fn(mut self) -> Self {
-                self.grouped_columns.push(#column_name.to_string());
-                if self.is_group_by == false {
-                    self.builder.push(" GROUP BY");
-                    self.is_group_by = true;
-                } else {
-                    self.builder.push(",");
-                }
-                let stmt = format!(" {}", #column_name).to_string();
-                self.builder.push(stmt);
-                self
+            #order_by_nulls_code
+
+            /// Keyset pagination: keeps the rows sorting after `value` and orders by this column.
+            /// Call it after the other filters; chain `limit(m).then_offset(n)` to jump within a page
+            /// when the key is not unique.
+            #struct_visibility fn #seek_after_fn(mut self, #param) -> Self where #constraints {
+                self.push_where_connector();
+                let stmt = format!(" {} > ", #column_name).to_string();
+                self.push(stmt);
+                self.push_bind(#param_use);
+                self.#order_by_fn(lorm::predicates::OrderBy::Asc)
             }
+
+            #group_by_code
         };
         Ok(code)
     })()).collect::<Result<Vec<_>, _>>()?;
 
-    let select_columns = model.full_column_select();
-    let all_column_names: Vec<&str> = model
-        .columns
-        .iter()
-        .map(|c| c.column_name.as_str())
+    let where_tokens = generate_where_methods(model, database_type, true)?;
+    let where_support = generate_where_support(struct_visibility, "select builder", "build()");
+
+    let select_columns = model.qualified_column_select();
+    // GROUP BY repeats the expressions, never their aliases.
+    let all_column_names: Vec<String> = model
+        .select_items(true)
+        .into_iter()
+        .map(|item| item.expression)
         .collect();
     let table_name = &model.table_name;
-    let select_base = format!("SELECT {select_columns} from {table_name}");
-    let with_initial_where_prefix = format!("SELECT {select_columns} from {table_name} WHERE ");
+    let from_clause = model.table_reference();
+
+    // join_{relation}() per belongs_to relation, the target being read back by build_joined().
+    let join_tokens = model
+        .relations
+        .iter()
+        .filter(|relation| relation.cardinality == Cardinality::BelongsTo)
+        .map(|relation| {
+            let join_fn = format_ident!("join_{}", belongs_to_method_name(relation));
+            let target = match &relation.target {
+                RelationTarget::Path(path) => quote! { #path },
+                RelationTarget::SelfRef => quote! { #struct_name },
+            };
+            let foreign_key = model.qualified_column(&relation.fk_column);
+            let doc = format!(
+                " Joins the row `{}` refers to, to be read with `build_joined::<{}>()`. Rows without it are left out.",
+                relation.fk_column,
+                target.to_string().replace(' ', "")
+            );
+            quote! {
+                #[doc = #doc]
+                #struct_visibility fn #join_fn(mut self) -> Self {
+                    self.joins.push(<#target as lorm::query::JoinTarget<#database_type>>::join_clause(#foreign_key));
+                    let alias = <#target as lorm::query::JoinTarget<#database_type>>::join_alias();
+                    self.projections.push(format!("{alias}.*"));
+                    self
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    // Postgres has a real boolean, SQLite and MySQL return an integer.
+    let (exists_type, exists_result) = if cfg!(feature = "postgres") {
+        (quote! { bool }, quote! { r })
+    } else {
+        (quote! { i64 }, quote! { r != 0 })
+    };
+
+    // PostgreSQL counts distinct row values, the others count the rows of a SELECT DISTINCT.
+    let count_distinct_query = if cfg!(feature = "postgres") {
+        quote! {
+            self.into_query_builder_selecting(&format!("COUNT(DISTINCT ({columns}))"))?
+        }
+    } else {
+        quote! {
+            {
+                let mut builder = sqlx::QueryBuilder::new("SELECT COUNT(*) FROM (");
+                for fragment in self.into_fragments_selecting(&format!("DISTINCT {columns}"))? {
+                    fragment(&mut builder);
+                }
+                builder.push(") lorm_distinct");
+                builder
+            }
+        }
+    };
+    let with_initial_where_prefix = format!(" WHERE {}", model.qualified_column(""));
+
+    let count_estimate_code = if cfg!(feature = "postgres") {
+        quote! {
+            /// Approximate row count of the whole table, read from the planner statistics
+            /// (`pg_class.reltuples`). Much cheaper than `COUNT(*)` on large tables, but it ignores
+            /// every filter of the builder and is only as fresh as the last `ANALYZE`/autovacuum.
+            /// Returns 0 for tables that were never analyzed.
+            #struct_visibility async fn count_estimate<'e, E: #executor_type>(self, executor: E) -> lorm::errors::Result<i64> {
+                let r: i64 = sqlx::query_scalar(
+                    "SELECT GREATEST(COALESCE((SELECT reltuples FROM pg_class WHERE oid = to_regclass($1)), 0), 0)::bigint",
+                )
+                .bind(#table_name)
+                .fetch_one(executor)
+                .await?;
+                Ok(r)
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // SQL-standard row limiting, which SQLite and MySQL do not support.
+    let fetch_code = if cfg!(feature = "postgres") {
+        quote! {
+            /// Same as `limit()` with the SQL-standard syntax, `FETCH NEXT n ROWS ONLY`. Chain it
+            /// after `offset()` for `OFFSET m FETCH NEXT n ROWS ONLY`.
+            #struct_visibility fn fetch(mut self, rows: i64) -> Self {
+                self.complete_group_by();
+                self.push(" FETCH NEXT ");
+                self.push_bind(rows);
+                self.push(" ROWS ONLY");
+                self
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // FOR UPDATE ends the statement, it is appended when the query is assembled so that a later
+    // order_by_{field}() or limit() still comes before it.
+    let (lock_field, lock_init, lock_code, lock_check, lock_fragments) = if cfg!(
+        feature = "postgres"
+    ) {
+        (
+            quote! {
+                for_update: bool,
+                lock_timeout: Option<std::time::Duration>,
+                statement_timeout: Option<std::time::Duration>,
+            },
+            quote! {
+                for_update: false,
+                lock_timeout: None,
+                statement_timeout: None,
+            },
+            quote! {
+                /// Locks the selected rows until the end of the transaction (`FOR UPDATE`).
+                #struct_visibility fn for_update(mut self) -> Self {
+                    self.for_update = true;
+                    self
+                }
+
+                /// Bounds the time spent waiting for row locks, e.g. with `for_update()`. Requires
+                /// `build_locked()`, which issues `SET LOCAL lock_timeout` before the query.
+                #struct_visibility fn lock_timeout(mut self, timeout: std::time::Duration) -> Self {
+                    self.lock_timeout = Some(timeout);
+                    self
+                }
+
+                /// Makes the server abort the query when it runs longer than `timeout`, unlike a
+                /// timeout on the future which leaves it running. Requires `build_locked()`, which
+                /// issues `SET LOCAL statement_timeout` before the query, as with `lock_timeout()`.
+                #struct_visibility fn statement_timeout(mut self, timeout: std::time::Duration) -> Self {
+                    self.statement_timeout = Some(timeout);
+                    self
+                }
+
+                /// Same as `build()`, applying the `lock_timeout()` and `statement_timeout()` first.
+                /// A lock that cannot be acquired in time fails with a `DatabaseError` (SQLSTATE
+                /// `55P03`), a query running too long with SQLSTATE `57014`.
+                ///
+                /// `SET LOCAL` is a no-op outside of a transaction, so the settings and the query run
+                /// in one opened with `begin()`: a new transaction on a pool or a connection, a
+                /// savepoint inside an existing transaction. The settings then last until the end
+                /// of that outer transaction. On a pool, the transaction is committed right after
+                /// the query, releasing the `for_update()` locks.
+                #struct_visibility async fn build_locked<'c, A>(mut self, conn: A) -> lorm::errors::Result<Vec<#struct_name>>
+                where
+                    A: sqlx::Acquire<'c, Database = sqlx::Postgres>,
+                {
+                    let mut tx = conn.begin().await?;
+                    // SET does not accept bound parameters, the values are plain integers.
+                    if let Some(timeout) = self.lock_timeout.take() {
+                        let sql = format!("SET LOCAL lock_timeout = {}", timeout.as_millis().max(1));
+                        sqlx::query(&sql).execute(&mut *tx).await?;
+                    }
+                    if let Some(timeout) = self.statement_timeout.take() {
+                        let sql = format!("SET LOCAL statement_timeout = {}", timeout.as_millis().max(1));
+                        sqlx::query(&sql).execute(&mut *tx).await?;
+                    }
+                    let persistent = self.persistent;
+                    let r = self
+                        .into_query_builder()?
+                        .build_query_as::<_>()
+                        .persistent(persistent)
+                        .fetch_all(&mut *tx)
+                        .await?;
+                    tx.commit().await?;
+                    Ok(r)
+                }
+            },
+            quote! {
+                if self.lock_timeout.is_some() || self.statement_timeout.is_some() {
+                    return Err(lorm::errors::Error::QueryPreparationError(
+                        "lock_timeout() and statement_timeout() are only applied by build_locked()".to_string(),
+                    ));
+                }
+            },
+            quote! {
+                if self.for_update {
+                    fragments.push(lorm::query::sql_fragment(" FOR UPDATE"));
+                }
+            },
+        )
+    } else {
+        (quote! {}, quote! {}, quote! {}, quote! {}, quote! {})
+    };
+
+    let (hint_field, hint_init, hint_code, hint_fragments) = if cfg!(feature = "mysql") {
+        (
+            quote! { table_hints: Vec<String>, },
+            quote! { table_hints: Vec::new(), },
+            quote! {
+                /// Adds `USE INDEX (index)` after the table name, suggesting the index to the optimizer.
+                #struct_visibility fn use_index(self, index: &str) -> Self {
+                    self.with_hint(&format!("USE INDEX ({index})"))
+                }
+
+                /// Adds a raw index hint after the table name, e.g. `FORCE INDEX (email)` or
+                /// `IGNORE INDEX (email)`. Nothing is escaped: never build `hint` from user input.
+                #struct_visibility fn with_hint(mut self, hint: &str) -> Self {
+                    self.table_hints.push(hint.to_string());
+                    self
+                }
+            },
+            quote! {
+                for hint in &self.table_hints {
+                    fragments.push(lorm::query::sql_fragment(format!(" {hint}")));
+                }
+            },
+        )
+    } else {
+        (quote! {}, quote! {}, quote! {}, quote! {})
+    };
+
+    // Soft-deleted rows are filtered in a derived table, so that the user's conditions, ORs
+    // included, cannot bring them back. Index hints go on the table inside. Without a table alias
+    // the derived table gets a fixed name, a schema-qualified table name is not a valid alias.
+    let (soft_delete_field, soft_delete_init, soft_delete_code, from_fragments) = match model
+        .soft_delete()
+    {
+        Some(column) => {
+            let derived_head = format!("(SELECT * FROM {table_name}");
+            let derived_tail = format!(
+                " WHERE {} IS NULL) {}",
+                column.column_name,
+                model.table_alias.as_deref().unwrap_or("lorm_rows")
+            );
+            (
+                quote! { with_deleted: bool, },
+                quote! { with_deleted: false, },
+                quote! {
+                    /// Includes the soft-deleted rows, which are left out by default.
+                    #struct_visibility fn with_deleted(mut self) -> Self {
+                        self.with_deleted = true;
+                        self
+                    }
+                },
+                quote! {
+                    if self.with_deleted {
+                        fragments.push(lorm::query::sql_fragment(format!("SELECT {projection} from {}", #from_clause)));
+                        #hint_fragments
+                    } else {
+                        fragments.push(lorm::query::sql_fragment(format!("SELECT {projection} from {}", #derived_head)));
+                        #hint_fragments
+                        fragments.push(lorm::query::sql_fragment(#derived_tail));
+                    }
+                },
+            )
+        }
+        None => (
+            quote! {},
+            quote! {},
+            quote! {},
+            quote! {
+                fragments.push(lorm::query::sql_fragment(format!("SELECT {projection} from {}", #from_clause)));
+                #hint_fragments
+            },
+        ),
+    };
+
+    // Columns that can be plucked, alone or as pairs of tuples in declaration order.
+    let pluck_columns = model
+        .query_columns()
+        .filter(|c| c.is_decodable())
+        .collect::<Vec<_>>();
+    let mut pluck_tokens = Vec::new();
+    for (i, first) in pluck_columns.iter().enumerate() {
+        for second in &pluck_columns[i + 1..] {
+            let pluck_fn = format_ident!("pluck_{}_{}", first.field, second.field);
+            let projection = format!(
+                "{}, {}",
+                model.qualified_column(&first.column_name),
+                model.qualified_column(&second.column_name)
+            );
+            let (first_ty, second_ty) = (&first.ty, &second.ty);
+            pluck_tokens.push(quote! {
+                #struct_visibility async fn #pluck_fn<'e, E: #executor_type>(self, executor: E) -> lorm::errors::Result<Vec<(#first_ty, #second_ty)>> {
+                    let persistent = self.persistent;
+                    let r = self
+                        .into_query_builder_selecting(#projection)?
+                        .build_query_as::<(#first_ty, #second_ty)>()
+                        .persistent(persistent)
+                        .fetch_all(executor)
+                        .await?;
+                    Ok(r)
+                }
+            });
+        }
+    }
+
+    // Regular expression filters on string columns.
+    let regex_tokens = model
+        .query_columns()
+        .filter(|c| !c.column_properties.store_as_text)
+        .filter(|c| {
+            to_column_type(&c.ty).is_ok_and(|ty| matches!(&ty, syn::Type::Path(p) if p.path.is_ident("str")))
+        })
+        .flat_map(|column| {
+            let column_name = model.qualified_column(&column.column_name);
+            [("where_regex", false), ("where_regex_i", true)]
+                .into_iter()
+                .map(move |(prefix, case_insensitive)| {
+                    let regex_fn = format_ident!("{}_{}", prefix, column.field);
+                    let body = if cfg!(feature = "postgres") {
+                        let operator = if case_insensitive { "~*" } else { "~" };
+                        let stmt = format!(" {column_name} {operator} ");
+                        quote! {
+                            let pattern: String = pattern.into();
+                            self.push_where_connector();
+                            self.push(#stmt);
+                            self.push_bind(pattern);
+                        }
+                    } else if cfg!(feature = "mysql") {
+                        let mode = if case_insensitive { "i" } else { "c" };
+                        let stmt = format!(" REGEXP_LIKE({column_name}, ");
+                        let tail = format!(", '{mode}')");
+                        quote! {
+                            let pattern: String = pattern.into();
+                            self.push_where_connector();
+                            self.push(#stmt);
+                            self.push_bind(pattern);
+                            self.push(#tail);
+                        }
+                    } else {
+                        let message = format!(
+                            "{regex_fn}() is not supported on SQLite, which has no REGEXP function by default"
+                        );
+                        quote! {
+                            let _ = pattern;
+                            self.preparation_error.get_or_insert_with(|| #message.to_string());
+                        }
+                    };
+                    quote! {
+                        #struct_visibility fn #regex_fn(mut self, pattern: impl Into<String>) -> Self {
+                            #body
+                            self
+                        }
+                    }
+                })
+                })
+        .collect::<Vec<_>>();
+
+    let distinct_tokens = pluck_columns
+        .iter()
+        .map(|column| {
+            let distinct_fn = format_ident!("distinct_values_{}", column.field);
+            let projection = format!(
+                "DISTINCT {}",
+                model.qualified_column(&column.column_name)
+            );
+            let ty = &column.ty;
+            quote! {
+                /// Runs the query selecting the distinct values of the column, e.g. for filter
+                /// dropdowns. Ordering by another column is rejected by some databases.
+                #struct_visibility async fn #distinct_fn<'e, E: #executor_type>(self, executor: E) -> lorm::errors::Result<Vec<#ty>> {
+                    let persistent = self.persistent;
+                    let r = self
+                        .into_query_builder_selecting(#projection)?
+                        .build_query_scalar::<#ty>()
+                        .persistent(persistent)
+                        .fetch_all(executor)
+                        .await?;
+                    Ok(r)
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    // String aggregation per group. The separator is inlined as a literal, as MySQL's
+    // `SEPARATOR` does not accept a bound value.
+    let group_concat_tokens = model
+        .query_columns()
+        .filter(|c| !c.column_properties.store_as_text)
+        .filter(|c| {
+            to_column_type(&c.ty).is_ok_and(|ty| matches!(&ty, syn::Type::Path(p) if p.path.is_ident("str")))
+        })
+        .map(|column| {
+            let group_concat_fn = format_ident!("group_concat_{}", column.field);
+            let column_name = model.qualified_column(&column.column_name);
+            let projection = if cfg!(feature = "postgres") {
+                let head = format!("string_agg({column_name}, '");
+                quote! { format!("{}{}')", #head, sep.replace('\'', "''")) }
+            } else if cfg!(feature = "mysql") {
+                let head = format!("GROUP_CONCAT({column_name} SEPARATOR '");
+                quote! { format!("{}{}')", #head, sep.replace('\\', "\\\\").replace('\'', "''")) }
+            } else {
+                let head = format!("group_concat({column_name}, '");
+                quote! { format!("{}{}')", #head, sep.replace('\'', "''")) }
+            };
+            let ty = if is_option_wrapped(&column.ty) {
+                quote! { Option<String> }
+            } else {
+                quote! { String }
+            };
+            quote! {
+                /// Runs the query selecting the values of the column joined by `sep`, one string per
+                /// group of `group_by_{field}()`. The GROUP BY is kept as written, unless `order_by_*()`,
+                /// `having_*()` or `limit()` were called before.
+                #struct_visibility async fn #group_concat_fn<'e, E: #executor_type>(mut self, sep: &str, executor: E) -> lorm::errors::Result<Vec<#ty>> {
+                    // Only the aggregate is selected, the other columns are not added to the GROUP BY.
+                    self.group_by_completed = true;
+                    let projection = #projection;
+                    let persistent = self.persistent;
+                    let r = self
+                        .into_query_builder_selecting(&projection)?
+                        .build_query_scalar::<#ty>()
+                        .persistent(persistent)
+                        .fetch_all(executor)
+                        .await?;
+                    Ok(r)
+                }
+            }
+        })
+        .collect::<Vec<_>>();
 
     Ok(quote! {
         #struct_visibility trait #trait_ident<#lifetime> {
             fn select() -> #builder_struct_ident<#lifetime>;
+
+            #(#ordered_trait_tokens)*
         }
 
         #[automatically_derived]
         impl<#lifetime> #trait_ident<#lifetime> for #struct_name {
             fn select() -> #builder_struct_ident<#lifetime> {
-                let builder = sqlx::QueryBuilder::new(#select_base);
-                #builder_struct_ident {
-                    builder,
-                    all_columns: vec![#(#all_column_names.to_string()),*],
-                    grouped_columns: Vec::new(),
-                    is_where: false,
-                    is_having: false,
-                    is_group_by: false,
-                    group_by_completed: false,
-                    is_order_by: false,
-                }
+                #builder_struct_ident::new()
             }
+
+            #(#ordered_impl_tokens)*
         }
 
+        /// Holds its SQL and bound values as replayable fragments, so that a base query can be
+        /// cloned and each clone refined independently.
+        #[derive(Clone)]
         #struct_visibility struct #builder_struct_ident<#lifetime> {
-            builder: sqlx::QueryBuilder<#lifetime, #database_type>,
+            projections: Vec<String>,
+            joins: Vec<String>,
+            fragments: Vec<lorm::query::SqlFragment<#lifetime, #database_type>>,
+            ctes: Vec<(String, Vec<lorm::query::SqlFragment<#lifetime, #database_type>>)>,
+            preparation_error: Option<String>,
             all_columns: Vec<String>,
             grouped_columns: Vec<String>,
             is_where: bool,
             is_having: bool,
             is_group_by: bool,
             group_by_completed: bool,
-            is_order_by: bool
+            is_order_by: bool,
+            is_limited: bool,
+            group_depth: i32,
+            pending_groups: u32,
+            is_or: bool,
+            persistent: bool,
+            #lock_field
+            #hint_field
+            #soft_delete_field
         }
 
         #[automatically_derived]
         impl<#lifetime> #builder_struct_ident<#lifetime> {
             #struct_visibility fn with_initial_where<T>(fk_col: &str, value: T) -> Self
             where
-                T: sqlx::Encode<#lifetime, #database_type> + sqlx::Type<#database_type> + Send + #lifetime,
+                T: sqlx::Encode<#lifetime, #database_type> + sqlx::Type<#database_type> + std::fmt::Debug + Clone + Send + Sync + #lifetime,
             {
-                let sql = format!("{}{} = ", #with_initial_where_prefix, fk_col);
-                let mut builder = sqlx::QueryBuilder::new(sql);
+                let mut builder = Self::new();
+                builder.push(format!("{}{} = ", #with_initial_where_prefix, fk_col));
                 builder.push_bind(value);
+                builder.is_where = true;
+                builder
+            }
+
+            fn new() -> Self {
                 Self {
-                    builder,
+                    projections: Vec::new(),
+                    joins: Vec::new(),
+                    fragments: Vec::new(),
+                    ctes: Vec::new(),
+                    preparation_error: None,
                     all_columns: vec![#(#all_column_names.to_string()),*],
                     grouped_columns: Vec::new(),
-                    is_where: true,
+                    is_where: false,
                     is_having: false,
                     is_group_by: false,
                     group_by_completed: false,
                     is_order_by: false,
+                    is_limited: false,
+                    group_depth: 0,
+                    pending_groups: 0,
+                    is_or: false,
+                    persistent: true,
+                    #lock_init
+                    #hint_init
+                    #soft_delete_init
+                }
+            }
+
+            fn push(&mut self, sql: impl std::fmt::Display) {
+                self.fragments.push(lorm::query::sql_fragment(sql.to_string()));
+            }
+
+            fn push_bind<T>(&mut self, value: T)
+            where
+                T: sqlx::Encode<#lifetime, #database_type> + sqlx::Type<#database_type> + std::fmt::Debug + Clone + Send + Sync + #lifetime,
+            {
+                self.fragments.push(lorm::query::bind_fragment(value));
+            }
+
+            /// Prepends `WITH name AS (...)` built from another select builder, whose bound values
+            /// come first in the final query. Reference it from this query, e.g. with `raw_where()`.
+            #struct_visibility fn with_cte(mut self, name: &str, cte: impl lorm::query::SubQuery<#lifetime, #database_type>) -> Self {
+                match cte.into_fragments() {
+                    Ok(fragments) => self.ctes.push((name.to_string(), fragments)),
+                    Err(e) => self.preparation_error = Some(format!("invalid common table expression {name}: {e}")),
+                }
+                self
+            }
+
+            /// Adds `{rank}() OVER (PARTITION BY ... ORDER BY ...) AS {alias}` to the selected columns.
+            /// Columns are given by name, as in SQL. Read the extra column with `build_as()`.
+            #struct_visibility fn window_rank(mut self, rank: lorm::predicates::Rank, partition_by: &[&str], order_by: &[(&str, lorm::predicates::OrderBy)], alias: &str) -> Self {
+                let mut window = Vec::new();
+                if !partition_by.is_empty() {
+                    window.push(format!("PARTITION BY {}", partition_by.join(", ")));
                 }
+                if !order_by.is_empty() {
+                    let columns = order_by
+                        .iter()
+                        .map(|(column, order)| format!("{column} {order}"))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    window.push(format!("ORDER BY {columns}"));
+                }
+                self.projections.push(format!("{rank}() OVER ({}) AS {alias}", window.join(" ")));
+                self
             }
+
+            #where_support
+
+            #(#where_tokens)*
+
             fn complete_group_by(&mut self) {
                 if self.is_group_by && !self.group_by_completed {
                     let remaining: Vec<&String> = self.all_columns.iter()
                         .filter(|c| !self.grouped_columns.contains(c))
                         .collect();
                     if !remaining.is_empty() {
-                        self.builder.push(format!(", {}", remaining.iter().map(|s| s.as_str()).collect::<Vec<&str>>().join(", ")));
+                        self.push(format!(", {}", remaining.iter().map(|s| s.as_str()).collect::<Vec<&str>>().join(", ")));
                     }
                     self.group_by_completed = true;
                 }
@@ -185,51 +726,953 @@ pub fn generate_select(
             #struct_visibility fn having_all_count(mut self, op: lorm::predicates::Having, value: i64) -> Self {
                 self.complete_group_by();
                 if self.is_having == false {
-                    self.builder.push(" HAVING");
+                    self.push(" HAVING");
                     self.is_having = true;
                 } else {
-                    self.builder.push(" AND");
+                    self.push(" AND");
                 }
                 let stmt = format!(" COUNT(*) {} ", op).to_string();
-                self.builder.push(stmt);
-                self.builder.push_bind(value);
+                self.push(stmt);
+                self.push_bind(value);
                 self
             }
 
-            #struct_visibility fn asc(mut self) -> Self {
-                self.builder.push(" ASC ");
+            #struct_visibility fn limit(mut self, limit: i64) -> Self {
+                self.complete_group_by();
+                self.push(" LIMIT ");
+                self.push_bind(limit);
+                self.is_limited = true;
                 self
             }
 
-            #struct_visibility fn desc(mut self) -> Self {
-                self.builder.push(" DESC ");
+            #struct_visibility fn offset(mut self, offset: i64) -> Self {
+                self.push(" OFFSET ");
+                self.push_bind(offset);
+                self.is_limited = true;
                 self
             }
 
-            #struct_visibility fn limit(mut self, limit: i64) -> Self {
-                self.complete_group_by();
-                self.builder.push(" LIMIT ");
-                self.builder.push_bind(limit);
-                self
+            /// Records a preparation error when `terminal`, which adds its own `LIMIT`, follows
+            /// `limit()` or `offset()`.
+            fn reject_limited(&mut self, terminal: &str) {
+                if self.is_limited {
+                    self.preparation_error.get_or_insert_with(|| {
+                        format!("{terminal} adds its own LIMIT and cannot follow limit() or offset()")
+                    });
+                }
             }
 
-            #struct_visibility fn offset(mut self, offset: i64) -> Self {
-                self.builder.push(" OFFSET ");
-                self.builder.push_bind(offset);
+            #fetch_code
+
+            /// Whether sqlx caches the prepared statement on the connection, `true` by default.
+            /// Turn it off for one-off dynamic queries, which would otherwise fill the cache.
+            #struct_visibility fn persistent(mut self, value: bool) -> Self {
+                self.persistent = value;
                 self
             }
 
+            /// Skips `offset` rows after a `seek_after_{field}()` keyset. Same SQL as `offset()`,
+            /// named for the hybrid keyset/offset pagination it is meant for.
+            #struct_visibility fn then_offset(self, offset: i64) -> Self {
+                self.offset(offset)
+            }
+
             #(#impl_tokens)*
 
-            #struct_visibility async fn build<'e, E: #executor_type>(mut self, executor: E) -> lorm::errors::Result<Vec<#struct_name>> {
-                self.complete_group_by();
+            #(#pluck_tokens)*
+
+            #(#distinct_tokens)*
+
+            #(#group_concat_tokens)*
+
+            #(#regex_tokens)*
+
+            #count_estimate_code
+
+            #lock_code
+
+            #hint_code
+
+            #soft_delete_code
+
+            #struct_visibility async fn build<'e, E: #executor_type>(self, executor: E) -> lorm::errors::Result<Vec<#struct_name>> {
+                let persistent = self.persistent;
+                let r = self
+                    .into_query_builder()?
+                    .build_query_as::<_>()
+                    .persistent(persistent)
+                    .fetch_all(executor)
+                    .await?;
+                Ok(r)
+            }
+
+            #(#join_tokens)*
+
+            /// Same as `build()`, pairing each row with the `T` joined by `join_{relation}()`.
+            #struct_visibility async fn build_joined<'e, T, E: #executor_type>(self, executor: E) -> lorm::errors::Result<Vec<(#struct_name, T)>>
+            where
+                T: lorm::query::JoinTarget<#database_type>,
+            {
+                let persistent = self.persistent;
+                let prefix = format!("{}__", T::join_alias());
+                let rows = self
+                    .into_query_builder()?
+                    .build()
+                    .persistent(persistent)
+                    .fetch_all(executor)
+                    .await?;
+                let r = rows
+                    .iter()
+                    .map(|row| {
+                        let item = <#struct_name as sqlx::FromRow<_>>::from_row(row)?;
+                        Ok((item, T::from_prefixed_row(row, &prefix)?))
+                    })
+                    .collect::<Result<Vec<_>, sqlx::Error>>()?;
+                Ok(r)
+            }
+
+            /// Streams the rows in chunks of `size` rows, each one fetched with `LIMIT`/`OFFSET` so that
+            /// only one chunk is held in memory. Rows are ordered by primary key unless ordered with
+            /// `order_by_{field}()`, which should then be unique for chunks not to skip or repeat rows.
+            /// The executor runs one query per chunk, hence `Copy`, e.g. `&pool`. The stream yields a
+            /// `QueryPreparationError` after `limit()` or `offset()`, which the chunks replace.
+            #struct_visibility fn fetch_chunks<'e, E>(mut self, size: i64, executor: E) -> impl lorm::query::Stream<Item = lorm::errors::Result<Vec<#struct_name>>> + Send + 'e
+            where
+                E: #executor_type + Copy + 'e,
+                #lifetime: 'e,
+            {
+                self.reject_limited("fetch_chunks()");
+                if self.is_order_by == false {
+                    self.complete_group_by();
+                    self.push(#pk_order);
+                    self.is_order_by = true;
+                }
+                let persistent = self.persistent;
+                let projection = self.default_projection();
+                lorm::query::fetch_chunks(self.into_fragments_selecting(&projection), size, move |mut builder| async move {
+                    let r = builder
+                        .build_query_as::<#struct_name>()
+                        .persistent(persistent)
+                        .fetch_all(executor)
+                        .await?;
+                    Ok(r)
+                })
+            }
+
+            /// Same as `build()` with `LIMIT 1`, returning the first row or `None` when nothing matches.
+            /// Replaces `limit()`: fails with `QueryPreparationError` after `limit()` or `offset()`.
+            #struct_visibility async fn first<'e, E: #executor_type>(mut self, executor: E) -> lorm::errors::Result<Option<#struct_name>> {
+                self.reject_limited("first()");
+                let persistent = self.persistent;
                 let r = self
-                    .builder
+                    .limit(1)
+                    .into_query_builder()?
+                    .build_query_as::<_>()
+                    .persistent(persistent)
+                    .fetch_optional(executor)
+                    .await?;
+                Ok(r)
+            }
+
+            /// Same as `build()`, expecting exactly one row: fails with `Error::NotFound`
+            /// when nothing matches and with `MultipleRowsError` when several rows do.
+            /// Replaces `limit()`: fails with `QueryPreparationError` after `limit()` or `offset()`.
+            #struct_visibility async fn one<'e, E: #executor_type>(mut self, executor: E) -> lorm::errors::Result<#struct_name> {
+                self.reject_limited("one()");
+                let persistent = self.persistent;
+                let mut r: Vec<#struct_name> = self
+                    .limit(2)
+                    .into_query_builder()?
                     .build_query_as::<_>()
+                    .persistent(persistent)
+                    .fetch_all(executor)
+                    .await?;
+                match r.len() {
+                    0 => Err(sqlx::Error::RowNotFound.into()),
+                    1 => Ok(r.remove(0)),
+                    _ => Err(lorm::errors::Error::MultipleRowsError("one() matched more than one row".to_string())),
+                }
+            }
+
+            /// Same as `build()`, mapping the rows to `T`, e.g. a struct flattening `Self` next to the
+            /// columns added by `window_rank()`.
+            #struct_visibility async fn build_as<'e, T, E: #executor_type>(self, executor: E) -> lorm::errors::Result<Vec<T>>
+            where
+                T: for<'r> sqlx::FromRow<'r, <#database_type as sqlx::Database>::Row> + Send + Unpin,
+            {
+                let persistent = self.persistent;
+                let r = self
+                    .into_query_builder()?
+                    .build_query_as::<T>()
+                    .persistent(persistent)
                     .fetch_all(executor)
                     .await?;
                 Ok(r)
             }
+
+            /// Same as `build()`, returning the rows untyped, without going through `FromRow`.
+            /// Columns are read with `sqlx::Row`, e.g. `row.try_get::<String, _>("email")`.
+            #struct_visibility async fn fetch_rows<'e, E: #executor_type>(self, executor: E) -> lorm::errors::Result<Vec<<#database_type as sqlx::Database>::Row>> {
+                let persistent = self.persistent;
+                let r = self
+                    .into_query_builder()?
+                    .build()
+                    .persistent(persistent)
+                    .fetch_all(executor)
+                    .await?;
+                Ok(r)
+            }
+
+            /// Whether the query matches at least one row, as `SELECT EXISTS(SELECT 1 ...)`.
+            #struct_visibility async fn exists<'e, E: #executor_type>(self, executor: E) -> lorm::errors::Result<bool> {
+                let persistent = self.persistent;
+                let mut builder = sqlx::QueryBuilder::new("SELECT EXISTS(");
+                for fragment in self.into_fragments_selecting("1")? {
+                    fragment(&mut builder);
+                }
+                builder.push(")");
+                let r = builder
+                    .build_query_scalar::<#exists_type>()
+                    .persistent(persistent)
+                    .fetch_one(executor)
+                    .await?;
+                Ok(#exists_result)
+            }
+
+            /// Counts the rows `build()` would return, as `SELECT COUNT(*) FROM (...)`, without
+            /// fetching them. GROUP BY, LIMIT and OFFSET apply as they do in `build()`.
+            #struct_visibility async fn count<'e, E: #executor_type>(self, executor: E) -> lorm::errors::Result<i64> {
+                let persistent = self.persistent;
+                let projection = self.default_projection();
+                let mut builder = sqlx::QueryBuilder::new("SELECT COUNT(*) FROM (");
+                for fragment in self.into_fragments_selecting(&projection)? {
+                    fragment(&mut builder);
+                }
+                builder.push(") lorm_count");
+                let r = builder
+                    .build_query_scalar::<i64>()
+                    .persistent(persistent)
+                    .fetch_one(executor)
+                    .await?;
+                Ok(r)
+            }
+
+            /// Counts the distinct combinations of `columns` among the matching rows. Nothing is
+            /// escaped: never build `columns` from user input.
+            #struct_visibility async fn count_distinct<'e, E: #executor_type>(self, columns: &[&str], executor: E) -> lorm::errors::Result<i64> {
+                if columns.is_empty() {
+                    return Err(lorm::errors::Error::QueryPreparationError(
+                        "count_distinct() without any column".to_string(),
+                    ));
+                }
+                let persistent = self.persistent;
+                let columns = columns.join(", ");
+                let r = #count_distinct_query
+                    .build_query_scalar::<i64>()
+                    .persistent(persistent)
+                    .fetch_one(executor)
+                    .await?;
+                Ok(r)
+            }
+
+            /// Selects the single SQL expression `expr`, e.g. `MAX(price)`, instead of the model columns
+            /// and returns its value on the first row. Nothing is escaped: never build `expr` from
+            /// user input.
+            #struct_visibility async fn scalar<'e, T, E: #executor_type>(self, expr: &str, executor: E) -> lorm::errors::Result<T>
+            where
+                T: Send + Unpin,
+                (T,): for<'r> sqlx::FromRow<'r, <#database_type as sqlx::Database>::Row>,
+            {
+                let persistent = self.persistent;
+                let r = self
+                    .into_query_builder_selecting(expr)?
+                    .build_query_scalar::<T>()
+                    .persistent(persistent)
+                    .fetch_one(executor)
+                    .await?;
+                Ok(r)
+            }
+
+            /// Calls `f` with the SQL accumulated so far, e.g. for logging, and returns the builder
+            /// unchanged. Columns still to be added to a GROUP BY by `build()` are not shown.
+            #struct_visibility fn tap_sql(self, f: impl FnOnce(&str)) -> Self {
+                f(&self.sql());
+                self
+            }
+
+            /// The SQL accumulated so far, without running anything. Columns still to be added to
+            /// a GROUP BY by `build()` are not shown, use `into_sql()` for the exact statement.
+            #struct_visibility fn sql(&self) -> String {
+                let mut builder = sqlx::QueryBuilder::<#database_type>::new("");
+                for fragment in self.assemble_fragments(&self.default_projection()) {
+                    fragment(&mut builder);
+                }
+                builder.into_sql()
+            }
+
+            /// Same as `sql()`, along with the `Debug` output of every bound value in bind order,
+            /// e.g. `["3", "\"%@example.com\""]`. Meant for debugging, not for building SQL.
+            #struct_visibility fn sql_and_args(&self) -> (String, Vec<String>) {
+                lorm::query::record_args(|| self.sql())
+            }
+
+            /// The SQL `build()` would run, without running it.
+            #struct_visibility fn into_sql(self) -> lorm::errors::Result<String> {
+                Ok(self.into_query_builder()?.into_sql())
+            }
+
+            /// Hands the query over to sqlx, e.g. to use another fetch mode. `persistent()` is not applied.
+            /// The returned `QueryBuilder` owns the SQL and the bound values, while the query built
+            /// from it borrows it, so keep it in a local binding:
+            /// `let mut qb = builder.into_query_builder()?; qb.build_query_as::<T>().fetch_all(executor)`.
+            /// Bound values must outlive `'a`, like the builder's.
+            #struct_visibility fn into_query_builder(self) -> lorm::errors::Result<sqlx::QueryBuilder<#lifetime, #database_type>> {
+                let projection = self.default_projection();
+                self.into_query_builder_selecting(&projection)
+            }
+
+            fn into_query_builder_selecting(self, projection: &str) -> lorm::errors::Result<sqlx::QueryBuilder<#lifetime, #database_type>> {
+                #lock_check
+                let mut builder = sqlx::QueryBuilder::new("");
+                for fragment in self.into_fragments_selecting(projection)? {
+                    fragment(&mut builder);
+                }
+                Ok(builder)
+            }
+
+            /// The model columns followed by the extra projections, e.g. from `window_rank()`.
+            fn default_projection(&self) -> String {
+                let mut projection = #select_columns.to_string();
+                for extra in &self.projections {
+                    projection.push_str(", ");
+                    projection.push_str(extra);
+                }
+                projection
+            }
+
+            fn into_fragments_selecting(mut self, projection: &str) -> lorm::errors::Result<Vec<lorm::query::SqlFragment<#lifetime, #database_type>>> {
+                if let Some(e) = self.preparation_error.take() {
+                    return Err(lorm::errors::Error::QueryPreparationError(e));
+                }
+                self.check_balanced_groups()?;
+                self.complete_group_by();
+                Ok(self.assemble_fragments(projection))
+            }
+
+            /// The common table expressions, the `SELECT` head and the accumulated fragments.
+            fn assemble_fragments(&self, projection: &str) -> Vec<lorm::query::SqlFragment<#lifetime, #database_type>> {
+                let mut fragments = Vec::new();
+                for (i, (name, cte)) in self.ctes.iter().enumerate() {
+                    let head = if i == 0 { format!("WITH {name} AS (") } else { format!(", {name} AS (") };
+                    fragments.push(lorm::query::sql_fragment(head));
+                    fragments.extend(cte.iter().cloned());
+                    fragments.push(lorm::query::sql_fragment(") "));
+                }
+                #from_fragments
+                for join in &self.joins {
+                    fragments.push(lorm::query::sql_fragment(join.clone()));
+                }
+                fragments.extend(self.fragments.iter().cloned());
+                #lock_fragments
+                fragments
+            }
+        }
+
+        #[automatically_derived]
+        impl<#lifetime> lorm::query::SubQuery<#lifetime, #database_type> for #builder_struct_ident<#lifetime> {
+            fn into_fragments(self) -> lorm::errors::Result<Vec<lorm::query::SqlFragment<#lifetime, #database_type>>> {
+                let projection = self.default_projection();
+                self.into_fragments_selecting(&projection)
+            }
         }
     })
 }
+
+/// Exposes the columns selected, inserted and updated by the generated statements as constants.
+pub(crate) fn generate_select_columns(model: &OrmModel) -> TokenStream {
+    let struct_name = model.struct_name;
+    let struct_visibility = model.struct_visibility;
+    let select_columns = model.full_column_select();
+    let insert_columns = model.insert_columns().map(|c| c.column_name.as_str());
+    let update_columns = model.update_columns().map(|c| c.column_name.as_str());
+
+    quote! {
+        #[automatically_derived]
+        impl #struct_name {
+            /// The columns selected by the generated queries, e.g. `by_{field}()`.
+            #struct_visibility const SELECT_COLUMNS: &'static str = #select_columns;
+
+            /// Returns [`Self::SELECT_COLUMNS`].
+            #struct_visibility fn table_columns() -> &'static str {
+                Self::SELECT_COLUMNS
+            }
+
+            /// The columns written when inserting a row: readonly columns are left to the database.
+            #struct_visibility const INSERT_COLUMNS: &'static [&'static str] = &[#(#insert_columns),*];
+
+            /// The columns written when updating a row: neither the primary key, readonly columns, the
+            /// soft-delete column nor the version column, which is incremented in place.
+            #struct_visibility const UPDATE_COLUMNS: &'static [&'static str] = &[#(#update_columns),*];
+
+            /// Returns [`Self::INSERT_COLUMNS`].
+            #struct_visibility fn columns_for_insert() -> &'static [&'static str] {
+                Self::INSERT_COLUMNS
+            }
+
+            /// Returns [`Self::UPDATE_COLUMNS`].
+            #struct_visibility fn columns_for_update() -> &'static [&'static str] {
+                Self::UPDATE_COLUMNS
+            }
+        }
+    }
+}
+
+/// Generates `exists()` for the delete and update builders, checking whether their conditions
+/// match any row without consuming the builder.
+pub(crate) fn generate_where_exists(
+    struct_visibility: &syn::Visibility,
+    executor_type: &TokenStream,
+    table_name: &str,
+) -> TokenStream {
+    let exists_head = format!("SELECT 1 FROM {table_name}");
+    quote! {
+        /// Whether the conditions match at least one row, e.g. to check before running `execute()`.
+        #struct_visibility async fn exists<'e, E: #executor_type>(&self, executor: E) -> lorm::errors::Result<bool> {
+            if let Some(e) = &self.preparation_error {
+                return Err(lorm::errors::Error::QueryPreparationError(e.clone()));
+            }
+            self.check_balanced_groups()?;
+            let mut builder = sqlx::QueryBuilder::new(#exists_head);
+            for fragment in &self.fragments {
+                fragment(&mut builder);
+            }
+            builder.push(" LIMIT 1");
+            let r = builder.build().fetch_optional(executor).await?;
+            Ok(r.is_some())
+        }
+    }
+}
+
+/// Generates the `where_{field}()`, `where_{field}_with()`, `where_between_{field}()` and
+/// `where_between_exclusive_{field}()` methods shared by the builders, plus `where_is_{field}()`
+/// and `where_not_{field}()` on `bool` fields. The builder provides `push()`, `push_bind()`, the
+/// `fragments` field and the methods of [generate_where_support].
+///
+/// Columns are prefixed with the table alias when `qualified` is set.
+pub(crate) fn generate_where_methods(
+    model: &OrmModel,
+    database_type: &TokenStream,
+    qualified: bool,
+) -> syn::Result<Vec<TokenStream>> {
+    let struct_visibility = model.struct_visibility;
+    let lifetime = quote! {'a};
+
+    model
+        .query_columns()
+        .map(|column| {
+            let field_name = &column.field;
+            let column_name = if qualified {
+                model.qualified_column(&column.column_name)
+            } else {
+                column.column_name.clone()
+            };
+
+            let parameter = quote! {value};
+            let base_type = to_column_type(&column.ty)?;
+
+            // String columns take anything convertible into a `Cow<str>`: `&str`, `String`,
+            // `&String` or a `Cow` itself, without the caller converting it first.
+            let is_string = matches!(&base_type, syn::Type::Path(p) if p.path.is_ident("str"))
+                && !is_shared_str_type(&column.ty)
+                && !column.column_properties.store_as_text
+                && !column.column_properties.use_json;
+            let cow_type = quote! { std::borrow::Cow<#lifetime, str> };
+            let bind_param = |param: TokenStream| {
+                if is_string {
+                    Ok((
+                        quote! { impl std::convert::Into<#cow_type> },
+                        quote! { std::convert::Into::<#cow_type>::into(#param) },
+                    ))
+                } else {
+                    column.bind_param(&param, &lifetime)
+                }
+            };
+            let constraints = column.bind_constraint(database_type, &lifetime)?;
+            let value_constraints = if is_string {
+                quote! { #cow_type: sqlx::Encode<#lifetime, #database_type> + sqlx::Type<#database_type> }
+            } else {
+                constraints.clone()
+            };
+            let (param_type, param_use) = bind_param(parameter.clone())?;
+            let (left_type, left_use) = bind_param(quote! {left})?;
+            let (right_type, right_use) = bind_param(quote! {right})?;
+
+            // The closure of `where_{field}_with()` returns an owned value, e.g. `String` for `str`.
+            let owned_type = quote! { <#base_type as std::borrow::ToOwned>::Owned };
+            let (owned_constraint, owned_use) = if column.column_properties.store_as_text {
+                (
+                    quote! { String: sqlx::Encode<#lifetime, #database_type> + sqlx::Type<#database_type> },
+                    quote! { f(sql).to_string() },
+                )
+            } else {
+                (
+                    quote! { #owned_type: sqlx::Encode<#lifetime, #database_type> + sqlx::Type<#database_type> + Send },
+                    quote! { f(sql) },
+                )
+            };
+
+            // Offset-aware timestamps are compared in UTC.
+            let normalize = |value: TokenStream, borrowed: TokenStream| {
+                if column.column_properties.store_as_text {
+                    return value;
+                }
+                utc_normalization(&column.ty, &borrowed).unwrap_or(value)
+            };
+            let param_use = normalize(param_use.clone(), param_use);
+            let left_use = normalize(left_use.clone(), left_use);
+            let right_use = normalize(right_use.clone(), right_use);
+            let owned_use = normalize(owned_use.clone(), quote! { &#owned_use });
+
+            let where_fn = format_ident!("where_{}", field_name);
+            let or_where_fn = format_ident!("or_where_{}", field_name);
+            let where_with_fn = format_ident!("where_{}_with", field_name);
+            let where_between_fn = format_ident!("where_between_{}", field_name);
+            let where_between_exclusive_fn =
+                format_ident!("where_between_exclusive_{}", field_name);
+
+            let is_bool = matches!(&column.ty, syn::Type::Path(p) if p.path.is_ident("bool"));
+            let flag_tokens = if is_bool {
+                // SQLite has no boolean type, flags are stored as integers.
+                let (true_literal, false_literal) = if cfg!(feature = "sqlite") {
+                    ("1", "0")
+                } else {
+                    ("TRUE", "FALSE")
+                };
+                let where_is_fn = format_ident!("where_is_{}", field_name);
+                let where_not_fn = format_ident!("where_not_{}", field_name);
+                let is_stmt = format!(" {column_name} = {true_literal}");
+                let not_stmt = format!(" {column_name} = {false_literal}");
+                quote! {
+                    #struct_visibility fn #where_is_fn(mut self) -> Self {
+                        self.push_where_connector();
+                        self.push(#is_stmt);
+                        self
+                    }
+
+                    #struct_visibility fn #where_not_fn(mut self) -> Self {
+                        self.push_where_connector();
+                        self.push(#not_stmt);
+                        self
+                    }
+                }
+            } else {
+                quote! {}
+            };
+
+            let null_tokens = if is_option_wrapped(&column.ty) {
+                let where_null_fn = format_ident!("where_null_{}", field_name);
+                let where_not_null_fn = format_ident!("where_not_null_{}", field_name);
+                let null_stmt = format!(" {column_name} IS NULL");
+                let not_null_stmt = format!(" {column_name} IS NOT NULL");
+                quote! {
+                    #struct_visibility fn #where_null_fn(mut self) -> Self {
+                        self.push_where_connector();
+                        self.push(#null_stmt);
+                        self
+                    }
+
+                    #struct_visibility fn #where_not_null_fn(mut self) -> Self {
+                        self.push_where_connector();
+                        self.push(#not_null_stmt);
+                        self
+                    }
+                }
+            } else {
+                quote! {}
+            };
+
+            // Set comparisons against a bound array, PostgreSQL only.
+            let array_tokens = if cfg!(feature = "postgres")
+                && !column.column_properties.use_json
+                && !column.column_properties.store_as_text
+                && !is_shared_str_type(&column.ty)
+            {
+                let where_eq_any_fn = format_ident!("where_eq_any_{}", field_name);
+                let where_gt_all_fn = format_ident!("where_gt_all_{}", field_name);
+                let eq_any_stmt = format!(" {column_name} = ANY(");
+                let gt_all_stmt = format!(" {column_name} > ALL(");
+                let array_constraints = quote! {
+                    &#lifetime [#owned_type]: sqlx::Encode<#lifetime, #database_type> + sqlx::Type<#database_type>,
+                    #owned_type: Sync
+                };
+                quote! {
+                    /// Keeps the rows whose column equals any element of `values`, bound as a
+                    /// single array. An empty slice matches no row.
+                    #struct_visibility fn #where_eq_any_fn(mut self, values: &#lifetime [#owned_type]) -> Self where #array_constraints {
+                        self.push_where_connector();
+                        self.push(#eq_any_stmt);
+                        self.push_bind(values);
+                        self.push(")");
+                        self
+                    }
+
+                    /// Keeps the rows whose column is greater than every element of `values`, bound
+                    /// as a single array. An empty slice matches every row.
+                    #struct_visibility fn #where_gt_all_fn(mut self, values: &#lifetime [#owned_type]) -> Self where #array_constraints {
+                        self.push_where_connector();
+                        self.push(#gt_all_stmt);
+                        self.push_bind(values);
+                        self.push(")");
+                        self
+                    }
+                }
+            } else {
+                quote! {}
+            };
+
+            // IN lists, JSON columns are left out.
+            let in_tokens = if column.column_properties.use_json {
+                quote! {}
+            } else {
+                let where_in_fn = format_ident!("where_in_{}", field_name);
+                let where_not_in_fn = format_ident!("where_not_in_{}", field_name);
+                let where_in_ref_fn = format_ident!("where_in_{}_ref", field_name);
+                let (item_type, item_value) = if column.column_properties.store_as_text {
+                    (
+                        quote! { impl std::borrow::Borrow<#base_type> },
+                        quote! { value.borrow().to_string() },
+                    )
+                } else if is_primitive_type(&base_type) {
+                    (quote! { #base_type }, quote! { *value })
+                } else {
+                    (
+                        quote! { impl std::borrow::Borrow<#base_type> },
+                        quote! { value.borrow() },
+                    )
+                };
+                let (ref_constraints, ref_item_value) = if column.column_properties.store_as_text {
+                    (constraints.clone(), quote! { value.to_string() })
+                } else {
+                    (
+                        quote! { &#lifetime #base_type: sqlx::Encode<#lifetime, #database_type> + sqlx::Type<#database_type>, #base_type: Sync },
+                        quote! { *value },
+                    )
+                };
+                // An empty list keeps no row for IN and every row for NOT IN.
+                let in_list = |head: String, empty: &str, item_value: &TokenStream| {
+                    quote! {
+                        self.push_where_connector();
+                        if values.is_empty() {
+                            self.push(#empty);
+                            return self;
+                        }
+                        self.push(#head);
+                        for (i, value) in values.iter().enumerate() {
+                            if i > 0 {
+                                self.push(", ");
+                            }
+                            self.push_bind(#item_value);
+                        }
+                        self.push(")");
+                        self
+                    }
+                };
+                let in_body = in_list(format!(" {column_name} IN ("), " 1 = 0", &item_value);
+                let not_in_body =
+                    in_list(format!(" {column_name} NOT IN ("), " 1 = 1", &item_value);
+                let in_ref_body = in_list(format!(" {column_name} IN ("), " 1 = 0", &ref_item_value);
+                quote! {
+                    /// Keeps the rows whose column is one of `values`. An empty slice matches no row.
+                    #struct_visibility fn #where_in_fn(mut self, values: &#lifetime [#item_type]) -> Self where #constraints {
+                        #in_body
+                    }
+
+                    /// Keeps the rows whose column is none of `values`. An empty slice matches every row.
+                    #struct_visibility fn #where_not_in_fn(mut self, values: &#lifetime [#item_type]) -> Self where #constraints {
+                        #not_in_body
+                    }
+
+                    /// Keeps the rows whose column is one of `values`, bound without being cloned.
+                    /// An empty slice matches no row.
+                    #struct_visibility fn #where_in_ref_fn(mut self, values: &#lifetime [&#lifetime #base_type]) -> Self where #ref_constraints {
+                        #in_ref_body
+                    }
+                }
+            };
+
+            // Reversed bounds are normalized: PostgreSQL has BETWEEN SYMMETRIC, the lower and
+            // upper bounds are picked in SQL otherwise.
+            let (least, greatest) = if cfg!(feature = "sqlite") {
+                ("min", "max")
+            } else {
+                ("LEAST", "GREATEST")
+            };
+            let ordered_bounds = |head: String, separator: String| {
+                let lower_head = format!("{head}{least}(");
+                let upper_head = format!("){separator}{greatest}(");
+                quote! {
+                    let left = #left_use;
+                    let right = #right_use;
+                    self.push(#lower_head);
+                    self.push_bind(left.clone());
+                    self.push(", ");
+                    self.push_bind(right.clone());
+                    self.push(#upper_head);
+                    self.push_bind(left);
+                    self.push(", ");
+                    self.push_bind(right);
+                    self.push(")");
+                }
+            };
+            let between_body = if cfg!(feature = "postgres") {
+                let head = format!(" {column_name} BETWEEN SYMMETRIC ");
+                quote! {
+                    self.push(#head);
+                    self.push_bind(#left_use);
+                    self.push(" AND ");
+                    self.push_bind(#right_use);
+                }
+            } else {
+                ordered_bounds(format!(" {column_name} BETWEEN "), " AND ".to_string())
+            };
+            let between_exclusive_body = ordered_bounds(
+                format!(" {column_name} > "),
+                format!(" AND {column_name} < "),
+            );
+
+            // Only PostgreSQL has ILIKE, the others compare the lowercased column and pattern.
+            // MySQL has no IS DISTINCT FROM either, it negates its null-safe equality instead.
+            let comparison = if cfg!(feature = "postgres") {
+                quote! { (format!(" {} {} ", #column_name, op), None::<&str>) }
+            } else if cfg!(feature = "mysql") {
+                quote! {
+                    match op {
+                        lorm::predicates::Where::ILike => (format!(" LOWER({}) LIKE LOWER(", #column_name), Some(")")),
+                        lorm::predicates::Where::DistinctFrom => (format!(" NOT ({} <=> ", #column_name), Some(")")),
+                        _ => (format!(" {} {} ", #column_name, op), None),
+                    }
+                }
+            } else {
+                quote! {
+                    if op == lorm::predicates::Where::ILike {
+                        (format!(" LOWER({}) LIKE LOWER(", #column_name), Some(")"))
+                    } else {
+                        (format!(" {} {} ", #column_name, op), None)
+                    }
+                }
+            };
+
+            // Decimal strings only compare for equality, `'10' < '9'`: range filters are left out
+            // and range operators rejected when the query is run.
+            let (text_guard, between_tokens) = if column.column_properties.store_as_text {
+                let message = format!(
+                    "only equality operators apply to the store_as = \"text\" column {column_name}, got "
+                );
+                let guard = quote! {
+                    if !matches!(
+                        op,
+                        lorm::predicates::Where::Eq
+                            | lorm::predicates::Where::NotEq
+                            | lorm::predicates::Where::DistinctFrom
+                            | lorm::predicates::Where::NotDistinctFrom
+                    ) {
+                        self.preparation_error.get_or_insert_with(|| format!("{}{:?}", #message, op));
+                        return self;
+                    }
+                };
+                (guard, quote! {})
+            } else {
+                let between = quote! {
+                    /// The bounds may be given in any order, `(4, 2)` matches the same rows as `(2, 4)`.
+                    #struct_visibility fn #where_between_fn(mut self, left: #left_type, right: #right_type) -> Self where #value_constraints {
+                        self.push_where_connector();
+                        #between_body
+                        self
+                    }
+
+                    /// The bounds may be given in any order, `(4, 2)` matches the same rows as `(2, 4)`.
+                    #struct_visibility fn #where_between_exclusive_fn(mut self, left: #left_type, right: #right_type) -> Self where #value_constraints {
+                        self.push_where_connector();
+                        #between_exclusive_body
+                        self
+                    }
+                };
+                (quote! {}, between)
+            };
+
+            Ok(quote! {
+                #flag_tokens
+
+                #null_tokens
+
+                #in_tokens
+
+                #array_tokens
+
+                #struct_visibility fn #where_fn(mut self, op: lorm::predicates::Where, #parameter: #param_type) -> Self where #value_constraints {
+                    if op == lorm::predicates::Where::Any {
+                        // A skipped condition leaves no pending OR for the next one.
+                        self.is_or = false;
+                        return self;
+                    }
+                    #text_guard
+                    self.push_where_connector();
+                    let (stmt, close) = #comparison;
+                    self.push(stmt);
+                    self.push_bind(#param_use);
+                    if let Some(close) = close {
+                        self.push(close);
+                    }
+                    self
+                }
+
+                /// Same as `where_{field}()`, joined with OR to the condition before it.
+                #struct_visibility fn #or_where_fn(self, op: lorm::predicates::Where, #parameter: #param_type) -> Self where #value_constraints {
+                    self.or().#where_fn(op, #parameter)
+                }
+
+                /// Same as `where_{field}()`, the value being computed by `f` when the query is
+                /// built, from the SQL built up to the bound value.
+                #struct_visibility fn #where_with_fn<F>(mut self, op: lorm::predicates::Where, f: F) -> Self
+                where
+                    F: Fn(&str) -> #owned_type + Send + Sync + #lifetime,
+                    #owned_constraint,
+                {
+                    if op == lorm::predicates::Where::Any {
+                        // A skipped condition leaves no pending OR for the next one.
+                        self.is_or = false;
+                        return self;
+                    }
+                    #text_guard
+                    self.push_where_connector();
+                    let (stmt, close) = #comparison;
+                    self.push(stmt);
+                    self.fragments.push(lorm::query::lazy_bind_fragment(move |sql: &str| #owned_use));
+                    if let Some(close) = close {
+                        self.push(close);
+                    }
+                    self
+                }
+
+                #between_tokens
+            })
+        })
+        .collect()
+}
+
+/// Generates `raw_where()`, the `begin_group()`/`end_group()` grouping and the WHERE connector
+/// shared by the builders, which provide the `is_where`, `pending_groups`, `is_or`,
+/// `group_depth` and `preparation_error` fields.
+///
+/// `builder` names the builder and `terminal` its terminal method in the error messages.
+pub(crate) fn generate_where_support(
+    struct_visibility: &syn::Visibility,
+    builder: &str,
+    terminal: &str,
+) -> TokenStream {
+    let unbalanced = format!("unbalanced begin_group()/end_group() in {builder}");
+    let unmatched = format!("end_group() without a matching begin_group() in {builder}");
+    let debug_assert_doc = format!(
+        " `{terminal}` performs the same check and returns a `QueryPreparationError` in release builds."
+    );
+    quote! {
+            /// Adds a raw SQL condition, as is, to the WHERE clause. Nothing is escaped or bound:
+            /// never build `sql` from user input.
+            #struct_visibility fn raw_where(mut self, sql: &str) -> Self {
+                self.push_where_connector();
+                self.push(format!(" {sql}"));
+                self
+            }
+
+            /// Pushes the connector preceding a WHERE condition: ` WHERE` for the first one, ` OR`
+            /// after `or()` and ` AND` otherwise, followed by the parentheses of the groups opened
+            /// since the previous condition.
+            fn push_where_connector(&mut self) {
+                if self.is_where == false {
+                    self.push(" WHERE");
+                    self.is_where = true;
+                } else {
+                    self.push(if self.is_or { " OR" } else { " AND" });
+                }
+                for _ in 0..self.pending_groups {
+                    self.push(" (");
+                }
+                self.pending_groups = 0;
+                self.is_or = false;
+            }
+
+            /// Joins the next condition with OR instead of AND: `WHERE a = ? OR b = ?`. AND binds
+            /// tighter than OR, `a OR b AND c` reads `a OR (b AND c)`: group the conditions with
+            /// `where_group()` to combine them differently.
+            #struct_visibility fn or(mut self) -> Self {
+                self.is_or = true;
+                self
+            }
+
+            /// Opens a parenthesized group of WHERE conditions. Must be closed with `end_group()`.
+            /// The parenthesis is only written along with the first condition of the group.
+            #struct_visibility fn begin_group(mut self) -> Self {
+                self.pending_groups += 1;
+                self.group_depth += 1;
+                self
+            }
+
+            /// Closes the group opened by the last unmatched `begin_group()`. An empty group is
+            /// left out, along with the `or()` preceding it.
+            #struct_visibility fn end_group(mut self) -> Self {
+                if self.group_depth == 0 {
+                    self.preparation_error.get_or_insert_with(|| #unmatched.to_string());
+                    return self;
+                }
+                self.group_depth -= 1;
+                if self.pending_groups > 0 {
+                    self.pending_groups -= 1;
+                    if self.pending_groups == 0 {
+                        self.is_or = false;
+                    }
+                } else {
+                    self.push(" )");
+                }
+                self
+            }
+
+            /// Adds the conditions set by `f` as a parenthesized group, joined with AND.
+            /// Nothing is added if `f` adds no condition.
+            #struct_visibility fn where_group<F>(self, f: F) -> Self
+            where
+                F: FnOnce(Self) -> Self,
+            {
+                f(self.begin_group()).end_group()
+            }
+
+            /// Adds the conditions set by `f` as a parenthesized group, joined with OR to the
+            /// conditions before it: `WHERE a = ? OR (b = ? AND c = ?)`. AND binds tighter than
+            /// OR, wrap the preceding conditions in a group to OR them as a whole.
+            /// Nothing is added if `f` adds no condition.
+            #struct_visibility fn or_where_group<F>(self, f: F) -> Self
+            where
+                F: FnOnce(Self) -> Self,
+            {
+                f(self.or().begin_group()).end_group()
+            }
+
+            /// Panics in debug builds if a `begin_group()` has no matching `end_group()` so far.
+            #[doc = #debug_assert_doc]
+            #struct_visibility fn debug_assert_balanced_groups(self) -> Self {
+                debug_assert_eq!(self.group_depth, 0, #unbalanced);
+                self
+            }
+
+            fn check_balanced_groups(&self) -> lorm::errors::Result<()> {
+                debug_assert_eq!(self.group_depth, 0, #unbalanced);
+                if self.group_depth != 0 {
+                    return Err(lorm::errors::Error::QueryPreparationError(format!(
+                        "{} (depth {})",
+                        #unbalanced,
+                        self.group_depth
+                    )));
+                }
+                Ok(())
+            }
+    }
+}
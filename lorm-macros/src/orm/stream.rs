@@ -0,0 +1,29 @@
+use crate::models::OrmModel;
+use quote::{__private::TokenStream, format_ident, quote};
+
+/// Generates `stream_all(executor)`, streaming every row of the table instead of loading them
+/// all in memory, e.g. for exports.
+pub fn generate_stream(executor_type: &TokenStream, model: &OrmModel) -> TokenStream {
+    let trait_ident = format_ident!("{}StreamTrait", model.struct_name);
+    let struct_name = model.struct_name;
+    let struct_visibility = model.struct_visibility;
+    let sql = format!(
+        "SELECT {} FROM {}{}",
+        model.full_column_select(),
+        model.table_name,
+        model.where_not_deleted()
+    );
+
+    quote! {
+        #struct_visibility trait #trait_ident<'e, E: #executor_type>: Sized {
+            fn stream_all(executor: E) -> impl lorm::query::Stream<Item = lorm::errors::Result<Self>> + Send + 'e;
+        }
+
+        #[automatically_derived]
+        impl<'e, E: #executor_type + 'e> #trait_ident<'e, E> for #struct_name {
+            fn stream_all(executor: E) -> impl lorm::query::Stream<Item = lorm::errors::Result<Self>> + Send + 'e {
+                lorm::query::map_stream_err(sqlx::query_as::<_, #struct_name>(#sql).fetch(executor))
+            }
+        }
+    }
+}
@@ -0,0 +1,245 @@
+use crate::models::OrmModel;
+use crate::utils::{db_placeholder, is_option_wrapped};
+use quote::{__private::TokenStream, format_ident, quote};
+
+/// Generates the `{Struct}Tracked` wrapper, returned by `tracked()`, which records the fields
+/// changed through its `set_{field}()` setters so that its `save()` only updates their columns.
+///
+/// The wrapper derefs to the row for reads. As with `apply_patch()`, `created_at` is never written,
+/// `updated_at` and the version are bumped, and flattened columns have no setter.
+pub fn generate_tracked(
+    executor_type: &TokenStream,
+    database_type: &TokenStream,
+    model: &OrmModel,
+) -> syn::Result<TokenStream> {
+    let tracked_ident = format_ident!("{}Tracked", model.struct_name);
+    let struct_name = model.struct_name;
+    let struct_visibility = model.struct_visibility;
+    let table_name = &model.table_name;
+    let full_select_columns = model.full_column_select();
+    let row = quote! {self.row};
+
+    let columns = model
+        .update_columns()
+        .filter(|c| {
+            !c.is_flattened && !c.column_properties.created_at && !c.column_properties.updated_at
+        })
+        .collect::<Vec<_>>();
+    if columns.is_empty() {
+        return Ok(quote! {});
+    }
+    let columns_count = columns.len();
+    let column_names = columns.iter().map(|c| c.column_name.as_str());
+
+    let setters = columns.iter().enumerate().map(|(i, column)| {
+        let field = &column.field;
+        let ty = &column.ty;
+        let set_fn = format_ident!("set_{}", column.field);
+        quote! {
+            /// Sets the field and marks its column as changed.
+            #struct_visibility fn #set_fn(&mut self, value: #ty) -> &mut Self {
+                self.row.#field = value;
+                self.changed[#i] = true;
+                self
+            }
+        }
+    });
+
+    let assignments = columns.iter().enumerate().map(|(i, column)| {
+        let assignment = format!("{} = ", column.column_name);
+        let value = column.accessor_on(&row);
+        let bind_value = if column.column_properties.use_json {
+            if is_option_wrapped(&column.ty) {
+                quote! { (#value).as_ref().map(sqlx::types::Json) }
+            } else {
+                quote! { sqlx::types::Json(#value) }
+            }
+        } else {
+            column.bind_value(value)
+        };
+        quote! {
+            if self.changed[#i] {
+                assignments.push(#assignment);
+                assignments.push_bind_unseparated(#bind_value);
+            }
+        }
+    });
+
+    let updated_at_code = match model.updated_at() {
+        Some(column) if !column.column_properties.readonly => {
+            let head = format!(", {} = ", column.column_name);
+            let new_method = &column.column_properties.new_expression;
+            quote! {
+                builder.push(#head);
+                builder.push_bind(#new_method);
+            }
+        }
+        _ => quote! {},
+    };
+
+    let pk_fields = model.primary_key.fields();
+    let pk_conditions = pk_fields.iter().enumerate().map(|(i, column)| {
+        let head = if i == 0 {
+            format!(" WHERE {} = ", column.column_name)
+        } else {
+            format!(" AND {} = ", column.column_name)
+        };
+        let value = column.bind_value(column.accessor_on(&row));
+        quote! {
+            builder.push(#head);
+            builder.push_bind(#value);
+        }
+    });
+
+    // The version read with the row must still be the stored one.
+    let (version_code, version_condition, no_row_error) = match model.version() {
+        Some(column) => {
+            let increment = format!(", {}", model.version_increment().unwrap_or_default());
+            let condition = format!(" AND {} = ", column.column_name);
+            let value = column.accessor_on(&row);
+            (
+                quote! {
+                    builder.push(#increment);
+                },
+                quote! {
+                    builder.push(#condition);
+                    builder.push_bind(#value);
+                },
+                quote! { lorm::errors::Error::OptimisticLockConflict },
+            )
+        }
+        None => (
+            quote! {},
+            quote! {},
+            quote! { lorm::errors::Error::NotFound },
+        ),
+    };
+
+    // MySQL has no RETURNING, and SQLite's yields the row before AFTER triggers ran, so the
+    // row is read back once the update is done.
+    let reads_back =
+        cfg!(feature = "mysql") || (cfg!(feature = "sqlite") && model.has_db_managed_columns());
+    let (executor_bound, fetch_code) = if reads_back {
+        let pk_where = pk_fields
+            .iter()
+            .enumerate()
+            .map(|(i, col)| {
+                Ok(format!(
+                    "{} = {}",
+                    col.column_name,
+                    db_placeholder(col.base_field, i + 1)?
+                ))
+            })
+            .collect::<syn::Result<Vec<_>>>()?
+            .join(" AND ");
+        let select_sql = format!("SELECT {full_select_columns} FROM {table_name} WHERE {pk_where}");
+        let pk_binds = pk_fields
+            .iter()
+            .map(|col| col.bind_value(col.accessor_on(&row)));
+        // MySQL only counts the rows actually changed, which a bumped version always is.
+        let execute_code = if model.version().is_some() {
+            quote! {
+                let r = builder.build().execute(executor).await?;
+                if r.rows_affected() == 0 {
+                    return Err(#no_row_error);
+                }
+            }
+        } else {
+            quote! {
+                builder.build().execute(executor).await?;
+            }
+        };
+        (
+            quote! { E: #executor_type + Copy },
+            quote! {
+                #execute_code
+                let r = sqlx::query_as::<_, #struct_name>(#select_sql)
+                #(
+                    .bind(#pk_binds)
+                )*
+                .fetch_one(executor).await?;
+                Ok(r)
+            },
+        )
+    } else {
+        let returning = format!(" RETURNING {full_select_columns}");
+        (
+            quote! { E: #executor_type },
+            quote! {
+                builder.push(#returning);
+                let r = builder
+                    .build_query_as::<#struct_name>()
+                    .fetch_optional(executor)
+                    .await?;
+                r.ok_or(#no_row_error)
+            },
+        )
+    };
+    let update_head = format!("UPDATE {table_name} SET ");
+
+    Ok(quote! {
+        /// A row recording the fields changed through its setters, see `tracked()`.
+        #struct_visibility struct #tracked_ident {
+            row: #struct_name,
+            changed: [bool; #columns_count],
+        }
+
+        #[automatically_derived]
+        impl #struct_name {
+            /// Wraps a row read from the database, so that `save()` on the wrapper only updates
+            /// the columns of the fields changed through its `set_{field}()` setters.
+            #struct_visibility fn tracked(self) -> #tracked_ident {
+                #tracked_ident {
+                    row: self,
+                    changed: [false; #columns_count],
+                }
+            }
+        }
+
+        #[automatically_derived]
+        impl #tracked_ident {
+            const COLUMNS: [&'static str; #columns_count] = [#(#column_names),*];
+
+            #(#setters)*
+
+            /// The columns changed through the setters, written by the next `save()`.
+            #struct_visibility fn changed_columns(&self) -> Vec<&'static str> {
+                Self::COLUMNS
+                    .iter()
+                    .zip(self.changed)
+                    .filter_map(|(column, changed)| changed.then_some(*column))
+                    .collect()
+            }
+
+            /// Returns the row, forgetting the changes.
+            #struct_visibility fn into_inner(self) -> #struct_name {
+                self.row
+            }
+
+            /// Updates the changed columns only and returns the stored row. Nothing is written
+            /// when no field changed.
+            #struct_visibility async fn save<'e, #executor_bound>(self, executor: E) -> lorm::errors::Result<#struct_name> {
+                if !self.changed.contains(&true) {
+                    return Ok(self.row);
+                }
+                let mut builder = sqlx::QueryBuilder::<#database_type>::new(#update_head);
+                let mut assignments = builder.separated(", ");
+                #(#assignments)*
+                #updated_at_code
+                #version_code
+                #(#pk_conditions)*
+                #version_condition
+                #fetch_code
+            }
+        }
+
+        #[automatically_derived]
+        impl std::ops::Deref for #tracked_ident {
+            type Target = #struct_name;
+
+            fn deref(&self) -> &Self::Target {
+                &self.row
+            }
+        }
+    })
+}
@@ -0,0 +1,205 @@
+use crate::models::OrmModel;
+use crate::orm::select::{generate_where_exists, generate_where_methods, generate_where_support};
+use crate::utils::{is_option_wrapped, is_primitive_type, to_column_type};
+use quote::{__private::TokenStream, format_ident, quote};
+
+/// Generates `update_where()`, a conditional bulk update builder with a `set_{field}()` method
+/// per updatable column and the same `where_{field}()` methods as `select()`.
+///
+/// `created_at` is never written, `updated_at` and the version are bumped by every `execute()`, as
+/// `save()` does.
+pub fn generate_update(
+    executor_type: &TokenStream,
+    database_type: &TokenStream,
+    model: &OrmModel,
+) -> syn::Result<TokenStream> {
+    let trait_ident = format_ident!("{}UpdateWhereTrait", model.struct_name);
+    let builder_struct_ident = format_ident!("{}UpdateBuilder", model.struct_name);
+    let struct_name = model.struct_name;
+    let struct_visibility = model.struct_visibility;
+    let table_name = &model.table_name;
+    let lifetime = quote! {'a};
+
+    let set_tokens = model
+        .update_columns()
+        .filter(|c| !c.column_properties.created_at && !c.column_properties.updated_at)
+        .map(|column| {
+            let set_fn = format_ident!("set_{}", column.field);
+            let column_name = &column.column_name;
+            let base_type = to_column_type(&column.ty)?;
+            let parameter = quote! {value};
+
+            // Nullable columns take an `Option` so that they can be reset to NULL.
+            let (param_type, constraints, bind_value) = if is_option_wrapped(&column.ty) {
+                let inner_type = if is_primitive_type(&base_type) {
+                    quote! { #base_type }
+                } else {
+                    quote! { &#lifetime #base_type }
+                };
+                if column.column_properties.use_json {
+                    (
+                        quote! { Option<#inner_type> },
+                        quote! { #base_type: serde::Serialize + Sync },
+                        quote! { #parameter.map(sqlx::types::Json) },
+                    )
+                } else {
+                    (
+                        quote! { Option<#inner_type> },
+                        column.bind_constraint(database_type, &lifetime)?,
+                        parameter.clone(),
+                    )
+                }
+            } else if column.column_properties.use_json {
+                (
+                    quote! { &#lifetime #base_type },
+                    quote! { #base_type: serde::Serialize + Sync },
+                    quote! { sqlx::types::Json(#parameter) },
+                )
+            } else {
+                let (param_type, param_use) = column.bind_param(&parameter, &lifetime)?;
+                (
+                    param_type,
+                    column.bind_constraint(database_type, &lifetime)?,
+                    param_use,
+                )
+            };
+
+            Ok(quote! {
+                #struct_visibility fn #set_fn(mut self, #parameter: #param_type) -> Self where #constraints {
+                    self.set_bind(#column_name, #bind_value);
+                    self
+                }
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let updated_at_code = match model.updated_at() {
+        Some(column) if !column.column_properties.readonly => {
+            let head = format!(", {} = ", column.column_name);
+            let new_method = &column.column_properties.new_expression;
+            quote! {
+                builder.push(#head);
+                builder.push_bind(#new_method);
+            }
+        }
+        _ => quote! {},
+    };
+    let version_code = match model.version_increment() {
+        Some(increment) => {
+            let assignment = format!(", {increment}");
+            quote! { builder.push(#assignment); }
+        }
+        None => quote! {},
+    };
+
+    // The UPDATE statement never uses the table alias, columns are left unqualified.
+    let where_tokens = generate_where_methods(model, database_type, false)?;
+    let where_support = generate_where_support(struct_visibility, "update builder", "execute()");
+    let exists_tokens = generate_where_exists(struct_visibility, executor_type, table_name);
+    let update_head = format!("UPDATE {table_name} SET ");
+
+    Ok(quote! {
+        #struct_visibility trait #trait_ident<#lifetime> {
+            fn update_where() -> #builder_struct_ident<#lifetime>;
+        }
+
+        #[automatically_derived]
+        impl<#lifetime> #trait_ident<#lifetime> for #struct_name {
+            fn update_where() -> #builder_struct_ident<#lifetime> {
+                #builder_struct_ident::new()
+            }
+        }
+
+        #struct_visibility struct #builder_struct_ident<#lifetime> {
+            assignments: Vec<lorm::query::SqlFragment<#lifetime, #database_type>>,
+            fragments: Vec<lorm::query::SqlFragment<#lifetime, #database_type>>,
+            is_where: bool,
+            group_depth: i32,
+            pending_groups: u32,
+            is_or: bool,
+            preparation_error: Option<String>,
+            update_all: bool,
+        }
+
+        #[automatically_derived]
+        impl<#lifetime> #builder_struct_ident<#lifetime> {
+            fn new() -> Self {
+                Self {
+                    assignments: Vec::new(),
+                    fragments: Vec::new(),
+                    is_where: false,
+                    group_depth: 0,
+                    pending_groups: 0,
+                    is_or: false,
+                    preparation_error: None,
+                    update_all: false,
+                }
+            }
+
+            fn push(&mut self, sql: impl std::fmt::Display) {
+                self.fragments.push(lorm::query::sql_fragment(sql.to_string()));
+            }
+
+            fn push_bind<T>(&mut self, value: T)
+            where
+                T: sqlx::Encode<#lifetime, #database_type> + sqlx::Type<#database_type> + std::fmt::Debug + Clone + Send + Sync + #lifetime,
+            {
+                self.fragments.push(lorm::query::bind_fragment(value));
+            }
+
+            /// Appends `column = value` to the SET clause.
+            fn set_bind<T>(&mut self, column: &str, value: T)
+            where
+                T: sqlx::Encode<#lifetime, #database_type> + sqlx::Type<#database_type> + std::fmt::Debug + Clone + Send + Sync + #lifetime,
+            {
+                let separator = if self.assignments.is_empty() { "" } else { ", " };
+                self.assignments.push(lorm::query::sql_fragment(format!("{separator}{column} = ")));
+                self.assignments.push(lorm::query::bind_fragment(value));
+            }
+
+            #(#set_tokens)*
+
+            #where_support
+
+            #(#where_tokens)*
+
+            #exists_tokens
+
+            /// Allows `execute()` without any condition, updating every row of the table.
+            #struct_visibility fn all(mut self) -> Self {
+                self.update_all = true;
+                self
+            }
+
+            /// Runs the `UPDATE` and returns the number of updated rows. Refuses to run without any
+            /// `set_{field}()`, or without any condition unless `all()` was called.
+            #struct_visibility async fn execute<'e, E: #executor_type>(self, executor: E) -> lorm::errors::Result<u64> {
+                if let Some(e) = self.preparation_error {
+                    return Err(lorm::errors::Error::QueryPreparationError(e));
+                }
+                self.check_balanced_groups()?;
+                if self.assignments.is_empty() {
+                    return Err(lorm::errors::Error::QueryPreparationError(
+                        "update_where() without any set_{field}()".to_string(),
+                    ));
+                }
+                if !self.is_where && !self.update_all {
+                    return Err(lorm::errors::Error::QueryPreparationError(
+                        "update_where() without any condition, call all() to update every row".to_string(),
+                    ));
+                }
+                let mut builder = sqlx::QueryBuilder::new(#update_head);
+                for fragment in self.assignments {
+                    fragment(&mut builder);
+                }
+                #updated_at_code
+                #version_code
+                for fragment in self.fragments {
+                    fragment(&mut builder);
+                }
+                let r = builder.build().execute(executor).await?;
+                Ok(r.rows_affected())
+            }
+        }
+    })
+}
@@ -0,0 +1,287 @@
+use crate::models::OrmModel;
+use crate::utils::max_bind_parameters;
+use quote::{__private::TokenStream, format_ident, quote};
+
+/// Generates `upsert_many(executor, &[Self])`, a single multi-row
+/// `INSERT ... ON CONFLICT (pk) DO UPDATE` (or `ON DUPLICATE KEY UPDATE` on MySQL),
+/// and `save_batch_in_chunks(conn, &[Self], chunk_size)`, which runs it chunk by chunk
+/// inside one transaction.
+///
+/// Nothing is generated when the primary key is left to the database (`readonly`),
+/// as rows without a key can never conflict.
+pub fn generate_upsert(
+    executor_type: &TokenStream,
+    database_type: &TokenStream,
+    model: &OrmModel,
+) -> syn::Result<TokenStream> {
+    let primary_key = model.primary_key();
+    let is_manual = !primary_key.is_generated();
+    if !is_manual && primary_key.generated_column().column_properties.readonly {
+        return Ok(quote! {});
+    }
+
+    let trait_ident = format_ident!("{}UpsertTrait", model.struct_name);
+    let batch_trait_ident = format_ident!("{}SaveBatchTrait", model.struct_name);
+    let struct_name = model.struct_name;
+    let struct_visibility = model.struct_visibility;
+    let table_name = &model.table_name;
+    let full_select_columns = model.full_column_select();
+
+    let insert_columns = model
+        .insert_columns()
+        .map(|col| col.column_name.as_str())
+        .collect::<Vec<_>>();
+    // Every row binds one parameter per inserted column.
+    let max_rows_per_statement = (max_bind_parameters() / insert_columns.len()).max(1);
+    let insert_columns = insert_columns.join(",");
+
+    let InsertRows {
+        keys_code,
+        rows_iter,
+        row_pattern,
+        row_binds,
+    } = insert_rows(model);
+
+    let pk_fields = primary_key.fields();
+    let pk_cols_list = pk_fields
+        .iter()
+        .map(|c| c.column_name.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    // created_at keeps the value already stored for existing rows.
+    let conflict_columns = model
+        .update_columns()
+        .filter(|col| !col.column_properties.created_at)
+        .map(|col| col.column_name.as_str())
+        .collect::<Vec<_>>();
+    // With nothing to update, a no-op assignment still makes existing rows part of the result.
+    let conflict_columns = if conflict_columns.is_empty() {
+        vec![pk_fields[0].column_name.as_str()]
+    } else {
+        conflict_columns
+    };
+
+    let insert_head = format!("INSERT INTO {table_name} ({insert_columns}) ");
+
+    // The body evaluates to the stored rows of `items`, running its statements on `executor`.
+    let (executor_bound, body): (TokenStream, Box<dyn Fn(TokenStream) -> TokenStream>) = if cfg!(
+        feature = "mysql"
+    ) {
+        let updates = conflict_columns
+            .iter()
+            .map(|c| format!("{c} = VALUES({c})"))
+            .chain(model.version_increment())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let on_duplicate = format!(" ON DUPLICATE KEY UPDATE {updates}");
+        let select_code = select_by_keys(model, &rows_iter, &row_pattern);
+
+        (
+            quote! { E: #executor_type + Copy },
+            Box::new(move |executor| {
+                quote! {
+                    #keys_code
+                    let mut insert = sqlx::QueryBuilder::new(#insert_head);
+                    insert.push_values(#rows_iter, |mut b, #row_pattern| {
+                        #(#row_binds)*
+                    });
+                    insert.push(#on_duplicate);
+                    insert.build().execute(#executor).await?;
+
+                    #select_code
+                    select.build_query_as::<#struct_name>().fetch_all(#executor).await?
+                }
+            }),
+        )
+    } else {
+        let updates = conflict_columns
+            .iter()
+            .map(|c| format!("{c} = EXCLUDED.{c}"))
+            .chain(model.version_increment())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let on_conflict = format!(
+            " ON CONFLICT ({pk_cols_list}) DO UPDATE SET {updates} RETURNING {full_select_columns}"
+        );
+        (
+            quote! { E: #executor_type },
+            Box::new(move |executor| {
+                quote! {
+                    #keys_code
+                    let mut insert = sqlx::QueryBuilder::new(#insert_head);
+                    insert.push_values(#rows_iter, |mut b, #row_pattern| {
+                        #(#row_binds)*
+                    });
+                    insert.push(#on_conflict);
+                    insert.build_query_as::<#struct_name>().fetch_all(#executor).await?
+                }
+            }),
+        )
+    };
+
+    let upsert_body = body(quote! { executor });
+    let chunk_body = body(quote! { &mut *tx });
+
+    Ok(quote! {
+        #struct_visibility trait #trait_ident<'e, #executor_bound>: Sized {
+            async fn upsert_many(executor: E, items: &[Self]) -> lorm::errors::Result<Vec<#struct_name>>;
+        }
+
+        #[automatically_derived]
+        impl<'e, #executor_bound> #trait_ident<'e, E> for #struct_name {
+            async fn upsert_many(executor: E, items: &[Self]) -> lorm::errors::Result<Vec<#struct_name>> {
+                if items.is_empty() {
+                    return Ok(Vec::new());
+                }
+                let r = { #upsert_body };
+                Ok(r)
+            }
+        }
+
+        #struct_visibility trait #batch_trait_ident<'c, A: sqlx::Acquire<'c, Database = #database_type> + Send>: Sized {
+            async fn save_batch_in_chunks(conn: A, items: &[Self], chunk_size: usize) -> lorm::errors::Result<Vec<#struct_name>>;
+        }
+
+        #[automatically_derived]
+        impl<'c, A: sqlx::Acquire<'c, Database = #database_type> + Send> #batch_trait_ident<'c, A> for #struct_name {
+            async fn save_batch_in_chunks(conn: A, items: &[Self], chunk_size: usize) -> lorm::errors::Result<Vec<#struct_name>> {
+                if chunk_size == 0 {
+                    return Err(lorm::errors::Error::QueryPreparationError(
+                        "save_batch_in_chunks() requires a chunk_size greater than zero".to_string(),
+                    ));
+                }
+                if items.is_empty() {
+                    return Ok(Vec::new());
+                }
+                let chunk_size = chunk_size.min(#max_rows_per_statement);
+                let mut tx = conn.begin().await?;
+                let mut saved = Vec::with_capacity(items.len());
+                for items in items.chunks(chunk_size) {
+                    let r = { #chunk_body };
+                    saved.extend(r);
+                }
+                tx.commit().await?;
+                Ok(saved)
+            }
+        }
+    })
+}
+
+/// The pieces of a multi-row `INSERT` shared by `upsert_many()` and `insert_many()`.
+pub(crate) struct InsertRows {
+    /// Resolves the generated pk of every row up front into `keys`, so that MySQL can re-select
+    /// the rows by key. Empty when the application or the database writes the key.
+    pub(crate) keys_code: TokenStream,
+    /// The rows given to `push_values()`, destructured by `row_pattern`.
+    pub(crate) rows_iter: TokenStream,
+    pub(crate) row_pattern: TokenStream,
+    /// One `push_bind()` per inserted column.
+    pub(crate) row_binds: Vec<TokenStream>,
+}
+
+/// Per-row bind values of `items`, generating a new pk and timestamps where save() would.
+pub(crate) fn insert_rows(model: &OrmModel) -> InsertRows {
+    let primary_key = model.primary_key();
+    let is_manual = !primary_key.is_generated();
+    let item = quote! {item};
+    let key = quote! {key};
+
+    let row_binds = model
+        .insert_columns()
+        .map(|col| {
+            let props = &col.column_properties;
+            if props.primary_key && !is_manual {
+                let value = col.bind_value(quote! { #key });
+                quote! { b.push_bind(#value); }
+            } else if (props.created_at || props.updated_at) && !props.readonly {
+                let new_method = &props.new_expression;
+                quote! { b.push_bind(#new_method); }
+            } else {
+                let accessor = col.accessor_on(&item);
+                if props.use_json {
+                    quote! { b.push_bind(sqlx::types::Json(#accessor)); }
+                } else {
+                    let value = col.bind_value(accessor);
+                    quote! { b.push_bind(#value); }
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    if is_manual || primary_key.generated_column().column_properties.readonly {
+        return InsertRows {
+            keys_code: quote! {},
+            rows_iter: quote! { items.iter() },
+            row_pattern: quote! { #item },
+            row_binds,
+        };
+    }
+    let pk_col = primary_key.generated_column();
+    let pk_ty = &pk_col.ty;
+    let pk_accessor = pk_col.accessor_on(&item);
+    let is_unset = pk_col.column_properties.is_set(pk_accessor.clone(), pk_ty);
+    let new_method = &pk_col.column_properties.new_expression;
+    InsertRows {
+        keys_code: quote! {
+            let keys: Vec<#pk_ty> = items
+                .iter()
+                .map(|#item| if #is_unset { #new_method } else { (#pk_accessor).clone() })
+                .collect();
+        },
+        rows_iter: quote! { items.iter().zip(keys.iter()) },
+        row_pattern: quote! { (#item, #key) },
+        row_binds,
+    }
+}
+
+/// Builds `select`, a `QueryBuilder` reading back the rows of `items` by primary key, for MySQL
+/// which has no `RETURNING`. The primary key must be written by the application.
+pub(crate) fn select_by_keys(
+    model: &OrmModel,
+    rows_iter: &TokenStream,
+    row_pattern: &TokenStream,
+) -> TokenStream {
+    let is_manual = !model.primary_key().is_generated();
+    let item = quote! {item};
+    let key = quote! {key};
+    let select_head = format!(
+        "SELECT {} FROM {} WHERE ",
+        model.full_column_select(),
+        model.table_name
+    );
+
+    let key_binds = model
+        .primary_key()
+        .fields()
+        .iter()
+        .enumerate()
+        .map(|(i, col)| {
+            let prefix = if i == 0 {
+                format!("({} = ", col.column_name)
+            } else {
+                format!(" AND {} = ", col.column_name)
+            };
+            let value = if is_manual {
+                col.bind_value(col.accessor_on(&item))
+            } else {
+                col.bind_value(quote! { #key })
+            };
+            quote! {
+                select.push(#prefix);
+                select.push_bind(#value);
+            }
+        })
+        .collect::<Vec<_>>();
+
+    quote! {
+        let mut select = sqlx::QueryBuilder::new(#select_head);
+        for (i, #row_pattern) in #rows_iter.enumerate() {
+            if i > 0 {
+                select.push(" OR ");
+            }
+            #(#key_binds)*
+            select.push(")");
+        }
+    }
+}
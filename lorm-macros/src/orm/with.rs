@@ -1,33 +1,38 @@
 use crate::models::OrmModel;
-use crate::utils::{
-    db_placeholder, get_bind_param_type_and_usage, get_bind_type_where_constraint, to_column_type,
-};
+use crate::utils::{db_placeholder, is_primitive_type, max_bind_parameters, to_column_type};
 use quote::{__private::TokenStream, format_ident, quote};
 
+/// Generates `with_{field}()` for every queryable field, plus `with_{field}_in()` and
+/// `count_by_{field}_in()` in a separate trait over `sqlx::Acquire`: they run one `IN` query per
+/// chunk of values under the backend's bind parameter limit, on the same connection.
 pub fn generate_with(
     executor_type: &TokenStream,
     database_type: &TokenStream,
     model: &OrmModel,
 ) -> syn::Result<TokenStream> {
     let trait_ident = format_ident!("{}WithTrait", model.struct_name);
+    let in_trait_ident = format_ident!("{}WithInTrait", model.struct_name);
+    // Every value binds one parameter.
+    let max_values_per_query = max_bind_parameters();
     let struct_name = model.struct_name;
     let struct_visibility = model.struct_visibility;
     let table_name = &model.table_name;
     let table_columns = model.full_column_select();
+    let not_deleted = model.not_deleted_and();
 
-    let stream: Vec<(TokenStream, TokenStream)> = model.query_columns().map(|column| (|| -> syn::Result<_> {
+    let stream: Vec<(TokenStream, TokenStream, TokenStream, TokenStream)> = model.query_columns().map(|column| (|| -> syn::Result<_> {
         let field_name = &column.field;
         let column_name = &column.column_name;
 
         let lifetime = quote! {'a};
         let param = quote! {value};
-        let (param_type, param_value) = get_bind_param_type_and_usage(&param, &column.ty, &lifetime)?;
+        let (param_type, param_value) = column.bind_param(&param, &lifetime)?;
 
         let constraints = if column.column_properties.use_json {
             let base_type = to_column_type(&column.ty).unwrap();
             quote! { #base_type: serde::Serialize }
         } else {
-            get_bind_type_where_constraint(&column.ty, database_type, &lifetime).unwrap()
+            column.bind_constraint(database_type, &lifetime).unwrap()
         };
 
         let bind_value = if column.column_properties.use_json {
@@ -37,15 +42,48 @@ pub fn generate_with(
         };
 
         let with_fn = format_ident!("with_{}",field_name);
+        let with_in_fn = format_ident!("with_{}_in",field_name);
+        let count_in_fn = format_ident!("count_by_{}_in",field_name);
         let placeholder = db_placeholder(column.base_field, 1).unwrap();
 
         let signature = quote! {
             async fn #with_fn<#lifetime>(executor: E, #param: #param_type) -> lorm::errors::Result<Vec<#struct_name>> where #constraints
         };
+        let base_type = to_column_type(&column.ty)?;
+        let is_primitive = is_primitive_type(&base_type);
+        let item_type = if is_primitive {
+            quote! { #base_type }
+        } else {
+            quote! { impl std::borrow::Borrow<#base_type> }
+        };
+        let in_signature = quote! {
+            async fn #with_in_fn<#lifetime>(conn: A, values: &#lifetime [#item_type]) -> lorm::errors::Result<Vec<#struct_name>> where #constraints
+        };
+        let count_in_signature = quote! {
+            async fn #count_in_fn<#lifetime>(conn: A, values: &#lifetime [#item_type]) -> lorm::errors::Result<i64> where #constraints
+        };
         let trait_code = quote! {
             #signature;
         };
-        let sql_ident = format!("SELECT {table_columns} FROM {table_name} WHERE {column_name} = {placeholder}");
+        let in_trait_code = quote! {
+            /// Returns the rows whose value is any of `values`, with an `IN` query per chunk of
+            /// values. A value repeated in two chunks returns its rows twice.
+            #in_signature;
+            /// Counts the rows whose value is any of `values`, 0 for an empty slice.
+            #count_in_signature;
+        };
+        let sql_ident = format!("SELECT {table_columns} FROM {table_name} WHERE {not_deleted}{column_name} = {placeholder}");
+        let in_head = format!("SELECT {table_columns} FROM {table_name} WHERE {not_deleted}{column_name} IN (");
+        let count_in_head = format!("SELECT COUNT(*) FROM {table_name} WHERE {not_deleted}{column_name} IN (");
+        let item_value = if column.column_properties.use_json {
+            quote! { sqlx::types::Json(value.borrow()) }
+        } else if column.column_properties.store_as_text {
+            quote! { value.to_string() }
+        } else if is_primitive {
+            quote! { *value }
+        } else {
+            quote! { value.borrow() }
+        };
 
         let impl_code = quote! {
             #signature {
@@ -55,10 +93,55 @@ pub fn generate_with(
                 Ok(r)
             }
         };
-        Ok((trait_code, impl_code))
-    })()).collect::<Result<Vec<(_, _)>, _>>()?;
-    let (trait_tokens, impl_tokens): (Vec<TokenStream>, Vec<TokenStream>) =
-        stream.into_iter().unzip();
+        let in_impl_code = quote! {
+            #in_signature {
+                if values.is_empty() {
+                    return Ok(Vec::new());
+                }
+                let mut conn = conn.acquire().await?;
+                let mut r = Vec::with_capacity(values.len());
+                for values in values.chunks(#max_values_per_query) {
+                    let mut query = sqlx::QueryBuilder::new(#in_head);
+                    let mut separated = query.separated(", ");
+                    for value in values {
+                        separated.push_bind(#item_value);
+                    }
+                    query.push(")");
+                    r.extend(query.build_query_as::<Self>().fetch_all(&mut *conn).await?);
+                }
+                Ok(r)
+            }
+
+            #count_in_signature {
+                if values.is_empty() {
+                    return Ok(0);
+                }
+                let mut conn = conn.acquire().await?;
+                let mut r = 0;
+                for values in values.chunks(#max_values_per_query) {
+                    let mut query = sqlx::QueryBuilder::new(#count_in_head);
+                    let mut separated = query.separated(", ");
+                    for value in values {
+                        separated.push_bind(#item_value);
+                    }
+                    query.push(")");
+                    r += query.build_query_scalar::<i64>().fetch_one(&mut *conn).await?;
+                }
+                Ok(r)
+            }
+        };
+        Ok((trait_code, impl_code, in_trait_code, in_impl_code))
+    })()).collect::<Result<Vec<(_, _, _, _)>, _>>()?;
+    let mut trait_tokens = Vec::new();
+    let mut impl_tokens = Vec::new();
+    let mut in_trait_tokens = Vec::new();
+    let mut in_impl_tokens = Vec::new();
+    for (trait_code, impl_code, in_trait_code, in_impl_code) in stream {
+        trait_tokens.push(trait_code);
+        impl_tokens.push(impl_code);
+        in_trait_tokens.push(in_trait_code);
+        in_impl_tokens.push(in_impl_code);
+    }
 
     Ok(quote! {
         #struct_visibility trait #trait_ident<'e, E: #executor_type>: Sized {
@@ -69,5 +152,14 @@ pub fn generate_with(
         impl<'e, E: #executor_type> #trait_ident<'e, E> for #struct_name {
             #(#impl_tokens)*
         }
+
+        #struct_visibility trait #in_trait_ident<'c, A: sqlx::Acquire<'c, Database = #database_type> + Send>: Sized {
+            #(#in_trait_tokens)*
+        }
+
+        #[automatically_derived]
+        impl<'c, A: sqlx::Acquire<'c, Database = #database_type> + Send> #in_trait_ident<'c, A> for #struct_name {
+            #(#in_impl_tokens)*
+        }
     })
 }
@@ -65,7 +65,7 @@ pub(crate) fn is_option_wrapped(ty: &Type) -> bool {
 /// Convert the type into the type that the db columns have. This does two things:
 ///
 /// - Returns the type without its `Option<>` wrapper if present.
-/// - Converts `String` to `&str`.
+/// - Converts `String`, `Arc<str>` and `Rc<str>` to `&str`.
 ///
 /// For example, `Option<String>` becomes `String`, and `Option<i32>` becomes `i32`.
 pub(crate) fn to_column_type(ty: &Type) -> syn::Result<Type> {
@@ -97,6 +97,9 @@ pub(crate) fn to_column_type(ty: &Type) -> syn::Result<Type> {
     {
         return syn::parse2(quote::quote! { str });
     }
+    if is_shared_str_type(res) {
+        return syn::parse2(quote::quote! { str });
+    }
 
     // This is a clone in disguise as `Type` doesn't implement `Clone`
     // parse(res.into_token_stream().into()) would use proc_macro API
@@ -105,6 +108,28 @@ pub(crate) fn to_column_type(ty: &Type) -> syn::Result<Type> {
     syn::parse2(token_stream)
 }
 
+/// Checks whether a type is a shared string, `Arc<str>` or `Rc<str>`, possibly wrapped in an
+/// `Option`. sqlx cannot bind them, their values are bound as `String`s.
+pub(crate) fn is_shared_str_type(ty: &Type) -> bool {
+    let Type::Path(type_path) = ty else {
+        return false;
+    };
+    let Some(last_segment) = type_path.path.segments.last() else {
+        return false;
+    };
+    let PathArguments::AngleBracketed(angle_bracketed) = &last_segment.arguments else {
+        return false;
+    };
+    let Some(syn::GenericArgument::Type(inner_type)) = angle_bracketed.args.first() else {
+        return false;
+    };
+    match last_segment.ident.to_string().as_str() {
+        "Option" => is_shared_str_type(inner_type),
+        "Arc" | "Rc" => matches!(inner_type, Type::Path(p) if p.path.is_ident("str")),
+        _ => false,
+    }
+}
+
 /// Generates a database-specific placeholder for a single field.
 ///
 /// Returns `"$n"` for PostgreSQL/SQLite or `"?"` for MySQL, where n is the index.
@@ -157,6 +182,18 @@ pub(crate) fn database_type(input: &DeriveInput) -> syn::Result<TokenStream> {
     }
 }
 
+/// Maximum number of bind parameters a single statement may use on the enabled database.
+///
+/// SQLite uses the historical `SQLITE_MAX_VARIABLE_NUMBER` default (999) so that older system
+/// libraries are covered as well.
+pub(crate) fn max_bind_parameters() -> usize {
+    if cfg!(feature = "postgres") || cfg!(feature = "mysql") {
+        65535
+    } else {
+        999
+    }
+}
+
 /// Generates the type constraints for the `where` clause needed for binding a field value to SQLx queries.
 ///
 /// These constraints are that the field type implements `sqlx::Encode` and `sqlx::Type` for the specific database type.
@@ -247,10 +284,229 @@ pub(crate) fn default_belongs_to_method(target: &syn::Path) -> String {
         .unwrap_or_default()
 }
 
+/// Renders `table alias` for a `FROM` clause, or just `table` without an alias.
+pub(crate) fn aliased_table(table_name: &str, alias: Option<&str>) -> String {
+    match alias {
+        Some(alias) => format!("{table_name} {alias}"),
+        None => table_name.to_string(),
+    }
+}
+
+/// Renders `alias.column`, or just `column` without an alias.
+pub(crate) fn qualify_column(alias: Option<&str>, column_name: &str) -> String {
+    match alias {
+        Some(alias) => format!("{alias}.{column_name}"),
+        None => column_name.to_string(),
+    }
+}
+
+/// Checks whether a type is a `Uuid`, whatever the path it is imported from.
+pub(crate) fn is_uuid_type(ty: &Type) -> bool {
+    matches!(ty, Type::Path(type_path)
+        if type_path.path.segments.last().is_some_and(|s| s.ident == "Uuid"))
+}
+
+/// Checks whether a type is a signed integer type, the types of `#[lorm(version)]` counters.
+pub(crate) fn is_signed_integer_type(ty: &Type) -> bool {
+    matches!(ty, Type::Path(type_path)
+    if type_path.path.segments.last().is_some_and(|s| {
+        matches!(s.ident.to_string().as_str(), "i16" | "i32" | "i64")
+    }))
+}
+
+/// Checks whether a type is an integer type the database can assign as an auto-increment key,
+/// signed or unsigned, e.g. `u64` for MySQL's `BIGINT UNSIGNED AUTO_INCREMENT`.
+pub(crate) fn is_auto_increment_type(ty: &Type) -> bool {
+    matches!(ty, Type::Path(type_path)
+    if type_path.path.segments.last().is_some_and(|s| {
+        matches!(
+            s.ident.to_string().as_str(),
+            "i16" | "i32" | "i64" | "u16" | "u32" | "u64"
+        )
+    }))
+}
+
+/// The "now" expression for a timestamp type, used by `created_at`/`updated_at` fields without a
+/// `new` attribute. Handles the chrono and time types, wrapped in an [Option] or not.
+///
+/// Returns `None` for any other type.
+pub(crate) fn now_expression(ty: &Type) -> Option<syn::Expr> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    let expression = match segment.ident.to_string().as_str() {
+        "Option" => {
+            let PathArguments::AngleBracketed(args) = &segment.arguments else {
+                return None;
+            };
+            let Some(syn::GenericArgument::Type(inner)) = args.args.first() else {
+                return None;
+            };
+            let now = now_expression(inner)?;
+            return syn::parse2(quote! { Some(#now) }).ok();
+        }
+        "DateTime" => {
+            let PathArguments::AngleBracketed(args) = &segment.arguments else {
+                return None;
+            };
+            let Some(syn::GenericArgument::Type(Type::Path(tz))) = args.args.first() else {
+                return None;
+            };
+            match tz.path.segments.last()?.ident.to_string().as_str() {
+                "Utc" => quote! { chrono::Utc::now() },
+                "FixedOffset" => quote! { chrono::Utc::now().fixed_offset() },
+                "Local" => quote! { chrono::Local::now() },
+                _ => return None,
+            }
+        }
+        "NaiveDateTime" => quote! { chrono::Utc::now().naive_utc() },
+        "OffsetDateTime" => quote! { time::OffsetDateTime::now_utc() },
+        "PrimitiveDateTime" => quote! {
+            {
+                let now = time::OffsetDateTime::now_utc();
+                time::PrimitiveDateTime::new(now.date(), now.time())
+            }
+        },
+        _ => return None,
+    };
+    syn::parse2(expression).ok()
+}
+
+/// Converts a borrowed timestamp to UTC, keeping its type, so that filters on offset-aware
+/// timestamps compare the same instant whatever the offset it was given in. SQLite stores them as
+/// text, where `12:00+02:00` and `10:00+00:00` would differ.
+///
+/// Applies to `DateTime<FixedOffset>` and `OffsetDateTime` (in an [Option] or not), returns
+/// `None` for any other type, whose bound value is left as is.
+pub(crate) fn utc_normalization(ty: &Type, value: &TokenStream) -> Option<TokenStream> {
+    let base_type = to_column_type(ty).ok()?;
+    let Type::Path(type_path) = &base_type else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    match segment.ident.to_string().as_str() {
+        "DateTime" => {
+            let PathArguments::AngleBracketed(args) = &segment.arguments else {
+                return None;
+            };
+            let Some(syn::GenericArgument::Type(Type::Path(tz))) = args.args.first() else {
+                return None;
+            };
+            (tz.path.segments.last()?.ident == "FixedOffset").then(|| {
+                quote! { { let v: &#base_type = #value; v.with_timezone(&chrono::Utc).fixed_offset() } }
+            })
+        }
+        "OffsetDateTime" => Some(quote! {
+            { let v: &#base_type = #value; v.to_offset(time::UtcOffset::UTC) }
+        }),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn now_of(ty: &str) -> Option<String> {
+        let ty: Type = syn::parse_str(ty).unwrap();
+        now_expression(&ty).map(|e| e.to_token_stream().to_string())
+    }
+
+    #[test]
+    fn now_expression_per_timestamp_type() {
+        assert_eq!(
+            now_of("chrono::DateTime<chrono::Utc>").as_deref(),
+            Some("chrono :: Utc :: now ()")
+        );
+        assert_eq!(
+            now_of("DateTime<FixedOffset>").as_deref(),
+            Some("chrono :: Utc :: now () . fixed_offset ()")
+        );
+        assert_eq!(
+            now_of("NaiveDateTime").as_deref(),
+            Some("chrono :: Utc :: now () . naive_utc ()")
+        );
+        assert_eq!(
+            now_of("time::OffsetDateTime").as_deref(),
+            Some("time :: OffsetDateTime :: now_utc ()")
+        );
+        assert_eq!(
+            now_of("Option<DateTime<Utc>>").as_deref(),
+            Some("Some (chrono :: Utc :: now ())")
+        );
+    }
+
+    #[test]
+    fn is_shared_str_type_matches_arc_and_rc_str() {
+        for ty in [
+            "Arc<str>",
+            "std::rc::Rc<str>",
+            "Option<std::sync::Arc<str>>",
+        ] {
+            assert!(is_shared_str_type(&syn::parse_str(ty).unwrap()), "{ty}");
+        }
+        for ty in ["Arc<String>", "Box<str>", "String", "str"] {
+            assert!(!is_shared_str_type(&syn::parse_str(ty).unwrap()), "{ty}");
+        }
+    }
+
+    #[test]
+    fn to_column_type_converts_shared_str_to_str() {
+        let ty: Type = syn::parse_str("Option<Arc<str>>").unwrap();
+        let result = to_column_type(&ty).unwrap();
+        assert_eq!(result.into_token_stream().to_string(), "str");
+    }
+
+    #[test]
+    fn is_uuid_type_matches_any_path() {
+        for ty in ["Uuid", "uuid::Uuid", "sqlx::types::Uuid"] {
+            assert!(is_uuid_type(&syn::parse_str(ty).unwrap()), "{ty}");
+        }
+        assert!(!is_uuid_type(&syn::parse_str("Option<Uuid>").unwrap()));
+        assert!(!is_uuid_type(&syn::parse_str("String").unwrap()));
+    }
+
+    #[test]
+    fn utc_normalization_applies_to_offset_aware_timestamps() {
+        let normalized = |ty: &str| {
+            let ty: Type = syn::parse_str(ty).unwrap();
+            utc_normalization(&ty, &quote! { value }).map(|t| t.to_string())
+        };
+        assert!(
+            normalized("Option<chrono::DateTime<chrono::FixedOffset>>")
+                .unwrap()
+                .contains("with_timezone (& chrono :: Utc) . fixed_offset ()")
+        );
+        assert!(
+            normalized("time::OffsetDateTime")
+                .unwrap()
+                .contains("to_offset (time :: UtcOffset :: UTC)")
+        );
+        assert_eq!(normalized("DateTime<Utc>"), None);
+        assert_eq!(normalized("NaiveDateTime"), None);
+        assert_eq!(normalized("String"), None);
+    }
+
+    #[test]
+    fn now_expression_ignores_other_types() {
+        assert_eq!(now_of("String"), None);
+        assert_eq!(now_of("DateTime<chrono_tz::Tz>"), None);
+        assert_eq!(now_of("i64"), None);
+    }
+
+    #[test]
+    fn table_alias_qualifies_table_and_columns() {
+        assert_eq!(aliased_table("users", Some("u")), "users u");
+        assert_eq!(qualify_column(Some("u"), "email"), "u.email");
+    }
+
+    #[test]
+    fn no_table_alias_keeps_plain_names() {
+        assert_eq!(aliased_table("users", None), "users");
+        assert_eq!(qualify_column(None, "email"), "email");
+    }
+
     #[test]
     fn infer_fk_column_basic() {
         let p: syn::Path = syn::parse_str("User").unwrap();
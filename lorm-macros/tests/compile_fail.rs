@@ -2,4 +2,5 @@
 fn compile_fail_tests() {
     let t = trybuild::TestCases::new();
     t.compile_fail("tests/compile_fail/*.rs");
+    t.pass("tests/compile_pass/*.rs");
 }
@@ -0,0 +1,23 @@
+use lorm::ToLOrm;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+// serde attributes are left to serde, `#[serde(rename)]` does not rename the column.
+#[derive(Debug, Default, Clone, FromRow, ToLOrm, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct User {
+    #[lorm(pk)]
+    #[serde(rename = "userId")]
+    pub id: Uuid,
+    #[lorm(by)]
+    #[serde(rename = "mail", default)]
+    pub email: String,
+    #[sqlx(rename = "name")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+}
+
+fn main() {
+    assert_eq!(User::SELECT_COLUMNS, "id, email, name");
+}
@@ -6,13 +6,251 @@ use thiserror::Error;
 #[non_exhaustive]
 pub enum Error {
     /// An error occurred in the underlying SQLx database layer.
-    #[error("{0}")]
-    DatabaseError(#[from] sqlx::Error),
+    ///
+    /// Bound values are never part of the SQL text sent by Lorm, but some databases echo them back
+    /// in their messages (e.g. MySQL's `Duplicate entry 'alice@example.com' for key ...`). The
+    /// `Display` output therefore replaces every single-quoted literal with `'?'`. The untouched
+    /// `sqlx::Error` stays available through the variant and `source()`.
+    #[error("{}", redact_literals(&.0.to_string()))]
+    DatabaseError(#[source] sqlx::Error),
+
+    /// A write collided with a unique constraint, e.g. inserting a `#[lorm(unique)]` value that
+    /// another row holds already.
+    ///
+    /// Converted from the database errors reporting a unique violation (SQLSTATE `23505` on
+    /// PostgreSQL, `1062` on MySQL, `SQLITE_CONSTRAINT_UNIQUE` / `_PRIMARYKEY` on SQLite), with
+    /// the same redacted `Display` output as `DatabaseError`. `constraint` is the name of the
+    /// violated constraint when the database reports it, which only PostgreSQL does.
+    #[error("{}", redact_literals(&.source.to_string()))]
+    UniqueViolation {
+        constraint: Option<String>,
+        #[source]
+        source: sqlx::Error,
+    },
+
+    /// A write referenced a missing row or deleted a referenced one.
+    ///
+    /// Converted like `UniqueViolation`, from SQLSTATE `23503` on PostgreSQL, `1451` / `1452` on
+    /// MySQL and `SQLITE_CONSTRAINT_FOREIGNKEY` on SQLite.
+    #[error("{}", redact_literals(&.source.to_string()))]
+    ForeignKeyViolation {
+        constraint: Option<String>,
+        #[source]
+        source: sqlx::Error,
+    },
+
+    /// A query expecting a row, e.g. `by_{field}()` or `one()`, matched none.
+    ///
+    /// Converted from `sqlx::Error::RowNotFound`, so that it can be told apart from real failures.
+    #[error("no rows returned by a query that expected to return at least one row")]
+    NotFound,
+
+    /// `save()` of a `#[lorm(version)]` model updated no row: the row was changed (or deleted)
+    /// since the instance was read, saving it would overwrite that change.
+    #[error("the row was modified since it was read, reload it before saving")]
+    OptimisticLockConflict,
 
     /// An error occurred while preparing a query.
     #[error("{0}")]
     QueryPreparationError(String),
+
+    /// A lookup expecting a single row, e.g. `by_{field}_unique()`, matched several rows.
+    #[error("{0}")]
+    MultipleRowsError(String),
+
+    /// The row built by the closure of `by_{field}_or_insert()` does not hold the value looked
+    /// up. Nothing was written.
+    #[error("{0}")]
+    MismatchedRow(String),
+}
+
+impl From<sqlx::Error> for Error {
+    fn from(error: sqlx::Error) -> Self {
+        match error {
+            sqlx::Error::RowNotFound => Error::NotFound,
+            sqlx::Error::Database(ref e) if e.is_unique_violation() => Error::UniqueViolation {
+                constraint: e.constraint().map(str::to_string),
+                source: error,
+            },
+            sqlx::Error::Database(ref e) if e.is_foreign_key_violation() => {
+                Error::ForeignKeyViolation {
+                    constraint: e.constraint().map(str::to_string),
+                    source: error,
+                }
+            }
+            error => Error::DatabaseError(error),
+        }
+    }
 }
 
 /// A specialized `Result` type for Lorm operations.
 pub type Result<T> = std::result::Result<T, Error>;
+
+/// Replaces the content of every single-quoted SQL literal with `?`, keeping the message shape.
+/// Doubled quotes (`''`) inside a literal are handled as escaped quotes.
+fn redact_literals(message: &str) -> String {
+    let mut redacted = String::with_capacity(message.len());
+    let mut chars = message.chars().peekable();
+    let mut in_literal = false;
+    while let Some(c) = chars.next() {
+        if c != '\'' {
+            if !in_literal {
+                redacted.push(c);
+            }
+            continue;
+        }
+        if !in_literal {
+            redacted.push_str("'?");
+            in_literal = true;
+        } else if chars.peek() == Some(&'\'') {
+            chars.next();
+        } else {
+            redacted.push('\'');
+            in_literal = false;
+        }
+    }
+    redacted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::error::{DatabaseError, ErrorKind};
+    use std::fmt::{Display, Formatter};
+
+    #[derive(Debug)]
+    enum FakeKind {
+        Unique,
+        ForeignKey,
+        Other,
+    }
+
+    /// A database error with its message, kind and violated constraint.
+    #[derive(Debug)]
+    struct FakeDatabaseError(&'static str, FakeKind, Option<&'static str>);
+
+    impl Display for FakeDatabaseError {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            f.write_str(self.0)
+        }
+    }
+
+    impl std::error::Error for FakeDatabaseError {}
+
+    impl DatabaseError for FakeDatabaseError {
+        fn message(&self) -> &str {
+            self.0
+        }
+
+        fn as_error(&self) -> &(dyn std::error::Error + Send + Sync + 'static) {
+            self
+        }
+
+        fn as_error_mut(&mut self) -> &mut (dyn std::error::Error + Send + Sync + 'static) {
+            self
+        }
+
+        fn into_error(self: Box<Self>) -> Box<dyn std::error::Error + Send + Sync + 'static> {
+            self
+        }
+
+        fn constraint(&self) -> Option<&str> {
+            self.2
+        }
+
+        fn kind(&self) -> ErrorKind {
+            match self.1 {
+                FakeKind::Unique => ErrorKind::UniqueViolation,
+                FakeKind::ForeignKey => ErrorKind::ForeignKeyViolation,
+                FakeKind::Other => ErrorKind::Other,
+            }
+        }
+    }
+
+    #[test]
+    fn test_database_error_display_redacts_bound_values() {
+        let err: Error = sqlx::Error::Database(Box::new(FakeDatabaseError(
+            "1062 (23000): Duplicate entry 'alice@example.com' for key 'users.email'",
+            FakeKind::Other,
+            None,
+        )))
+        .into();
+        let message = err.to_string();
+        assert!(!message.contains("alice@example.com"));
+        assert_eq!(
+            message,
+            "error returned from database: 1062 (23000): Duplicate entry '?' for key '?'"
+        );
+    }
+
+    #[test]
+    fn test_redact_literals() {
+        assert_eq!(
+            redact_literals("UNIQUE constraint failed: users.email"),
+            "UNIQUE constraint failed: users.email"
+        );
+        assert_eq!(
+            redact_literals("value 'o''brien' rejected"),
+            "value '?' rejected"
+        );
+        assert_eq!(redact_literals("unterminated 'secret"), "unterminated '?");
+    }
+
+    #[test]
+    fn test_row_not_found_converts_to_not_found() {
+        let err: Error = sqlx::Error::RowNotFound.into();
+        assert!(matches!(err, Error::NotFound));
+        let err: Error = sqlx::Error::PoolTimedOut.into();
+        assert!(matches!(
+            err,
+            Error::DatabaseError(sqlx::Error::PoolTimedOut)
+        ));
+    }
+
+    #[test]
+    fn test_constraint_violations_convert_to_typed_errors() {
+        let err: Error = sqlx::Error::Database(Box::new(FakeDatabaseError(
+            "duplicate key value violates unique constraint \"users_email_key\"",
+            FakeKind::Unique,
+            Some("users_email_key"),
+        )))
+        .into();
+        assert!(matches!(
+            &err,
+            Error::UniqueViolation { constraint: Some(c), .. } if c == "users_email_key"
+        ));
+        assert_eq!(
+            err.to_string(),
+            "error returned from database: duplicate key value violates unique constraint \"users_email_key\""
+        );
+
+        let err: Error = sqlx::Error::Database(Box::new(FakeDatabaseError(
+            "1452 (23000): Cannot add or update a child row: a foreign key constraint fails",
+            FakeKind::ForeignKey,
+            None,
+        )))
+        .into();
+        assert!(matches!(
+            err,
+            Error::ForeignKeyViolation {
+                constraint: None,
+                ..
+            }
+        ));
+        assert!(std::error::Error::source(&err).is_some());
+
+        let err: Error = sqlx::Error::Database(Box::new(FakeDatabaseError(
+            "CHECK constraint failed",
+            FakeKind::Other,
+            None,
+        )))
+        .into();
+        assert!(matches!(err, Error::DatabaseError(_)));
+    }
+
+    #[test]
+    fn test_query_preparation_error_display() {
+        let err = Error::QueryPreparationError("unbalanced group".to_string());
+        assert_eq!(err.to_string(), "unbalanced group");
+    }
+}
@@ -16,7 +16,7 @@
 //!
 //! ```ignore
 //! use lorm::ToLOrm;
-//! use lorm::predicates::Where;
+//! use lorm::predicates::{OrderBy, Where};
 //! use sqlx::{FromRow, SqlitePool};
 //!
 //! #[derive(Debug, Default, Clone, FromRow, ToLOrm)]
@@ -44,8 +44,7 @@
 //!     // Query with filtering and pagination
 //!     let users = User::select()
 //!         .where_email(Where::Eq, "alice@example.com")
-//!         .order_by_email()
-//!         .desc()
+//!         .order_by_email(OrderBy::Desc)
 //!         .limit(10)
 //!         .build(&pool)
 //!         .await?;
@@ -61,24 +60,86 @@
 //!
 //! For a struct with `#[derive(ToLOrm)]`, Lorm generates:
 //!
-//! - `save(&executor)` - Insert or update (upsert)
-//! - `delete(&executor)` - Delete by primary key
+//! - `save(&executor)` - Insert or update (upsert), failing with `Error::OptimisticLockConflict` when a `#[lorm(version)]` field is stale
+//! - `save_and_reload(&write_executor, &read_executor)` - Save, then read the row back on another executor
+//! - `upsert_many(&executor, &[Self])` - Bulk insert or update in a single statement
+//! - `save_batch_in_chunks(conn, &[Self], chunk_size)` - Chunked `upsert_many` inside one transaction
+//! - `insert_many(conn, &[Self])` - Bulk insert with multi-row statements inside one transaction
+//! - `delete(&executor)` - Delete by primary key, or mark the row as deleted with a `#[lorm(soft_delete)]` field
+//! - `hard_delete(&executor)` - Delete a soft-deletable row for good
+//! - `delete_many(conn, &[pk])` - Delete the rows with any of the primary keys inside one transaction
+//! - `delete_where()` - Start a conditional bulk delete, run with `execute(&executor)`
+//! - `hard_delete_many(conn, &[pk])`, `hard_delete_by_{field}(&executor, value)`, `hard_delete_where()` - Same as the `delete` variants, removing soft-deletable rows for good
+//! - `truncate_cascade(&executor)` - Empty the table and every table referencing it (PostgreSQL only)
+//! - `update_where()` - Start a conditional bulk update (`set_{field}()` + `where_{field}()`), run with `execute(&executor)`
+//! - `apply_patch(&executor, pk, &patch)` - Update only the fields set in a `{Struct}Patch`
+//! - `tracked()` - Wrap a row in a `{Struct}Tracked`, whose `save(&executor)` only updates the fields changed through its setters
+//! - `force_set_{field}(&executor, pk, value)` - Write a `readonly` column directly
 //! - `by_{field}(&executor, value)` - Find one by field (for `#[lorm(by)]` fields)
+//! - `by_{field}_unique(&executor, value)` - Same, failing with `Error::MultipleRowsError` if several rows match
+//! - `by_{field}_or_insert(&executor, value, || Self)` - Find one by a unique field or the primary key, else insert the row built by the closure
+//! - `try_by_{field}(&executor, value)` - Same as `by_{field}()`, returning `None` when nothing matches
+//! - `count_grouped_by_{field}(&executor)` - Count the records per distinct value of the field
+//! - `exists_by_{field}(&executor, value)` - Check whether a record matches the field value
+//! - `exists_by_pk(&executor, pk)` - Check whether a primary key exists
+//! - `by_pk_for_update(&mut *tx, pk)` - Fetch and lock a row (`FOR UPDATE`, no lock on SQLite)
+//! - `pluck_pks(&executor)` - Fetch every primary key
+//! - `max_pk(&executor)` / `min_pk(&executor)` - Fetch the highest / lowest primary key
+//! - `reload_many(conn, &mut items)` - Refresh loaded records in place, one query per chunk of records
 //! - `with_{field}(&executor, value)` - Find all by field (for `#[lorm(by)]` fields)
+//! - `with_{field}_in(conn, &[values])` - Find all matching any of the values with `IN` queries split under the bind parameter limit
+//! - `count_by_{field}_in(conn, &[values])` - Count the rows matching any of the values
 //! - `select()` - Start a query builder
+//! - `stream_all(&executor)` - Stream every row of the table instead of loading them all
+//! - `all_ordered_by_{field}(&executor, OrderBy)` - Fetch all rows ordered by a field
 //!
 //! # Query Builder
 //!
 //! The `select()` method returns a builder with these methods:
 //!
 //! - `where_{field}(Where::Eq, value)` - Filter by comparison
+//! - `where_{field}_with(Where::Eq, |sql| value)` - Same, with the value computed at build time
 //! - `where_between_{field}(start, end)` - Filter by range
-//! - `order_by_{field}()` - Add ordering (chain with `.asc()` or `.desc()`)
+//! - `where_null_{field}()` / `where_not_null_{field}()` - Filter by an `Option` field being NULL
+//! - `where_in_{field}(&[value])` / `where_not_in_{field}(&[value])` - Filter by a list of values
+//! - `where_in_{field}_ref(&[&value])` - Filter by a list of borrowed values
+//! - `where_eq_any_{field}(&[value])` / `where_gt_all_{field}(&[value])` - Compare against an array (PostgreSQL only)
+//! - `where_is_{field}()` / `where_not_{field}()` - Filter by a `bool` field
+//! - `where_between_exclusive_{field}(start, end)` - Filter by range, excluding both ends
+//! - `raw_where(sql)` / `with_cte(name, builder)` - Raw conditions and common table expressions
+//! - `order_by_{field}(OrderBy::Asc)` - Add ordering, in the given direction
+//! - `order_by_{field}_nulls(OrderBy::Asc, Nulls::Last)` - Same, placing NULL values first or last
 //! - `group_by_{field}()` - Group results
 //! - `limit(n)` / `offset(n)` - Pagination
+//! - `fetch(n)` - `FETCH NEXT n ROWS ONLY`, the SQL-standard `limit(n)` (PostgreSQL only)
+//! - `seek_after_{field}(key)` / `then_offset(n)` - Keyset pagination
+//! - `persistent(bool)` - Whether sqlx caches the prepared statement
+//! - `count_estimate(&executor)` - Approximate table row count (PostgreSQL only)
+//! - `for_update()` / `lock_timeout(d)` + `build_locked(&executor)` - Row locking (PostgreSQL only)
+//! - `statement_timeout(d)` + `build_locked(&executor)` - Server-side query timeout (PostgreSQL only)
+//! - `where_regex_{field}(pattern)` / `where_regex_i_{field}(pattern)` - Regular expressions (PostgreSQL and MySQL)
+//! - `use_index(index)` / `with_hint(hint)` - Index hints (MySQL only)
+//! - `window_rank(rank, partition_by, order_by, alias)` - Ranking window functions
 //! - `build(&executor)` - Execute and return results
+//! - `first(&executor)` / `one(&executor)` - Execute returning the first row as an `Option` / exactly one row
+//! - `build_as::<T>(&executor)` - Execute and map rows to another `FromRow` type
+//! - `fetch_chunks(size, &executor)` - Stream the results in `Vec` chunks of `size` rows, one `LIMIT`/`OFFSET` query each
+//! - `join_{relation}()` / `build_joined(&executor)` - Fetch each row with the row a `belongs_to` field refers to
+//! - `fetch_rows(&executor)` - Execute and return the untyped rows
+//! - `exists(&executor)` - Whether any row matches
+//! - `count(&executor)` - Count the rows `build()` would return, without fetching them
+//! - `count_distinct(&columns, &executor)` - Count the distinct combinations of columns
+//! - `into_query_builder()` - Hand the query over to sqlx as a `sqlx::QueryBuilder`
+//! - `tap_sql(|sql| ...)` - Inspect the SQL accumulated so far without consuming the builder
+//! - `sql()` / `into_sql()` - The SQL accumulated so far, or the exact statement `build()` would run
+//! - `sql_and_args()` - Same as `sql()`, with the `Debug` output of the bound values
+//! - `pluck_{a}_{b}(&executor)` - Execute selecting two columns only, as `(A, B)` tuples
+//! - `distinct_values_{field}(&executor)` - Execute selecting the distinct values of a column
+//! - `group_concat_{field}(sep, &executor)` - Execute joining a string column by `sep` per group
+//! - `scalar(expr, &executor)` - Execute selecting a single raw SQL expression, e.g. `MAX(price)`
 
 pub mod errors;
 pub mod predicates;
+pub mod query;
 
 pub use lorm_macros::ToLOrm;
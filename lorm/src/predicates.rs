@@ -45,6 +45,26 @@ pub enum Where {
 
     /// Like (`LIKE`) to search for a specified pattern
     Like,
+
+    /// Case insensitive like (`ILIKE`) to search for a specified pattern.
+    ///
+    /// SQLite and MySQL have no `ILIKE`, the condition is rendered as `LOWER(column) LIKE LOWER(pattern)`.
+    ILike,
+
+    /// Null-safe not equals (`IS DISTINCT FROM`): unlike `NotEq`, a NULL column is distinct from
+    /// any value.
+    ///
+    /// MySQL has no `IS DISTINCT FROM`, the condition is rendered as `NOT (column <=> value)`.
+    DistinctFrom,
+
+    /// Null-safe equals (`IS NOT DISTINCT FROM`), rendered as `<=>` on MySQL.
+    NotDistinctFrom,
+
+    /// No condition at all: `where_{field}(Where::Any, value)` leaves the query unchanged and
+    /// ignores the value, so that dynamic filters can always pass an operator.
+    ///
+    /// Renders as an empty string.
+    Any,
 }
 
 impl Display for Where {
@@ -58,6 +78,16 @@ impl Display for Where {
             Where::LesserThan => write!(f, "<"),
             Where::LesserOrEqualTo => write!(f, "<="),
             Where::Like => write!(f, "LIKE"),
+            #[cfg(feature = "postgres")]
+            Where::ILike => write!(f, "ILIKE"),
+            #[cfg(not(feature = "postgres"))]
+            Where::ILike => write!(f, "LIKE"),
+            Where::DistinctFrom => write!(f, "IS DISTINCT FROM"),
+            #[cfg(feature = "mysql")]
+            Where::NotDistinctFrom => write!(f, "<=>"),
+            #[cfg(not(feature = "mysql"))]
+            Where::NotDistinctFrom => write!(f, "IS NOT DISTINCT FROM"),
+            Where::Any => Ok(()),
         }
     }
 }
@@ -155,6 +185,106 @@ impl Display for Function {
     }
 }
 
+/// Sort direction for ORDER BY clauses.
+///
+/// # Example
+///
+/// ```ignore
+/// use lorm::predicates::OrderBy;
+///
+/// // All users, most recent first
+/// let users = User::all_ordered_by_created_at(&pool, OrderBy::Desc).await?;
+/// ```
+#[derive(Default, Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub enum OrderBy {
+    /// Ascending (`ASC`) order
+    #[default]
+    Asc,
+
+    /// Descending (`DESC`) order
+    Desc,
+}
+
+impl Display for OrderBy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OrderBy::Asc => write!(f, "ASC"),
+            OrderBy::Desc => write!(f, "DESC"),
+        }
+    }
+}
+
+/// Placement of NULL values in ORDER BY clauses, used with the generated
+/// `order_by_{field}_nulls()` builder methods of `Option` fields.
+///
+/// MySQL has no `NULLS FIRST`/`NULLS LAST`, the placement is emulated by ordering on
+/// `(column IS NULL)` first.
+///
+/// # Example
+///
+/// ```ignore
+/// use lorm::predicates::{Nulls, OrderBy};
+///
+/// // Highest counts first, users without a count last
+/// let users = User::select()
+///     .order_by_count_nulls(OrderBy::Desc, Nulls::Last)
+///     .build(&pool)
+///     .await?;
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub enum Nulls {
+    /// NULL values sort before the others (`NULLS FIRST`)
+    First,
+
+    /// NULL values sort after the others (`NULLS LAST`)
+    Last,
+}
+
+impl Display for Nulls {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Nulls::First => write!(f, "NULLS FIRST"),
+            Nulls::Last => write!(f, "NULLS LAST"),
+        }
+    }
+}
+
+/// Ranking window functions, used with the generated `window_rank()` builder method.
+///
+/// # Example
+///
+/// ```ignore
+/// use lorm::predicates::{OrderBy, Rank};
+///
+/// // Leaderboard: players ranked by score, ties sharing a rank
+/// let ranked: Vec<RankedPlayer> = Player::select()
+///     .window_rank(Rank::DenseRank, &[], &[("score", OrderBy::Desc)], "position")
+///     .build_as(&pool)
+///     .await?;
+/// ```
+#[derive(Default, Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub enum Rank {
+    /// Sequential number of the row (`ROW_NUMBER`), ties broken arbitrarily
+    #[default]
+    RowNumber,
+
+    /// Rank with gaps after ties (`RANK`)
+    Rank,
+
+    /// Rank without gaps after ties (`DENSE_RANK`)
+    DenseRank,
+}
+
+impl Display for Rank {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rank::RowNumber => write!(f, "ROW_NUMBER"),
+            Rank::Rank => write!(f, "RANK"),
+            Rank::DenseRank => write!(f, "DENSE_RANK"),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -168,6 +298,16 @@ mod tests {
         assert_eq!(Where::LesserThan.to_string(), "<");
         assert_eq!(Where::LesserOrEqualTo.to_string(), "<=");
         assert_eq!(Where::Like.to_string(), "LIKE");
+        #[cfg(feature = "postgres")]
+        assert_eq!(Where::ILike.to_string(), "ILIKE");
+        #[cfg(not(feature = "postgres"))]
+        assert_eq!(Where::ILike.to_string(), "LIKE");
+        assert_eq!(Where::DistinctFrom.to_string(), "IS DISTINCT FROM");
+        #[cfg(feature = "mysql")]
+        assert_eq!(Where::NotDistinctFrom.to_string(), "<=>");
+        #[cfg(not(feature = "mysql"))]
+        assert_eq!(Where::NotDistinctFrom.to_string(), "IS NOT DISTINCT FROM");
+        assert_eq!(Where::Any.to_string(), "");
     }
 
     #[test]
@@ -191,4 +331,20 @@ mod tests {
         assert_eq!(Function::Min.to_string(), "MIN");
         assert_eq!(Function::Max.to_string(), "MAX");
     }
+
+    #[test]
+    fn test_order_by_display() {
+        assert_eq!(OrderBy::Asc.to_string(), "ASC");
+        assert_eq!(OrderBy::Desc.to_string(), "DESC");
+        assert_eq!(Nulls::First.to_string(), "NULLS FIRST");
+        assert_eq!(Nulls::Last.to_string(), "NULLS LAST");
+        assert_eq!(OrderBy::default(), OrderBy::Asc);
+    }
+
+    #[test]
+    fn test_rank_display() {
+        assert_eq!(Rank::RowNumber.to_string(), "ROW_NUMBER");
+        assert_eq!(Rank::Rank.to_string(), "RANK");
+        assert_eq!(Rank::DenseRank.to_string(), "DENSE_RANK");
+    }
 }
@@ -0,0 +1,186 @@
+//! Building blocks shared by the generated query builders.
+
+use futures_util::TryStreamExt;
+use sqlx::{Database, QueryBuilder};
+use std::cell::RefCell;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+/// Re-exported so that the generated streaming methods can be named without depending on `futures`.
+pub use futures_util::Stream;
+
+/// A piece of a query (SQL text and/or bound values) replayed into an `sqlx::QueryBuilder`
+/// when the query is built. Bound values are only numbered at that point, which is what allows
+/// a builder to be embedded in another query.
+pub type SqlFragment<'a, DB> = Arc<dyn Fn(&mut QueryBuilder<'a, DB>) + Send + Sync + 'a>;
+
+/// Implemented by the generated select builders so that they can be embedded in another
+/// query, e.g. as a common table expression with `with_cte()`.
+pub trait SubQuery<'a, DB: Database> {
+    /// Consumes the builder and returns its fragments, or the reason it cannot be built.
+    fn into_fragments(self) -> crate::errors::Result<Vec<SqlFragment<'a, DB>>>;
+}
+
+/// Fragment pushing raw SQL text.
+pub fn sql_fragment<'a, DB: Database>(sql: impl Into<String>) -> SqlFragment<'a, DB> {
+    let sql = sql.into();
+    Arc::new(move |builder: &mut QueryBuilder<'a, DB>| {
+        builder.push(sql.as_str());
+    })
+}
+
+thread_local! {
+    /// The `Debug` output of the values bound while `record_args()` runs on this thread.
+    static RECORDED_ARGS: RefCell<Option<Vec<String>>> = const { RefCell::new(None) };
+}
+
+/// Runs `f`, returning its result and the `Debug` output of every value bound by the fragments
+/// replayed meanwhile, in bind order. Used to debug a query without running it.
+pub fn record_args<R>(f: impl FnOnce() -> R) -> (R, Vec<String>) {
+    let outer = RECORDED_ARGS.with(|args| args.borrow_mut().replace(Vec::new()));
+    let result = f();
+    let recorded = RECORDED_ARGS.with(|args| std::mem::replace(&mut *args.borrow_mut(), outer));
+    (result, recorded.unwrap_or_default())
+}
+
+fn record_arg(value: &impl Debug) {
+    RECORDED_ARGS.with(|args| {
+        if let Some(args) = args.borrow_mut().as_mut() {
+            args.push(format!("{value:?}"));
+        }
+    });
+}
+
+/// Fragment binding `value`, cloned every time the fragment is replayed.
+pub fn bind_fragment<'a, DB: Database, T>(value: T) -> SqlFragment<'a, DB>
+where
+    T: sqlx::Encode<'a, DB> + sqlx::Type<DB> + Debug + Clone + Send + Sync + 'a,
+{
+    Arc::new(move |builder: &mut QueryBuilder<'a, DB>| {
+        record_arg(&value);
+        builder.push_bind(value.clone());
+    })
+}
+
+/// Fragment binding the value returned by `f`, called with the SQL built so far every time the
+/// fragment is replayed.
+pub fn lazy_bind_fragment<'a, DB: Database, T, F>(f: F) -> SqlFragment<'a, DB>
+where
+    T: sqlx::Encode<'a, DB> + sqlx::Type<DB> + Debug + Send + 'a,
+    F: Fn(&str) -> T + Send + Sync + 'a,
+{
+    Arc::new(move |builder: &mut QueryBuilder<'a, DB>| {
+        let value = f(builder.sql());
+        record_arg(&value);
+        builder.push_bind(value);
+    })
+}
+
+/// Maps the errors of a row stream, e.g. from sqlx's `fetch()`, to Lorm errors.
+pub fn map_stream_err<'e, T: 'e>(
+    stream: impl Stream<Item = Result<T, sqlx::Error>> + Send + 'e,
+) -> impl Stream<Item = crate::errors::Result<T>> + Send + 'e {
+    stream.map_err(crate::errors::Error::from)
+}
+
+/// Streams the rows of a query in chunks of `size` rows, the query being replayed from
+/// `fragments` with ` LIMIT size OFFSET n` appended and run by `fetch` once per chunk. The stream
+/// ends after the first chunk shorter than `size`, and yields `fragments`' error if there is one.
+pub fn fetch_chunks<'a: 'e, 'e, DB, T, F, Fut>(
+    fragments: crate::errors::Result<Vec<SqlFragment<'a, DB>>>,
+    size: i64,
+    fetch: F,
+) -> impl Stream<Item = crate::errors::Result<Vec<T>>> + Send + 'e
+where
+    DB: Database,
+    i64: sqlx::Encode<'a, DB> + sqlx::Type<DB>,
+    F: Fn(QueryBuilder<'a, DB>) -> Fut + Send + 'e,
+    Fut: Future<Output = crate::errors::Result<Vec<T>>> + Send + 'e,
+    T: Send + 'e,
+{
+    let (fragments, error) = match fragments {
+        Ok(_) if size <= 0 => (
+            Vec::new(),
+            Some(crate::errors::Error::QueryPreparationError(format!(
+                "fetch_chunks() needs a positive chunk size, got {size}"
+            ))),
+        ),
+        Ok(fragments) => (fragments, None),
+        Err(e) => (Vec::new(), Some(e)),
+    };
+    futures_util::stream::try_unfold(
+        (fragments, fetch, error, 0, false),
+        move |(fragments, fetch, error, offset, done)| async move {
+            if let Some(e) = error {
+                return Err(e);
+            }
+            if done {
+                return Ok(None);
+            }
+            let chunk = fetch(chunk_query(&fragments, size, offset)).await?;
+            if chunk.is_empty() {
+                return Ok(None);
+            }
+            let done = (chunk.len() as i64) < size;
+            Ok(Some((chunk, (fragments, fetch, None, offset + size, done))))
+        },
+    )
+}
+
+fn chunk_query<'a, DB>(
+    fragments: &[SqlFragment<'a, DB>],
+    size: i64,
+    offset: i64,
+) -> QueryBuilder<'a, DB>
+where
+    DB: Database,
+    i64: sqlx::Encode<'a, DB> + sqlx::Type<DB>,
+{
+    let mut builder = QueryBuilder::new("");
+    for fragment in fragments {
+        fragment(&mut builder);
+    }
+    builder.push(" LIMIT ");
+    builder.push_bind(size);
+    builder.push(" OFFSET ");
+    builder.push_bind(offset);
+    builder
+}
+
+/// Implemented by the derive so that a model can be fetched along the rows of another one, see
+/// the select builders' `join_{relation}()` and `build_joined()`.
+pub trait JoinTarget<DB: Database>: Sized {
+    /// Table the model is stored in.
+    const TABLE_NAME: &'static str;
+
+    /// Primary key column, the one a foreign key refers to.
+    const PRIMARY_KEY: &'static str;
+
+    /// Columns of the model, as read by its `FromRow`.
+    const COLUMNS: &'static [&'static str];
+
+    /// Alias of the joined table, whose columns are selected as `{alias}__{column}` so that they
+    /// never clash with the columns of the joining model.
+    fn join_alias() -> String {
+        let table = Self::TABLE_NAME.replace(|c: char| !c.is_ascii_alphanumeric(), "_");
+        format!("j_{table}")
+    }
+
+    /// ` JOIN` clause matching the primary key to `foreign_key`, a column of the joining model.
+    fn join_clause(foreign_key: &str) -> String {
+        let alias = Self::join_alias();
+        let columns = Self::COLUMNS
+            .iter()
+            .map(|column| format!("{column} AS {alias}__{column}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!(
+            " JOIN (SELECT {columns} FROM {}) {alias} ON {alias}.{alias}__{} = {foreign_key}",
+            Self::TABLE_NAME,
+            Self::PRIMARY_KEY
+        )
+    }
+
+    /// Reads the model from the columns named `{prefix}{column}`.
+    fn from_prefixed_row(row: &DB::Row, prefix: &str) -> Result<Self, sqlx::Error>;
+}
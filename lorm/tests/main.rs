@@ -1,7 +1,8 @@
 use anyhow::Result;
 use fake::Fake;
 use fake::faker::internet::en::SafeEmail;
-use lorm::predicates::{Function, Having, Where};
+use futures_util::TryStreamExt;
+use lorm::predicates::{Function, Having, Nulls, OrderBy, Rank, Where};
 use sqlx::Executor;
 use std::ops::Add;
 use std::time::Duration;
@@ -25,6 +26,12 @@ type Pool = PgPool;
 #[cfg(feature = "mysql")]
 type Pool = MySqlPool;
 
+/// The bind parameter limit the generated batch methods split their statements under.
+#[cfg(feature = "sqlite")]
+const MAX_BIND_PARAMETERS: usize = 999;
+#[cfg(not(feature = "sqlite"))]
+const MAX_BIND_PARAMETERS: usize = 65535;
+
 #[cfg(any(feature = "sqlite", feature = "postgres"))]
 mod models {
     use chrono::FixedOffset;
@@ -76,7 +83,7 @@ mod models {
         #[lorm(readonly)]
         pub id: i32,
 
-        #[lorm(by)]
+        #[lorm(by, unique)]
         #[sqlx(rename = "e_mail")]
         pub email: String,
 
@@ -94,6 +101,24 @@ mod models {
         pub updated_at: chrono::DateTime<FixedOffset>,
     }
 
+    /// The alt_users table, queried under a table alias.
+    #[derive(Debug, Default, Clone, FromRow, ToLOrm)]
+    #[lorm(rename = "alt_users", table_alias = "au")]
+    pub struct AliasedAltUser {
+        #[allow(unused)]
+        #[lorm(pk)]
+        #[lorm(readonly)]
+        pub id: i32,
+
+        #[allow(unused)]
+        #[lorm(by)]
+        #[sqlx(rename = "e_mail")]
+        pub email: String,
+
+        #[lorm(by)]
+        pub count: Option<i32>,
+    }
+
     #[derive(Debug, Default, Clone, sqlx::FromRow, ToLOrm)]
     pub struct Profile {
         #[lorm(pk)]
@@ -163,6 +188,41 @@ mod models {
         pub name: String,
     }
 
+    #[derive(Debug, Default, Clone, lorm::ToLOrm)]
+    #[lorm(pk_type = "manual", rename = "tags")]
+    pub struct SharedTag {
+        #[lorm(pk)]
+        #[lorm(by)]
+        pub name: std::sync::Arc<str>,
+    }
+
+    // sqlx cannot decode an `Arc<str>`, it is read as a `String`.
+    impl<'r, R: sqlx::Row> sqlx::FromRow<'r, R> for SharedTag
+    where
+        &'r str: sqlx::ColumnIndex<R>,
+        String: sqlx::Decode<'r, R::Database> + sqlx::Type<R::Database>,
+    {
+        fn from_row(row: &'r R) -> Result<Self, sqlx::Error> {
+            let name: String = row.try_get("name")?;
+            Ok(Self { name: name.into() })
+        }
+    }
+
+    /// Not `Default`, it can only be initialized by `#[lorm(transient, default = "...")]`.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Label(pub String);
+
+    #[derive(Debug, Clone, lorm::ToLOrm)]
+    #[lorm(pk_type = "manual", rename = "tags")]
+    pub struct LabeledTag {
+        #[lorm(pk)]
+        #[lorm(by)]
+        pub name: String,
+
+        #[lorm(transient, default = "Label(\"unlabeled\".to_string())")]
+        pub label: Label,
+    }
+
     #[derive(Debug, Default, Clone, sqlx::FromRow, lorm::ToLOrm)]
     #[lorm(pk_type = "manual", rename = "tags")]
     pub struct TagRef {
@@ -205,6 +265,132 @@ mod models {
         pub user_id: Option<Uuid>,
     }
 
+    /// The users table, named with its schema.
+    #[derive(Debug, Default, Clone, FromRow, ToLOrm)]
+    #[lorm(rename = "public.users")]
+    pub struct QualifiedUser {
+        #[allow(unused)]
+        #[lorm(pk)]
+        pub id: Uuid,
+        #[allow(unused)]
+        #[lorm(by)]
+        pub email: String,
+    }
+
+    /// The documents table, named with its schema, read through the soft-delete derived table.
+    #[derive(Debug, Default, Clone, FromRow, ToLOrm)]
+    #[lorm(rename = "public.documents")]
+    pub struct QualifiedDocument {
+        #[allow(unused)]
+        #[lorm(pk)]
+        pub id: Uuid,
+        #[allow(unused)]
+        #[lorm(by)]
+        pub title: String,
+        #[allow(unused)]
+        #[lorm(soft_delete)]
+        pub deleted_at: Option<chrono::DateTime<FixedOffset>>,
+    }
+
+    /// Every non-pk column is readonly: saving an existing row has nothing to update.
+    #[derive(Debug, Default, Clone, FromRow, ToLOrm)]
+    pub struct Visit {
+        #[lorm(pk)]
+        pub id: Uuid,
+        #[lorm(created_at)]
+        #[lorm(readonly)]
+        pub created_at: chrono::DateTime<FixedOffset>,
+    }
+
+    /// `updated_at` is maintained by a trigger on the table.
+    #[derive(Debug, Default, Clone, FromRow, ToLOrm)]
+    pub struct Note {
+        #[lorm(pk)]
+        pub id: Uuid,
+        pub body: String,
+        #[lorm(updated_at, db_managed)]
+        pub updated_at: chrono::DateTime<FixedOffset>,
+    }
+
+    /// The documents table without the soft delete, its Uuid key relying on the default `new`.
+    #[derive(Debug, Default, Clone, FromRow, ToLOrm)]
+    #[lorm(rename = "documents")]
+    pub struct PlainDocument {
+        // Without `new` and `is_set`, Uuid keys default to `Uuid::new_v4()` and `Uuid::is_nil`.
+        #[lorm(pk)]
+        pub id: Uuid,
+        pub title: String,
+    }
+
+    /// `delete()` only stamps `deleted_at`, lookups skip the stamped rows.
+    #[derive(Debug, Default, Clone, FromRow, ToLOrm)]
+    pub struct Document {
+        #[lorm(pk)]
+        pub id: Uuid,
+        #[lorm(by)]
+        pub title: String,
+        #[lorm(soft_delete)]
+        pub deleted_at: Option<chrono::DateTime<FixedOffset>>,
+    }
+
+    /// `save()` only updates the row still holding the same `version`.
+    #[derive(Debug, Default, Clone, FromRow, ToLOrm)]
+    pub struct Page {
+        #[lorm(pk)]
+        pub id: Uuid,
+        pub body: String,
+        #[lorm(version)]
+        pub version: i64,
+    }
+
+    /// A plain integer key, assigned by the database.
+    #[derive(Debug, Default, Clone, FromRow, ToLOrm)]
+    pub struct Event {
+        #[lorm(pk)]
+        pub id: i64,
+        pub name: String,
+    }
+
+    /// A `by` bool field, for `where_is_{field}()` and `count_grouped_by_{field}()`.
+    #[derive(Debug, Default, Clone, FromRow, ToLOrm)]
+    pub struct Task {
+        #[lorm(pk)]
+        pub id: Uuid,
+        pub title: String,
+        #[lorm(by)]
+        pub done: bool,
+    }
+
+    /// Upserted on its unique email with `upsert_on_email()`.
+    #[derive(Debug, Default, Clone, FromRow, ToLOrm)]
+    pub struct Subscriber {
+        #[lorm(pk)]
+        #[lorm(readonly)]
+        pub id: i32,
+
+        #[lorm(by, unique)]
+        pub email: String,
+
+        pub count: Option<i32>,
+
+        #[allow(unused)]
+        #[lorm(created_at)]
+        #[lorm(readonly)]
+        pub created_at: chrono::DateTime<FixedOffset>,
+    }
+
+    /// Timestamps without a `new` attribute, defaulting to the current time.
+    #[derive(Debug, Default, Clone, FromRow, ToLOrm)]
+    pub struct Reminder {
+        #[lorm(pk)]
+        pub id: Uuid,
+        pub title: String,
+        #[lorm(created_at)]
+        pub created_at: chrono::DateTime<FixedOffset>,
+        #[lorm(updated_at)]
+        pub updated_at: chrono::DateTime<FixedOffset>,
+    }
+
     #[cfg(feature = "sqlite")]
     impl<'r> sqlx::FromRow<'r, sqlx::sqlite::SqliteRow> for OptCustomer {
         fn from_row(row: &'r sqlx::sqlite::SqliteRow) -> Result<Self, sqlx::Error> {
@@ -254,6 +440,25 @@ mod models {
             Ok(Self { id, email, address })
         }
     }
+
+    /// `i128` has no SQL type: the key is stored as TEXT, Lorm implements `FromRow` to parse it.
+    #[derive(Debug, Default, Clone, lorm::ToLOrm)]
+    pub struct Ledger {
+        #[lorm(pk, store_as = "text")]
+        #[lorm(new = "i128::from(u64::MAX) + i128::from(Uuid::new_v4().as_u64_pair().0)")]
+        pub id: i128,
+        pub name: String,
+    }
+
+    /// `INET` column mapped to `IpNetwork` through sqlx's `ipnetwork` feature.
+    #[cfg(feature = "postgres")]
+    #[derive(Debug, Clone, FromRow, ToLOrm)]
+    pub struct Device {
+        #[lorm(pk, new = "Uuid::new_v4()")]
+        pub id: Uuid,
+        #[lorm(by)]
+        pub ip: sqlx::types::ipnetwork::IpNetwork,
+    }
 }
 
 #[cfg(feature = "mysql")]
@@ -307,7 +512,7 @@ mod models {
         #[lorm(readonly)]
         pub id: i32,
 
-        #[lorm(by)]
+        #[lorm(by, unique)]
         #[sqlx(rename = "e_mail")]
         pub email: String,
 
@@ -325,6 +530,24 @@ mod models {
         pub updated_at: chrono::DateTime<Utc>,
     }
 
+    /// The alt_users table, queried under a table alias.
+    #[derive(Debug, Default, Clone, FromRow, ToLOrm)]
+    #[lorm(rename = "alt_users", table_alias = "au")]
+    pub struct AliasedAltUser {
+        #[allow(unused)]
+        #[lorm(pk)]
+        #[lorm(readonly)]
+        pub id: i32,
+
+        #[allow(unused)]
+        #[lorm(by)]
+        #[sqlx(rename = "e_mail")]
+        pub email: String,
+
+        #[lorm(by)]
+        pub count: Option<i32>,
+    }
+
     #[derive(Debug, Default, Clone, sqlx::FromRow, ToLOrm)]
     pub struct Profile {
         #[lorm(pk)]
@@ -418,6 +641,41 @@ mod models {
         pub name: String,
     }
 
+    #[derive(Debug, Default, Clone, lorm::ToLOrm)]
+    #[lorm(pk_type = "manual", rename = "tags")]
+    pub struct SharedTag {
+        #[lorm(pk)]
+        #[lorm(by)]
+        pub name: std::sync::Arc<str>,
+    }
+
+    // sqlx cannot decode an `Arc<str>`, it is read as a `String`.
+    impl<'r, R: sqlx::Row> sqlx::FromRow<'r, R> for SharedTag
+    where
+        &'r str: sqlx::ColumnIndex<R>,
+        String: sqlx::Decode<'r, R::Database> + sqlx::Type<R::Database>,
+    {
+        fn from_row(row: &'r R) -> Result<Self, sqlx::Error> {
+            let name: String = row.try_get("name")?;
+            Ok(Self { name: name.into() })
+        }
+    }
+
+    /// Not `Default`, it can only be initialized by `#[lorm(transient, default = "...")]`.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Label(pub String);
+
+    #[derive(Debug, Clone, lorm::ToLOrm)]
+    #[lorm(pk_type = "manual", rename = "tags")]
+    pub struct LabeledTag {
+        #[lorm(pk)]
+        #[lorm(by)]
+        pub name: String,
+
+        #[lorm(transient, default = "Label(\"unlabeled\".to_string())")]
+        pub label: Label,
+    }
+
     #[derive(Debug, Default, Clone, FromRow, ToLOrm)]
     #[lorm(has_many(Self, fk = "parent_id", as = "children"))]
     pub struct Category {
@@ -452,6 +710,113 @@ mod models {
         #[lorm(belongs_to = User)]
         pub user_id: Option<Uuid>,
     }
+
+    /// Every non-pk column is readonly: saving an existing row has nothing to update.
+    #[derive(Debug, Default, Clone, FromRow, ToLOrm)]
+    pub struct Visit {
+        #[lorm(pk)]
+        pub id: Uuid,
+        #[lorm(created_at)]
+        #[lorm(readonly)]
+        pub created_at: chrono::DateTime<Utc>,
+    }
+
+    /// `updated_at` is maintained by a trigger on the table.
+    #[derive(Debug, Default, Clone, FromRow, ToLOrm)]
+    pub struct Note {
+        #[lorm(pk)]
+        pub id: Uuid,
+        pub body: String,
+        #[lorm(updated_at, db_managed)]
+        pub updated_at: chrono::DateTime<Utc>,
+    }
+
+    /// The documents table without the soft delete, its Uuid key relying on the default `new`.
+    #[derive(Debug, Default, Clone, FromRow, ToLOrm)]
+    #[lorm(rename = "documents")]
+    pub struct PlainDocument {
+        // Without `new` and `is_set`, Uuid keys default to `Uuid::new_v4()` and `Uuid::is_nil`.
+        #[lorm(pk)]
+        pub id: Uuid,
+        pub title: String,
+    }
+
+    /// `delete()` only stamps `deleted_at`, lookups skip the stamped rows.
+    #[derive(Debug, Default, Clone, FromRow, ToLOrm)]
+    pub struct Document {
+        #[lorm(pk)]
+        pub id: Uuid,
+        #[lorm(by)]
+        pub title: String,
+        #[lorm(soft_delete)]
+        pub deleted_at: Option<chrono::DateTime<Utc>>,
+    }
+
+    /// `save()` only updates the row still holding the same `version`.
+    #[derive(Debug, Default, Clone, FromRow, ToLOrm)]
+    pub struct Page {
+        #[lorm(pk)]
+        pub id: Uuid,
+        pub body: String,
+        #[lorm(version)]
+        pub version: i64,
+    }
+
+    /// A plain integer key, assigned by the database.
+    #[derive(Debug, Default, Clone, FromRow, ToLOrm)]
+    pub struct Event {
+        #[lorm(pk)]
+        pub id: i64,
+        pub name: String,
+    }
+
+    /// A `BIGINT UNSIGNED AUTO_INCREMENT` key, assigned by the database.
+    #[derive(Debug, Default, Clone, FromRow, ToLOrm)]
+    pub struct Tick {
+        #[lorm(pk)]
+        pub id: u64,
+        pub name: String,
+    }
+
+    /// A `by` bool field, for `where_is_{field}()` and `count_grouped_by_{field}()`.
+    #[derive(Debug, Default, Clone, FromRow, ToLOrm)]
+    pub struct Task {
+        #[lorm(pk)]
+        pub id: Uuid,
+        pub title: String,
+        #[lorm(by)]
+        pub done: bool,
+    }
+
+    /// Upserted on its unique email with `upsert_on_email()`.
+    #[derive(Debug, Default, Clone, FromRow, ToLOrm)]
+    pub struct Subscriber {
+        #[lorm(pk)]
+        #[lorm(readonly)]
+        pub id: i32,
+
+        #[lorm(by, unique)]
+        pub email: String,
+
+        pub count: Option<i32>,
+
+        #[allow(unused)]
+        #[lorm(created_at)]
+        #[lorm(readonly)]
+        pub created_at: chrono::DateTime<Utc>,
+    }
+
+    /// Timestamps without a `new` attribute, defaulting to the current time.
+    #[derive(Debug, Default, Clone, FromRow, ToLOrm)]
+    pub struct Reminder {
+        #[lorm(pk)]
+        pub id: Uuid,
+        pub title: String,
+        #[lorm(created_at)]
+        pub created_at: chrono::DateTime<Utc>,
+        #[lorm(updated_at)]
+        pub updated_at: chrono::DateTime<Utc>,
+    }
 }
 
 use models::*;
@@ -560,26 +925,234 @@ async fn test_user_does_not_exists() {
 
     let email = SafeEmail().fake::<String>();
     let res = User::by_email(&pool, &email).await;
-    assert_eq!(res.is_err(), true);
+    assert!(matches!(res, Err(lorm::errors::Error::NotFound)));
 
     let id = Uuid::new_v4();
     let res = User::by_id(&pool, &id).await;
-    assert_eq!(res.is_err(), true);
+    assert!(matches!(res, Err(lorm::errors::Error::NotFound)));
 }
 
 #[tokio::test]
-async fn test_user_is_created() {
+async fn test_try_by_field_returns_none_when_missing() {
     let pool = get_pool().await.expect("Failed to create pool");
+    let users = create_users(&pool, 1, None).await;
 
-    let mut u = User::default();
-    let email = SafeEmail().fake::<String>();
-    u.email = email.clone();
-    let u = u.save(&pool).await.unwrap();
+    let u = User::try_by_email(&pool, &users[0].email).await.unwrap();
+    assert_eq!(u.unwrap().id, users[0].id);
+    assert!(
+        User::try_by_email(&pool, "missing")
+            .await
+            .unwrap()
+            .is_none()
+    );
+    assert!(
+        User::try_by_id(&pool, &Uuid::new_v4())
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
 
-    let res = User::by_id(&pool, &u.id).await;
-    assert_eq!(res.is_err(), false);
+#[tokio::test]
+async fn test_exists_by_field() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let users = create_users(&pool, 1, None).await;
 
-    let u = res.unwrap();
+    assert!(User::exists_by_email(&pool, &users[0].email).await.unwrap());
+    assert!(!User::exists_by_email(&pool, "missing").await.unwrap());
+    assert!(User::exists_by_id(&pool, &users[0].id).await.unwrap());
+
+    assert!(
+        User::select()
+            .where_email(Where::Eq, users[0].email.as_str())
+            .where_id(Where::NotEq, &Uuid::nil())
+            .exists(&pool)
+            .await
+            .unwrap()
+    );
+    assert!(
+        !User::select()
+            .where_email(Where::Eq, "missing")
+            .exists(&pool)
+            .await
+            .unwrap()
+    );
+}
+
+#[tokio::test]
+async fn test_where_string_field_accepts_str_string_and_cow() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let users = create_users(&pool, 1, None).await;
+    let email: &str = &users[0].email;
+
+    let by_str = User::select()
+        .where_email(Where::Eq, email)
+        .build(&pool)
+        .await
+        .unwrap();
+    let by_string = User::select()
+        .where_email(Where::Eq, email.to_string())
+        .build(&pool)
+        .await
+        .unwrap();
+    let by_string_ref = User::select()
+        .where_email(Where::Eq, &users[0].email)
+        .build(&pool)
+        .await
+        .unwrap();
+    let by_cow = User::select()
+        .where_email(Where::Eq, std::borrow::Cow::Borrowed(email))
+        .build(&pool)
+        .await
+        .unwrap();
+    for res in [by_str, by_string, by_string_ref, by_cow] {
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].id, users[0].id);
+    }
+
+    let res = User::select()
+        .where_between_email(email.to_string(), email)
+        .build(&pool)
+        .await
+        .unwrap();
+    assert_eq!(res.len(), 1);
+}
+
+#[tokio::test]
+async fn test_exists_by_pk() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let users = create_users(&pool, 1, None).await;
+
+    assert!(User::exists_by_pk(&pool, &users[0].id).await.unwrap());
+    assert!(!User::exists_by_pk(&pool, &Uuid::new_v4()).await.unwrap());
+
+    let role = UserRole {
+        user_id: "user_exists".to_string(),
+        role_id: "admin".to_string(),
+        assigned_at: "2024-01-01".to_string(),
+    };
+    role.save(&pool).await.unwrap();
+    assert!(
+        UserRole::exists_by_pk(&pool, "user_exists", "admin")
+            .await
+            .unwrap()
+    );
+    assert!(
+        !UserRole::exists_by_pk(&pool, "user_exists", "reader")
+            .await
+            .unwrap()
+    );
+}
+
+#[tokio::test]
+async fn test_arc_str_pk() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let name: std::sync::Arc<str> = "shared".into();
+    let tag = SharedTag { name: name.clone() };
+    let saved = tag.save(&pool).await.unwrap();
+    assert_eq!(saved.name, name);
+
+    let found = SharedTag::by_name(&pool, &name).await.unwrap();
+    assert_eq!(found.name, name);
+    let found = SharedTag::with_name_in(&pool, &["shared", "missing"])
+        .await
+        .unwrap();
+    assert_eq!(found.len(), 1);
+    assert!(SharedTag::exists_by_pk(&pool, "shared").await.unwrap());
+
+    saved.delete(&pool).await.unwrap();
+    assert!(!SharedTag::exists_by_pk(&pool, &name).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_non_persistent_query() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let _ = create_alt_users(&pool, 5).await;
+
+    let res = AltUser::select()
+        .where_count(Where::GreaterOrEqualTo, 3)
+        .persistent(false)
+        .build(&pool)
+        .await
+        .unwrap();
+    assert_eq!(res.len(), 2);
+
+    let max: Option<i32> = AltUser::select()
+        .persistent(false)
+        .scalar("MAX(count)", &pool)
+        .await
+        .unwrap();
+    assert_eq!(max, Some(4));
+}
+
+#[tokio::test]
+async fn test_max_and_min_pk() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    assert_eq!(AltUser::max_pk(&pool).await.unwrap(), None);
+    assert_eq!(AltUser::min_pk(&pool).await.unwrap(), None);
+
+    let users = create_alt_users(&pool, 3).await;
+    assert_eq!(
+        AltUser::max_pk(&pool).await.unwrap(),
+        Some(users.last().unwrap().id)
+    );
+    assert_eq!(AltUser::min_pk(&pool).await.unwrap(), Some(users[0].id));
+}
+
+#[tokio::test]
+async fn test_transient_field_is_initialized_by_default_expression() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let tag = LabeledTag {
+        name: "transient".to_string(),
+        label: Label("set in memory".to_string()),
+    };
+    let saved = tag.save(&pool).await.unwrap();
+    assert_eq!(saved.label, Label("unlabeled".to_string()));
+
+    let found = LabeledTag::by_name(&pool, "transient").await.unwrap();
+    assert_eq!(found.name, "transient");
+    assert_eq!(found.label, Label("unlabeled".to_string()));
+}
+
+#[tokio::test]
+async fn test_pluck_pks_returns_every_key() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let users = create_users(&pool, 4, None).await;
+
+    let mut expected: Vec<Uuid> = users.iter().map(|u| u.id).collect();
+    expected.sort();
+    assert_eq!(User::pluck_pks(&pool).await.unwrap(), expected);
+
+    for role_id in ["admin", "reader"] {
+        let role = UserRole {
+            user_id: "user_pks".to_string(),
+            role_id: role_id.to_string(),
+            assigned_at: "2024-01-01".to_string(),
+        };
+        role.save(&pool).await.unwrap();
+    }
+    assert_eq!(
+        UserRole::pluck_pks(&pool).await.unwrap(),
+        vec![
+            ("user_pks".to_string(), "admin".to_string()),
+            ("user_pks".to_string(), "reader".to_string()),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_user_is_created() {
+    let pool = get_pool().await.expect("Failed to create pool");
+
+    let mut u = User::default();
+    let email = SafeEmail().fake::<String>();
+    u.email = email.clone();
+    let u = u.save(&pool).await.unwrap();
+
+    let res = User::by_id(&pool, &u.id).await;
+    assert_eq!(res.is_err(), false);
+
+    let u = res.unwrap();
     assert_eq!(u.created_at.to_rfc2822() == u.updated_at.to_rfc2822(), true);
 
     let res = User::by_email(&pool, &email).await;
@@ -607,6 +1180,254 @@ async fn test_user_is_updated() {
     assert_eq!(u.created_at.to_rfc3339() != u.updated_at.to_rfc3339(), true);
 }
 
+#[tokio::test]
+async fn test_timestamps_without_new_default_to_now() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let before = chrono::Utc::now() - chrono::Duration::seconds(5);
+
+    let r = Reminder {
+        title: "call back".to_string(),
+        ..Default::default()
+    };
+    let r = r.save(&pool).await.unwrap();
+    assert!(r.created_at > before);
+    assert!(r.updated_at > before);
+}
+
+#[tokio::test]
+async fn test_uuid_pk_without_new_defaults_to_new_v4() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let new_document = || PlainDocument {
+        title: "Hello".to_string(),
+        ..Default::default()
+    };
+    let first = new_document().save(&pool).await.unwrap();
+    let second = new_document().save(&pool).await.unwrap();
+    assert!(!first.id.is_nil());
+    assert!(!second.id.is_nil());
+    assert_ne!(first.id, second.id);
+
+    // A key that is already set is kept, so saving again updates the same row.
+    let mut first = first;
+    first.title = "Updated".to_string();
+    let updated = first.save(&pool).await.unwrap();
+    assert_eq!(updated.id, first.id);
+    assert_eq!(
+        PlainDocument::by_id(&pool, &first.id).await.unwrap().title,
+        "Updated"
+    );
+}
+
+#[tokio::test]
+async fn test_where_is_and_not_filter_bool_fields() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    create_tasks(&pool, 5).await;
+
+    let done = Task::select().where_is_done().build(&pool).await.unwrap();
+    assert_eq!(done.len(), 3);
+    assert!(done.iter().all(|t| t.done));
+
+    let pending = Task::select().where_not_done().build(&pool).await.unwrap();
+    assert_eq!(pending.len(), 2);
+    assert!(pending.iter().all(|t| !t.done));
+}
+
+#[tokio::test]
+async fn test_order_by_mixed_directions() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    for (email, count) in [("a", 1), ("b", 2), ("c", 1), ("d", 2)] {
+        let u = AltUser {
+            email: format!("{email}@order.lorm.test"),
+            count: Some(count),
+            ..Default::default()
+        };
+        u.save(&pool).await.unwrap();
+    }
+
+    let mut sql = String::new();
+    let res = AltUser::select()
+        .order_by_count(OrderBy::Desc)
+        .order_by_email(OrderBy::Asc)
+        .tap_sql(|s| sql = s.to_string())
+        .build(&pool)
+        .await
+        .unwrap();
+    let emails: Vec<&str> = res.iter().map(|u| &u.email[..1]).collect();
+    assert_eq!(emails, vec!["b", "d", "a", "c"]);
+    assert!(sql.ends_with("ORDER BY count DESC, e_mail ASC"), "{sql}");
+
+    // The first column keeps its own direction, it does not default to the one of the last.
+    let mut sql = String::new();
+    let res = AltUser::select()
+        .order_by_count(OrderBy::Asc)
+        .order_by_email(OrderBy::Desc)
+        .tap_sql(|s| sql = s.to_string())
+        .build(&pool)
+        .await
+        .unwrap();
+    let emails: Vec<&str> = res.iter().map(|u| &u.email[..1]).collect();
+    assert_eq!(emails, vec!["c", "a", "d", "b"]);
+    assert!(sql.ends_with("ORDER BY count ASC, e_mail DESC"), "{sql}");
+}
+
+#[cfg(feature = "postgres")]
+#[tokio::test]
+async fn test_truncate_cascade_empties_dependent_tables() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let authors = create_users(&pool, 2, None).await;
+    for author in &authors {
+        let p = Post {
+            title: "Truncated".to_string(),
+            user_id: author.id,
+            ..Default::default()
+        };
+        p.save(&pool).await.unwrap();
+    }
+
+    User::truncate_cascade(&pool).await.unwrap();
+    assert!(User::pluck_pks(&pool).await.unwrap().is_empty());
+    assert!(Post::pluck_pks(&pool).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_order_by_nulls_placement() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    for (email, count) in [("a", Some(1)), ("b", None), ("c", Some(2))] {
+        let u = AltUser {
+            email: format!("{email}@nulls.lorm.test"),
+            count,
+            ..Default::default()
+        };
+        u.save(&pool).await.unwrap();
+    }
+
+    let mut sql = String::new();
+    let res = AltUser::select()
+        .order_by_count_nulls(OrderBy::Asc, Nulls::Last)
+        .tap_sql(|s| sql = s.to_string())
+        .build(&pool)
+        .await
+        .unwrap();
+    let counts: Vec<Option<i32>> = res.iter().map(|u| u.count).collect();
+    assert_eq!(counts, vec![Some(1), Some(2), None]);
+    #[cfg(not(feature = "mysql"))]
+    assert!(sql.ends_with("ORDER BY count ASC NULLS LAST"), "{sql}");
+    #[cfg(feature = "mysql")]
+    assert!(
+        sql.ends_with("ORDER BY (count IS NULL) ASC, count ASC"),
+        "{sql}"
+    );
+
+    let mut sql = String::new();
+    let res = AltUser::select()
+        .order_by_count_nulls(OrderBy::Desc, Nulls::First)
+        .tap_sql(|s| sql = s.to_string())
+        .build(&pool)
+        .await
+        .unwrap();
+    let counts: Vec<Option<i32>> = res.iter().map(|u| u.count).collect();
+    assert_eq!(counts, vec![None, Some(2), Some(1)]);
+    #[cfg(not(feature = "mysql"))]
+    assert!(sql.ends_with("ORDER BY count DESC NULLS FIRST"), "{sql}");
+    #[cfg(feature = "mysql")]
+    assert!(
+        sql.ends_with("ORDER BY (count IS NULL) DESC, count DESC"),
+        "{sql}"
+    );
+}
+
+#[tokio::test]
+async fn test_count_grouped_by_field() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    create_tasks(&pool, 5).await;
+
+    let counts = Task::count_grouped_by_done(&pool).await.unwrap();
+    assert_eq!(counts, vec![(false, 2), (true, 3)]);
+
+    create_alt_users(&pool, 2).await;
+    let counts = AltUser::count_grouped_by_count(&pool).await.unwrap();
+    assert_eq!(counts, vec![(Some(0), 1), (Some(1), 1)]);
+}
+
+#[tokio::test]
+async fn test_delete_by_field() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    create_alt_users(&pool, 43).await;
+    for i in 0..2 {
+        let u = AltUser {
+            email: format!("delete-by-{i}@example.com"),
+            count: Some(42),
+            ..Default::default()
+        };
+        u.save(&pool).await.unwrap();
+    }
+
+    let deleted = AltUser::delete_by_count(&pool, 42).await.unwrap();
+    assert_eq!(deleted, 3);
+    assert!(AltUser::with_count(&pool, 42).await.unwrap().is_empty());
+    assert_eq!(AltUser::with_count(&pool, 41).await.unwrap().len(), 1);
+
+    let deleted = AltUser::delete_by_count(&pool, 42).await.unwrap();
+    assert_eq!(deleted, 0);
+}
+
+#[tokio::test]
+async fn test_join_fetches_posts_with_their_author() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let authors = create_users(&pool, 2, None).await;
+    for (i, author) in authors.iter().enumerate() {
+        for j in 0..=i {
+            let p = Post {
+                title: format!("Post {i}-{j}"),
+                published: true,
+                user_id: author.id,
+                ..Default::default()
+            };
+            p.save(&pool).await.unwrap();
+        }
+    }
+
+    let res: Vec<(Post, User)> = Post::select()
+        .where_id(Where::NotEq, &Uuid::nil())
+        .join_user()
+        .build_joined(&pool)
+        .await
+        .unwrap();
+    assert_eq!(res.len(), 3);
+    for (post, author) in &res {
+        assert_eq!(post.user_id, author.id);
+        let expected = authors.iter().find(|a| a.id == author.id).unwrap();
+        assert_eq!(author.email, expected.email);
+        assert_ne!(post.id, author.id);
+    }
+    let mut titles: Vec<&str> = res.iter().map(|(p, _)| p.title.as_str()).collect();
+    titles.sort();
+    assert_eq!(titles, vec!["Post 0-0", "Post 1-0", "Post 1-1"]);
+}
+
+#[tokio::test]
+async fn test_save_and_reload_reads_back_on_another_pool() {
+    let primary = get_pool().await.expect("Failed to create pool");
+    // A second pool on the same database stands for a replica.
+    let replica = Pool::connect_with(primary.connect_options().as_ref().clone())
+        .await
+        .unwrap();
+
+    let u = User {
+        email: SafeEmail().fake::<String>(),
+        ..Default::default()
+    };
+    let saved = u.save_and_reload(&primary, &replica).await.unwrap();
+    assert_eq!(saved.email, u.email);
+    assert!(!saved.id.is_nil());
+
+    let mut saved = saved;
+    saved.email = SafeEmail().fake::<String>();
+    let reloaded = saved.save_and_reload(&primary, &primary).await.unwrap();
+    assert_eq!(reloaded.id, saved.id);
+    assert_eq!(reloaded.email, saved.email);
+}
+
 #[tokio::test]
 async fn test_user_is_deleted() {
     let pool = get_pool().await.expect("Failed to create pool");
@@ -620,160 +1441,2461 @@ async fn test_user_is_deleted() {
 
     u.delete(&pool).await.unwrap();
     let res = User::by_id(&pool, &u.id).await;
-    assert_eq!(res.is_err(), true);
+    assert!(matches!(res, Err(lorm::errors::Error::NotFound)));
 }
 
 #[tokio::test]
-async fn test_user_are_listed() {
+async fn test_delete_where_removes_matching_rows() {
     let pool = get_pool().await.expect("Failed to create pool");
-    let _ = create_users(&pool, 10, None).await;
+    let _ = create_alt_users(&pool, 10).await;
 
-    let res = User::select().limit(2).build(&pool).await.unwrap();
-    assert_eq!(res.is_empty(), false);
-    assert_eq!(res.len(), 2);
+    let deleted = AltUser::delete_where()
+        .where_count(Where::LesserThan, 3)
+        .execute(&pool)
+        .await
+        .unwrap();
+    assert_eq!(deleted, 3);
+    let remaining = AltUser::select().build(&pool).await.unwrap();
+    assert_eq!(remaining.len(), 7);
+    assert!(remaining.iter().all(|u| u.count >= Some(3)));
+
+    let res = AltUser::delete_where().execute(&pool).await;
+    assert!(matches!(
+        res,
+        Err(lorm::errors::Error::QueryPreparationError(_))
+    ));
+
+    let deleted = AltUser::delete_where().all().execute(&pool).await.unwrap();
+    assert_eq!(deleted, 7);
+}
+
+#[tokio::test]
+async fn test_count_distinct_counts_combinations() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let _ = create_alt_users(&pool, 10).await;
+    let _ = create_alt_users(&pool, 11).await;
+
+    let count = AltUser::select()
+        .count_distinct(&["count"], &pool)
+        .await
+        .unwrap();
+    assert_eq!(count, 11);
+
+    let count = AltUser::select()
+        .count_distinct(&["e_mail", "count"], &pool)
+        .await
+        .unwrap();
+    assert_eq!(count, 21);
+
+    let count = AltUser::select()
+        .where_count(Where::LesserThan, 5)
+        .count_distinct(&["count"], &pool)
+        .await
+        .unwrap();
+    assert_eq!(count, 5);
+
+    let res = AltUser::select().count_distinct(&[], &pool).await;
+    assert!(matches!(
+        res,
+        Err(lorm::errors::Error::QueryPreparationError(_))
+    ));
+}
+
+#[test]
+fn test_sql_renders_without_a_connection() {
+    let query = AltUser::select()
+        .where_count(Where::GreaterThan, 2)
+        .group_by_email();
+    let sql = query.sql();
+    assert!(sql.starts_with("SELECT "), "{sql}");
+    #[cfg(feature = "postgres")]
+    assert!(sql.ends_with("WHERE count > $1 GROUP BY e_mail"), "{sql}");
+    #[cfg(not(feature = "postgres"))]
+    assert!(sql.ends_with("WHERE count > ? GROUP BY e_mail"), "{sql}");
+
+    // into_sql() completes the GROUP BY as build() does.
+    let full = query.into_sql().unwrap();
+    assert!(full.starts_with(&sql), "{full}");
+    assert!(full.contains("GROUP BY e_mail, id"), "{full}");
+}
+
+#[test]
+fn test_sql_and_args_records_bound_values() {
+    let cte = AltUser::select().where_count(Where::LesserThan, 9);
+    let query = AltUser::select()
+        .with_cte("small", cte)
+        .where_count(Where::GreaterThan, 2)
+        .where_email_with(Where::Like, |_| "%@args.lorm.test".to_string())
+        .where_in_count(&[4, 5])
+        .limit(10);
+    let (sql, args) = query.sql_and_args();
+    assert_eq!(sql, query.sql());
+    assert_eq!(args, vec!["9", "2", "\"%@args.lorm.test\"", "4", "5", "10"]);
+}
+
+#[tokio::test]
+async fn test_exists_checks_before_a_conditional_delete() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let _ = create_alt_users(&pool, 5).await;
+
+    let query = AltUser::delete_where().where_count(Where::GreaterThan, 10);
+    assert!(!query.exists(&pool).await.unwrap());
+
+    let query = AltUser::delete_where().where_count(Where::GreaterThan, 2);
+    assert!(query.exists(&pool).await.unwrap());
+    assert_eq!(query.execute(&pool).await.unwrap(), 2);
+
+    let query = AltUser::update_where()
+        .set_count(Some(42))
+        .where_count(Where::Eq, 1);
+    assert!(query.exists(&pool).await.unwrap());
+
+    assert!(
+        AltUser::select()
+            .where_count(Where::LesserThan, 3)
+            .exists(&pool)
+            .await
+            .unwrap()
+    );
+    assert!(
+        !AltUser::select()
+            .where_count(Where::GreaterThan, 2)
+            .exists(&pool)
+            .await
+            .unwrap()
+    );
+}
+
+#[tokio::test]
+async fn test_update_where_updates_matching_rows() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let users = create_alt_users(&pool, 10).await;
+
+    // Needed for the updated_at bump assertion.
+    let _ = sleep_until(Instant::now().add(Duration::from_secs(1))).await;
+
+    let updated = AltUser::update_where()
+        .set_count(Some(100))
+        .where_count(Where::LesserThan, 3)
+        .execute(&pool)
+        .await
+        .unwrap();
+    assert_eq!(updated, 3);
+    let res = AltUser::with_count(&pool, 100).await.unwrap();
+    assert_eq!(res.len(), 3);
+    for u in &res {
+        let before = users.iter().find(|b| b.id == u.id).unwrap();
+        assert!(u.updated_at > before.updated_at);
+    }
+
+    let updated = AltUser::update_where()
+        .set_count(None)
+        .set_email("renamed@example.com")
+        .where_count(Where::Eq, 9)
+        .execute(&pool)
+        .await
+        .unwrap();
+    assert_eq!(updated, 1);
+    let u = AltUser::by_id(&pool, users[9].id).await.unwrap();
+    assert_eq!(u.count, None);
+    assert_eq!(u.email, "renamed@example.com");
+
+    let res = AltUser::update_where()
+        .set_count(Some(0))
+        .execute(&pool)
+        .await;
+    assert!(matches!(
+        res,
+        Err(lorm::errors::Error::QueryPreparationError(_))
+    ));
+    let res = AltUser::update_where()
+        .where_count(Where::Eq, 5)
+        .execute(&pool)
+        .await;
+    assert!(matches!(
+        res,
+        Err(lorm::errors::Error::QueryPreparationError(_))
+    ));
+
+    let updated = AltUser::update_where()
+        .set_count(Some(7))
+        .all()
+        .execute(&pool)
+        .await
+        .unwrap();
+    assert_eq!(updated, 10);
+}
+
+#[tokio::test]
+async fn test_update_where_sets_one_column_by_pk() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let users = create_alt_users(&pool, 3).await;
+
+    let updated = AltUser::update_where()
+        .set_email("x@example.com")
+        .where_id(Where::Eq, users[1].id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    assert_eq!(updated, 1);
+
+    let u = AltUser::by_id(&pool, users[1].id).await.unwrap();
+    assert_eq!(u.email, "x@example.com");
+    assert_eq!(u.count, users[1].count);
+    for other in [&users[0], &users[2]] {
+        let u = AltUser::by_id(&pool, other.id).await.unwrap();
+        assert_eq!(u.email, other.email);
+    }
+}
+
+#[tokio::test]
+async fn test_select_builder_clones_into_variants() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let _ = create_alt_users(&pool, 10).await;
+
+    let base = AltUser::select()
+        .where_count(Where::GreaterThan, 2)
+        .order_by_count(OrderBy::Asc);
+    let first = base.clone().limit(1).build(&pool).await.unwrap();
+    let page = base.clone().limit(3).offset(1).build(&pool).await.unwrap();
+    let all = base.build(&pool).await.unwrap();
+
+    assert_eq!(first.len(), 1);
+    assert_eq!(first[0].count, Some(3));
+    assert_eq!(
+        page.iter().map(|u| u.count).collect::<Vec<_>>(),
+        vec![Some(4), Some(5), Some(6)]
+    );
+    assert_eq!(all.len(), 7);
+}
+
+#[tokio::test]
+async fn test_user_are_listed() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let _ = create_users(&pool, 10, None).await;
+
+    let res = User::select().limit(2).build(&pool).await.unwrap();
+    assert_eq!(res.is_empty(), false);
+    assert_eq!(res.len(), 2);
+}
+
+#[tokio::test]
+async fn test_delete_many_by_primary_keys() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let users = create_users(&pool, 10, None).await;
+
+    let ids = users[..3].iter().map(|u| u.id).collect::<Vec<_>>();
+    let deleted = User::delete_many(&pool, &ids).await.unwrap();
+    assert_eq!(deleted, 3);
+    let res = User::select().build(&pool).await.unwrap();
+    assert_eq!(res.len(), 7);
+    assert!(res.iter().all(|u| !ids.contains(&u.id)));
+
+    assert_eq!(User::delete_many(&pool, &ids).await.unwrap(), 0);
+    assert_eq!(User::delete_many(&pool, &[] as &[Uuid]).await.unwrap(), 0);
+
+    let alt_users = create_alt_users(&pool, 3).await;
+    let deleted = AltUser::delete_many(&pool, &[alt_users[0].id, alt_users[2].id])
+        .await
+        .unwrap();
+    assert_eq!(deleted, 2);
+    assert_eq!(AltUser::select().build(&pool).await.unwrap().len(), 1);
+
+    // The first and last keys, in different chunks, have a row.
+    let alt_users = create_alt_users(&pool, 2).await;
+    let mut ids: Vec<i32> = (0..MAX_BIND_PARAMETERS as i32 + 10)
+        .map(|i| 1_000_000 + i)
+        .collect();
+    ids[0] = alt_users[0].id;
+    *ids.last_mut().unwrap() = alt_users[1].id;
+    let deleted = AltUser::delete_many(&pool, &ids).await.unwrap();
+    assert_eq!(deleted, 2);
+    assert_eq!(AltUser::select().build(&pool).await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_with_is_working() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let _ = create_alt_users(&pool, 10).await;
+    let _ = create_alt_users(&pool, 11).await;
+    let res = AltUser::with_count(&pool, 5).await.unwrap();
+    assert_eq!(res.len(), 2);
+    let res = AltUser::with_count(&pool, 10).await.unwrap();
+    assert_eq!(res.len(), 1);
+    let res = AltUser::with_count(&pool, 11).await.unwrap();
+    assert_eq!(res.len(), 0);
+}
+
+#[tokio::test]
+async fn test_with_in_fetches_every_matching_value() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let _ = create_alt_users(&pool, 10).await;
+    let _ = create_alt_users(&pool, 11).await;
+    let res = AltUser::with_count_in(&pool, &[5, 10, 42]).await.unwrap();
+    assert_eq!(res.len(), 3);
+    let res = AltUser::with_count_in(&pool, &[]).await.unwrap();
+    assert!(res.is_empty());
+
+    let users = create_users(&pool, 3, None).await;
+    let emails = [users[0].email.as_str(), users[2].email.as_str()];
+    let res = User::with_email_in(&pool, &emails).await.unwrap();
+    let mut ids: Vec<Uuid> = res.iter().map(|u| u.id).collect();
+    ids.sort();
+    let mut expected = vec![users[0].id, users[2].id];
+    expected.sort();
+    assert_eq!(ids, expected);
+}
+
+#[tokio::test]
+async fn test_with_in_splits_values_over_the_bind_limit() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let _ = create_alt_users(&pool, 10).await;
+    let _ = create_alt_users(&pool, 11).await;
+
+    // 5 and 10 land in different chunks, the other values match nothing.
+    let mut counts: Vec<i32> = (1000..1000 + MAX_BIND_PARAMETERS as i32 + 10).collect();
+    counts[0] = 5;
+    *counts.last_mut().unwrap() = 10;
+    let res = AltUser::with_count_in(&pool, &counts).await.unwrap();
+    assert_eq!(res.len(), 3);
+    let count = AltUser::count_by_count_in(&pool, &counts).await.unwrap();
+    assert_eq!(count, 3);
+
+    let mut tx = pool.begin().await.unwrap();
+    let count = AltUser::count_by_count_in(&mut *tx, &counts).await.unwrap();
+    assert_eq!(count, 3);
+    tx.rollback().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_fetch_rows_returns_untyped_rows() {
+    use sqlx::Row;
+
+    let pool = get_pool().await.expect("Failed to create pool");
+    let _ = create_alt_users(&pool, 3).await;
+
+    let rows = AltUser::select()
+        .where_count(Where::GreaterThan, 0)
+        .order_by_count(OrderBy::Asc)
+        .fetch_rows(&pool)
+        .await
+        .unwrap();
+    let counts: Vec<Option<i32>> = rows
+        .iter()
+        .map(|row| row.try_get::<Option<i32>, _>("count").unwrap())
+        .collect();
+    assert_eq!(counts, vec![Some(1), Some(2)]);
+    let email: String = rows[0].try_get("e_mail").unwrap();
+    assert!(email.starts_with("1-"));
+}
+
+#[tokio::test]
+async fn test_where_null_is_working() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let _ = create_alt_users(&pool, 3).await;
+    let u = AltUser {
+        email: "no-count@example.com".to_string(),
+        ..Default::default()
+    };
+    let u = u.save(&pool).await.unwrap();
+
+    let res = AltUser::select()
+        .where_null_count()
+        .build(&pool)
+        .await
+        .unwrap();
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0].id, u.id);
+
+    let res = AltUser::select()
+        .where_not_null_count()
+        .where_count(Where::GreaterThan, 0)
+        .build(&pool)
+        .await
+        .unwrap();
+    assert_eq!(res.len(), 2);
+}
+
+#[tokio::test]
+async fn test_where_in_binds_every_value() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let _ = create_alt_users(&pool, 10).await;
+
+    let mut sql = String::new();
+    let res = AltUser::select()
+        .where_in_count(&[2, 5, 42])
+        .tap_sql(|s| sql = s.to_string())
+        .build(&pool)
+        .await
+        .unwrap();
+    assert_eq!(res.len(), 2);
+    #[cfg(feature = "postgres")]
+    assert!(sql.ends_with("count IN ($1, $2, $3)"), "{sql}");
+    #[cfg(not(feature = "postgres"))]
+    assert!(sql.ends_with("count IN (?, ?, ?)"), "{sql}");
+
+    let res = AltUser::select()
+        .where_not_in_count(&[2, 5, 42])
+        .build(&pool)
+        .await
+        .unwrap();
+    assert_eq!(res.len(), 8);
+
+    let mut sql = String::new();
+    let res = AltUser::select()
+        .where_in_count(&[])
+        .tap_sql(|s| sql = s.to_string())
+        .build(&pool)
+        .await
+        .unwrap();
+    assert!(res.is_empty());
+    assert!(!sql.contains('?') && !sql.contains('$'), "{sql}");
+
+    let res = AltUser::select()
+        .where_not_in_count(&[])
+        .build(&pool)
+        .await
+        .unwrap();
+    assert_eq!(res.len(), 10);
+
+    let users = create_users(&pool, 3, None).await;
+    let ids = [users[0].id, users[1].id];
+    let res = User::select().where_in_id(&ids).build(&pool).await.unwrap();
+    assert_eq!(res.len(), 2);
+}
+
+#[cfg(feature = "postgres")]
+#[tokio::test]
+async fn test_where_array_comparisons() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let users = create_alt_users(&pool, 10).await;
+
+    let mut sql = String::new();
+    let res = AltUser::select()
+        .where_gt_all_count(&[3, 6, 1])
+        .tap_sql(|s| sql = s.to_string())
+        .build(&pool)
+        .await
+        .unwrap();
+    assert_eq!(res.len(), 3);
+    assert!(res.iter().all(|u| u.count > Some(6)));
+    assert!(sql.ends_with("count > ALL($1)"), "{sql}");
+
+    let ids = [users[1].id, users[4].id, -1];
+    let res = AltUser::select()
+        .where_eq_any_id(&ids)
+        .build(&pool)
+        .await
+        .unwrap();
+    assert_eq!(res.len(), 2);
+
+    let res = AltUser::select()
+        .where_eq_any_id(&[])
+        .build(&pool)
+        .await
+        .unwrap();
+    assert!(res.is_empty());
+}
+
+#[tokio::test]
+async fn test_where_in_ref_binds_borrowed_values() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let users = create_users(&pool, 3, None).await;
+    let emails = [users[0].email.as_str(), users[2].email.as_str()];
+    let res = User::select()
+        .where_in_email_ref(&emails)
+        .build(&pool)
+        .await
+        .unwrap();
+    let mut ids: Vec<Uuid> = res.iter().map(|u| u.id).collect();
+    ids.sort();
+    let mut expected = vec![users[0].id, users[2].id];
+    expected.sort();
+    assert_eq!(ids, expected);
+
+    let res = User::select()
+        .where_in_email_ref(&[])
+        .build(&pool)
+        .await
+        .unwrap();
+    assert!(res.is_empty());
+}
+
+#[tokio::test]
+async fn test_select_columns_match_by_id() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let users = create_users(&pool, 1, None).await;
+    let expected = User::by_id(&pool, &users[0].id).await.unwrap();
+
+    #[cfg(feature = "postgres")]
+    let placeholder = "$1";
+    #[cfg(not(feature = "postgres"))]
+    let placeholder = "?";
+    let sql = format!(
+        "SELECT {} FROM users WHERE id = {placeholder}",
+        User::SELECT_COLUMNS
+    );
+    let u: User = sqlx::query_as(&sql)
+        .bind(expected.id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(u.id, expected.id);
+    assert_eq!(u.email, expected.email);
+    assert_eq!(u.created_at, expected.created_at);
+    assert_eq!(
+        User::SELECT_COLUMNS,
+        "id, email, count, created_at, updated_at"
+    );
+    assert_eq!(User::table_columns(), User::SELECT_COLUMNS);
+}
+
+#[test]
+fn test_insert_and_update_columns_exclude_readonly_fields() {
+    // The readonly AltUser::id, AltUser::created_at and User::count are left to the database.
+    assert_eq!(
+        AltUser::columns_for_insert(),
+        &["e_mail", "count", "updated_at"]
+    );
+    assert_eq!(AltUser::UPDATE_COLUMNS, AltUser::INSERT_COLUMNS);
+    assert_eq!(
+        User::INSERT_COLUMNS,
+        &["id", "email", "created_at", "updated_at"]
+    );
+    assert_eq!(
+        User::columns_for_update(),
+        &["email", "created_at", "updated_at"]
+    );
+    // The version is inserted but incremented in place rather than bound on update.
+    assert_eq!(Page::INSERT_COLUMNS, &["id", "body", "version"]);
+    assert_eq!(Page::UPDATE_COLUMNS, &["body"]);
+}
+
+#[tokio::test]
+async fn test_count_by_in_counts_every_matching_value() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let _ = create_alt_users(&pool, 10).await;
+    let _ = create_alt_users(&pool, 11).await;
+    let count = AltUser::count_by_count_in(&pool, &[2, 5, 10, 42])
+        .await
+        .unwrap();
+    assert_eq!(count, 5);
+    let count = AltUser::count_by_count_in(&pool, &[]).await.unwrap();
+    assert_eq!(count, 0);
+}
+
+#[tokio::test]
+async fn test_by_unique_rejects_multiple_matches() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let _ = create_alt_users(&pool, 10).await;
+    let _ = create_alt_users(&pool, 11).await;
+
+    let res = AltUser::by_count_unique(&pool, 5).await;
+    assert!(matches!(
+        res,
+        Err(lorm::errors::Error::MultipleRowsError(_))
+    ));
+
+    let u = AltUser::by_count_unique(&pool, 10).await.unwrap();
+    assert_eq!(u.count, Some(10));
+
+    let res = AltUser::by_count_unique(&pool, 42).await;
+    assert!(matches!(res, Err(lorm::errors::Error::NotFound)));
+}
+
+#[tokio::test]
+async fn test_offset_is_working() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let users = create_users(&pool, 10, None).await;
+
+    let res = User::select()
+        .order_by_email(OrderBy::Desc)
+        .limit(2)
+        .build(&pool)
+        .await
+        .unwrap();
+    assert_eq!(res.is_empty(), false);
+    assert_eq!(res.len(), 2);
+    let u = res.last().unwrap();
+    assert_eq!(u.email, users.get(8).unwrap().email);
+
+    let res = User::select()
+        .order_by_email(OrderBy::Desc)
+        .limit(2)
+        .offset(2)
+        .build(&pool)
+        .await
+        .unwrap();
+    assert_eq!(res.is_empty(), false);
+    assert_eq!(res.len(), 2);
+    let u = res.last().unwrap();
+    assert_eq!(u.email, users.get(6).unwrap().email);
+}
+
+#[tokio::test]
+async fn test_group_by_is_working() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let _ = create_users(&pool, 11, None).await;
+    let other_users = create_users(&pool, 11, None).await;
+
+    let res = User::select()
+        .group_by_email()
+        .order_by_created_at(OrderBy::Desc)
+        .limit(2)
+        .build(&pool)
+        .await
+        .unwrap();
+    assert_eq!(res.is_empty(), false);
+    assert_eq!(res.len(), 2);
+    let u = res.first().unwrap();
+    assert_eq!(u.email, other_users.get(10).unwrap().email);
+}
+
+#[tokio::test]
+async fn test_automatic_pk_and_ts_insertion_update_is_working() {
+    let pool = get_pool().await.expect("Failed to create pool");
+
+    let mut u = AltUser::default();
+    u.email = SafeEmail().fake::<String>();
+    let u = u.save(&pool).await.unwrap();
+    let res = AltUser::by_id(&pool, u.id).await;
+    assert_eq!(res.is_err(), false);
+}
+
+#[tokio::test]
+async fn test_where_is_working() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let users = create_alt_users(&pool, 10).await;
+    let u = users.get(2).unwrap();
+
+    let res = AltUser::select()
+        .where_id(Where::Eq, u.id)
+        .build(&pool)
+        .await
+        .unwrap();
+    assert_eq!(res.len(), 1);
+}
+
+#[tokio::test]
+async fn test_between_is_working() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let _ = create_alt_users(&pool, 10).await;
+
+    let res = AltUser::select()
+        .where_between_count(2, 4)
+        .build(&pool)
+        .await
+        .unwrap();
+    assert_eq!(res.len(), 3);
+}
+
+#[tokio::test]
+async fn test_first_and_one() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let _ = create_alt_users(&pool, 5).await;
+
+    let u = AltUser::select()
+        .where_count(Where::GreaterThan, 1)
+        .order_by_count(OrderBy::Desc)
+        .first(&pool)
+        .await
+        .unwrap();
+    assert_eq!(u.unwrap().count, Some(4));
+
+    let u = AltUser::select()
+        .where_count(Where::GreaterThan, 10)
+        .first(&pool)
+        .await
+        .unwrap();
+    assert!(u.is_none());
+
+    let u = AltUser::select()
+        .where_count(Where::Eq, 3)
+        .one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(u.count, Some(3));
+
+    let res = AltUser::select()
+        .where_count(Where::GreaterThan, 1)
+        .one(&pool)
+        .await;
+    assert!(matches!(
+        res,
+        Err(lorm::errors::Error::MultipleRowsError(_))
+    ));
+
+    let res = AltUser::select()
+        .where_count(Where::GreaterThan, 10)
+        .one(&pool)
+        .await;
+    assert!(matches!(res, Err(lorm::errors::Error::NotFound)));
+
+    let res = AltUser::select().limit(3).first(&pool).await;
+    assert!(matches!(
+        res,
+        Err(lorm::errors::Error::QueryPreparationError(_))
+    ));
+
+    let res = AltUser::select()
+        .where_count(Where::Eq, 3)
+        .offset(1)
+        .one(&pool)
+        .await;
+    assert!(matches!(
+        res,
+        Err(lorm::errors::Error::QueryPreparationError(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_between_accepts_reversed_bounds() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let _ = create_alt_users(&pool, 10).await;
+
+    let res = AltUser::select()
+        .where_between_count(4, 2)
+        .order_by_count(OrderBy::Asc)
+        .build(&pool)
+        .await
+        .unwrap();
+    let counts: Vec<_> = res.iter().map(|u| u.count).collect();
+    assert_eq!(counts, vec![Some(2), Some(3), Some(4)]);
+
+    let res = AltUser::select()
+        .where_between_exclusive_count(5, 2)
+        .order_by_count(OrderBy::Asc)
+        .build(&pool)
+        .await
+        .unwrap();
+    let counts: Vec<_> = res.iter().map(|u| u.count).collect();
+    assert_eq!(counts, vec![Some(3), Some(4)]);
+
+    let deleted = AltUser::delete_where()
+        .where_between_count(9, 8)
+        .execute(&pool)
+        .await
+        .unwrap();
+    assert_eq!(deleted, 2);
+}
+
+#[tokio::test]
+async fn test_where_with_computes_bind_at_build_time() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let _ = create_alt_users(&pool, 10).await;
+
+    let steps = [3, 4];
+    let res = AltUser::select()
+        .where_count_with(Where::GreaterOrEqualTo, move |sql| {
+            assert!(sql.ends_with("count >= "), "{sql}");
+            steps.iter().sum()
+        })
+        .order_by_count(OrderBy::Asc)
+        .build(&pool)
+        .await
+        .unwrap();
+    let counts: Vec<_> = res.iter().map(|u| u.count).collect();
+    assert_eq!(counts, vec![Some(7), Some(8), Some(9)]);
+}
+
+#[tokio::test]
+async fn test_distinct_values_honor_filters() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let _ = create_alt_users(&pool, 5).await;
+    let _ = create_alt_users(&pool, 3).await;
+
+    let mut counts = AltUser::select()
+        .where_count(Where::GreaterOrEqualTo, 1)
+        .distinct_values_count(&pool)
+        .await
+        .unwrap();
+    counts.sort();
+    assert_eq!(counts, vec![Some(1), Some(2), Some(3), Some(4)]);
+}
+
+#[cfg(feature = "postgres")]
+#[tokio::test]
+async fn test_where_regex_filters_by_pattern() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    for email in ["alice@example.com", "Bob@example.com", "carol@example.org"] {
+        let u = User {
+            email: email.to_string(),
+            ..Default::default()
+        };
+        u.save(&pool).await.unwrap();
+    }
+
+    let res = User::select()
+        .where_regex_email(r"^[a-z]+@example\.com$")
+        .build(&pool)
+        .await
+        .unwrap();
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0].email, "alice@example.com");
+
+    let res = User::select()
+        .where_regex_i_email(r"^[a-z]+@example\.com$")
+        .build(&pool)
+        .await
+        .unwrap();
+    assert_eq!(res.len(), 2);
+}
+
+#[cfg(feature = "postgres")]
+#[tokio::test]
+async fn test_offset_fetch_standard_syntax() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let _ = create_alt_users(&pool, 10).await;
+
+    let mut sql = String::new();
+    let res = AltUser::select()
+        .order_by_count(OrderBy::Asc)
+        .offset(2)
+        .fetch(3)
+        .tap_sql(|s| sql = s.to_string())
+        .build(&pool)
+        .await
+        .unwrap();
+    assert!(sql.ends_with("OFFSET $1 FETCH NEXT $2 ROWS ONLY"), "{sql}");
+    let counts = res.iter().map(|u| u.count).collect::<Vec<_>>();
+    assert_eq!(counts, vec![Some(2), Some(3), Some(4)]);
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn test_where_regex_is_rejected_on_sqlite() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let res = User::select().where_regex_email("^a").build(&pool).await;
+    assert!(matches!(
+        res,
+        Err(lorm::errors::Error::QueryPreparationError(ref e)) if e.contains("where_regex_email()")
+    ));
+}
+
+#[tokio::test]
+async fn test_by_or_insert_creates_once() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let email = "first@example.com";
+
+    let created = Subscriber::by_email_or_insert(&pool, email, || Subscriber {
+        email: email.to_string(),
+        count: Some(1),
+        ..Default::default()
+    })
+    .await
+    .unwrap();
+    assert_eq!(created.email, email);
+    assert_eq!(created.count, Some(1));
+
+    let found = Subscriber::by_email_or_insert(&pool, email, || {
+        panic!("the row exists, nothing should be built")
+    })
+    .await
+    .unwrap();
+    assert_eq!(found.id, created.id);
+    assert_eq!(found.count, Some(1));
+
+    // The built row must hold the value looked up, nothing is inserted otherwise.
+    let mismatched = Subscriber::by_email_or_insert(&pool, "second@example.com", || Subscriber {
+        email: "third@example.com".to_string(),
+        ..Default::default()
+    })
+    .await;
+    assert!(matches!(
+        mismatched,
+        Err(lorm::errors::Error::MismatchedRow(_))
+    ));
+    let subscribers = Subscriber::select().build(&pool).await.unwrap();
+    assert_eq!(subscribers.len(), 1);
+    assert_eq!(subscribers[0].id, created.id);
+}
+
+#[tokio::test]
+async fn test_by_pk_or_insert_conflicting_on_another_unique_column() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let taken = User {
+        email: "taken@example.com".to_string(),
+        ..Default::default()
+    }
+    .save(&pool)
+    .await
+    .unwrap();
+
+    let id = Uuid::new_v4();
+    let res = User::by_id_or_insert(&pool, &id, || User {
+        id,
+        email: taken.email.clone(),
+        ..Default::default()
+    })
+    .await;
+    assert!(matches!(
+        res,
+        Err(lorm::errors::Error::UniqueViolation { .. })
+    ));
+    let users = User::select().build(&pool).await.unwrap();
+    assert_eq!(users.len(), 1);
+    assert_eq!(users[0].id, taken.id);
+
+    let created = User::by_id_or_insert(&pool, &id, || User {
+        id,
+        email: "free@example.com".to_string(),
+        ..Default::default()
+    })
+    .await
+    .unwrap();
+    assert_eq!(created.id, id);
+    assert_eq!(created.email, "free@example.com");
+}
+
+// The MySQL models use `DateTime<Utc>`, which has a single representation.
+#[cfg(any(feature = "sqlite", feature = "postgres"))]
+#[tokio::test]
+async fn test_where_timestamp_compares_instants_across_offsets() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let users = create_users(&pool, 2, None).await;
+    let created_at = users[0].created_at;
+
+    let utc = created_at.with_timezone(&chrono::Utc);
+    let shifted = created_at.with_timezone(&chrono::FixedOffset::east_opt(2 * 3600).unwrap());
+    for value in [utc.fixed_offset(), shifted] {
+        let res = User::select()
+            .where_created_at(Where::Eq, &value)
+            .build(&pool)
+            .await
+            .unwrap();
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].id, users[0].id);
+    }
+
+    let res = User::select()
+        .where_between_created_at(&shifted, &shifted)
+        .build(&pool)
+        .await
+        .unwrap();
+    assert_eq!(res.len(), 1);
+}
+
+#[tokio::test]
+async fn test_scalar_selects_raw_expression() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let _ = create_alt_users(&pool, 10).await;
+
+    let max: Option<i32> = AltUser::select()
+        .where_count(Where::LesserThan, 6)
+        .scalar("MAX(count)", &pool)
+        .await
+        .unwrap();
+    assert_eq!(max, Some(5));
+
+    let max: Option<i32> = AltUser::select()
+        .where_count(Where::GreaterThan, 100)
+        .scalar("MAX(count)", &pool)
+        .await
+        .unwrap();
+    assert_eq!(max, None);
+}
+
+#[tokio::test]
+async fn test_group_concat_joins_values_per_group() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let mut users = create_alt_users(&pool, 3).await;
+    users.extend(create_alt_users(&pool, 3).await);
+
+    let mut groups = AltUser::select()
+        .group_by_count()
+        .group_concat_email("; ", &pool)
+        .await
+        .unwrap();
+    groups.sort();
+    assert_eq!(groups.len(), 3);
+    for (i, group) in groups.iter().enumerate() {
+        let mut emails = group.split("; ").collect::<Vec<_>>();
+        emails.sort();
+        let mut expected = users
+            .iter()
+            .filter(|u| u.count == Some(i as i32))
+            .map(|u| u.email.as_str())
+            .collect::<Vec<_>>();
+        expected.sort();
+        assert_eq!(emails, expected);
+    }
+
+    let groups = AltUser::select()
+        .where_count(Where::GreaterThan, 100)
+        .group_by_count()
+        .group_concat_email("'", &pool)
+        .await
+        .unwrap();
+    assert!(groups.is_empty());
+}
+
+#[tokio::test]
+async fn test_between_exclusive_excludes_boundaries() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let _ = create_alt_users(&pool, 10).await;
+
+    let res = AltUser::select()
+        .where_between_exclusive_count(2, 5)
+        .order_by_count(OrderBy::Asc)
+        .build(&pool)
+        .await
+        .unwrap();
+    let counts: Vec<_> = res.iter().map(|u| u.count).collect();
+    assert_eq!(counts, vec![Some(3), Some(4)]);
+
+    let res = AltUser::select()
+        .where_between_exclusive_count(2, 3)
+        .build(&pool)
+        .await
+        .unwrap();
+    assert!(res.is_empty());
+}
+
+#[tokio::test]
+async fn test_stream_all_yields_every_row() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    create_users(&pool, 25, None).await;
+
+    let mut stream = std::pin::pin!(User::stream_all(&pool));
+    let mut count = 0;
+    while let Some(u) = stream.try_next().await.unwrap() {
+        assert!(!u.id.is_nil());
+        count += 1;
+    }
+    assert_eq!(count, 25);
+}
+
+#[tokio::test]
+async fn test_fetch_chunks_pages_through_the_table() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    create_users(&pool, 250, None).await;
+
+    let mut chunks = std::pin::pin!(User::select().fetch_chunks(100, &pool));
+    let mut sizes = Vec::new();
+    let mut ids = Vec::new();
+    while let Some(chunk) = chunks.try_next().await.unwrap() {
+        sizes.push(chunk.len());
+        ids.extend(chunk.into_iter().map(|u| u.id));
+    }
+    assert_eq!(sizes, vec![100, 100, 50]);
+    let mut sorted = ids.clone();
+    sorted.sort();
+    sorted.dedup();
+    assert_eq!(sorted, ids);
+
+    let chunks = User::select()
+        .fetch_chunks(0, &pool)
+        .try_collect::<Vec<_>>()
+        .await;
+    assert!(matches!(
+        chunks,
+        Err(lorm::errors::Error::QueryPreparationError(_))
+    ));
+
+    let chunks = User::select()
+        .limit(10)
+        .fetch_chunks(100, &pool)
+        .try_collect::<Vec<_>>()
+        .await;
+    assert!(matches!(
+        chunks,
+        Err(lorm::errors::Error::QueryPreparationError(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_pluck_pairs_returns_tuples() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let users = create_users(&pool, 3, None).await;
+
+    let pairs = User::select()
+        .order_by_email(OrderBy::Asc)
+        .pluck_id_email(&pool)
+        .await
+        .unwrap();
+    let mut expected: Vec<(Uuid, String)> = users.iter().map(|u| (u.id, u.email.clone())).collect();
+    expected.sort_by(|a, b| a.1.cmp(&b.1));
+    assert_eq!(pairs, expected);
+
+    let alt_users = create_alt_users(&pool, 3).await;
+    let pairs = AltUser::select()
+        .where_count(Where::Eq, 1)
+        .pluck_id_count(&pool)
+        .await
+        .unwrap();
+    assert_eq!(pairs, vec![(alt_users[1].id, Some(1))]);
+}
+
+#[tokio::test]
+async fn test_into_query_builder_runs_with_sqlx() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let _ = create_alt_users(&pool, 10).await;
+
+    let mut query = AltUser::select()
+        .where_count(Where::GreaterOrEqualTo, 7)
+        .order_by_count(OrderBy::Asc)
+        .into_query_builder()
+        .unwrap();
+    let res = query
+        .build_query_as::<AltUser>()
+        .persistent(false)
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+    let counts: Vec<_> = res.iter().map(|u| u.count).collect();
+    assert_eq!(counts, vec![Some(7), Some(8), Some(9)]);
+}
+
+#[tokio::test]
+async fn test_tap_sql_captures_the_current_sql() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let _ = create_alt_users(&pool, 10).await;
+
+    let mut captured = String::new();
+    let res = AltUser::select()
+        .where_count(Where::GreaterOrEqualTo, 7)
+        .tap_sql(|sql| captured = sql.to_string())
+        .order_by_count(OrderBy::Asc)
+        .build(&pool)
+        .await
+        .unwrap();
+    assert_eq!(res.len(), 3);
+    assert!(captured.starts_with("SELECT id"));
+    assert!(captured.contains("from alt_users WHERE count >= "));
+    assert!(!captured.contains("ORDER BY"));
+}
+
+#[tokio::test]
+async fn test_table_alias_qualifies_generated_sql() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let _ = create_alt_users(&pool, 10).await;
+
+    let query = AliasedAltUser::select()
+        .where_count(Where::GreaterOrEqualTo, 7)
+        .order_by_count(OrderBy::Asc);
+    let sql = query.sql();
+    assert!(
+        sql.starts_with("SELECT au.id, au.e_mail, au.count "),
+        "{sql}"
+    );
+    assert!(
+        sql.contains(" from alt_users au WHERE au.count >= "),
+        "{sql}"
+    );
+    assert!(sql.ends_with(" ORDER BY au.count ASC"), "{sql}");
+    let counts: Vec<_> = query
+        .build(&pool)
+        .await
+        .unwrap()
+        .iter()
+        .map(|u| u.count)
+        .collect();
+    assert_eq!(counts, vec![Some(7), Some(8), Some(9)]);
+}
+
+#[tokio::test]
+async fn test_like_is_working() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let _ = create_alt_users(&pool, 11).await;
+
+    let res = AltUser::select()
+        .where_email(Where::Like, "1%")
+        .build(&pool)
+        .await
+        .unwrap();
+    assert_eq!(res.len(), 2);
+
+    let res = AltUser::select()
+        .where_email(Where::Like, "%")
+        .build(&pool)
+        .await
+        .unwrap();
+    assert_eq!(res.len(), 11);
+}
+
+#[tokio::test]
+async fn test_where_any_adds_no_condition() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let _ = create_alt_users(&pool, 3).await;
+
+    let mut sql = String::new();
+    let res = AltUser::select()
+        .where_email(Where::Any, "ignored")
+        .tap_sql(|s| sql = s.to_string())
+        .build(&pool)
+        .await
+        .unwrap();
+    assert_eq!(res.len(), 3);
+    assert!(!sql.contains("WHERE"), "{sql}");
+
+    let mut sql = String::new();
+    let res = AltUser::select()
+        .where_count(Where::Any, 0)
+        .where_count(Where::GreaterThan, 0)
+        .where_email_with(Where::Any, |_| "ignored".to_string())
+        .tap_sql(|s| sql = s.to_string())
+        .build(&pool)
+        .await
+        .unwrap();
+    assert_eq!(res.len(), 2);
+    #[cfg(feature = "postgres")]
+    assert!(sql.ends_with("WHERE count > $1"), "{sql}");
+    #[cfg(not(feature = "postgres"))]
+    assert!(sql.ends_with("WHERE count > ?"), "{sql}");
+
+    // The OR before a skipped condition does not carry over to the next one.
+    let mut sql = String::new();
+    let res = AltUser::select()
+        .where_count(Where::GreaterThan, 0)
+        .or()
+        .where_email_with(Where::Any, |_| "ignored".to_string())
+        .where_count(Where::LesserThan, 2)
+        .tap_sql(|s| sql = s.to_string())
+        .build(&pool)
+        .await
+        .unwrap();
+    assert_eq!(res.len(), 1);
+    #[cfg(feature = "postgres")]
+    assert!(sql.ends_with("WHERE count > $1 AND count < $2"), "{sql}");
+    #[cfg(not(feature = "postgres"))]
+    assert!(sql.ends_with("WHERE count > ? AND count < ?"), "{sql}");
+}
+
+#[tokio::test]
+async fn test_where_distinct_from_is_null_safe() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let _ = create_alt_users(&pool, 3).await;
+    let null_count = AltUser {
+        email: "null-count@example.com".to_string(),
+        count: None,
+        ..Default::default()
+    };
+    null_count.save(&pool).await.unwrap();
+
+    let res = AltUser::select()
+        .where_count(Where::NotEq, 1)
+        .build(&pool)
+        .await
+        .unwrap();
+    assert_eq!(res.len(), 2);
+
+    let res = AltUser::select()
+        .where_count(Where::DistinctFrom, 1)
+        .build(&pool)
+        .await
+        .unwrap();
+    assert_eq!(res.len(), 3);
+    assert!(res.iter().any(|u| u.count.is_none()));
+
+    let res = AltUser::select()
+        .where_count(Where::NotDistinctFrom, 1)
+        .build(&pool)
+        .await
+        .unwrap();
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0].count, Some(1));
+}
+
+#[tokio::test]
+async fn test_ilike_binds_the_pattern() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let _ = create_alt_users(&pool, 3).await;
+    let u = AltUser {
+        email: "Mixed.Case@ILike.Lorm.TEST".to_string(),
+        ..Default::default()
+    };
+    let u = u.save(&pool).await.unwrap();
+
+    let mut sql = String::new();
+    let res = AltUser::select()
+        .where_email(Where::ILike, "%@ilike.lorm.test")
+        .tap_sql(|s| sql = s.to_string())
+        .build(&pool)
+        .await
+        .unwrap();
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0].id, u.id);
+    assert!(!sql.contains("ilike.lorm"), "{sql}");
+    #[cfg(feature = "postgres")]
+    assert!(sql.ends_with("e_mail ILIKE $1"), "{sql}");
+    #[cfg(not(feature = "postgres"))]
+    assert!(sql.ends_with("LOWER(e_mail) LIKE LOWER(?)"), "{sql}");
+
+    let mut sql = String::new();
+    let _ = AltUser::select()
+        .where_email(Where::Like, "%@ilike.lorm.test")
+        .tap_sql(|s| sql = s.to_string())
+        .build(&pool)
+        .await
+        .unwrap();
+    assert!(!sql.contains("ilike.lorm"), "{sql}");
+    #[cfg(feature = "postgres")]
+    assert!(sql.ends_with("e_mail LIKE $1"), "{sql}");
+    #[cfg(not(feature = "postgres"))]
+    assert!(sql.ends_with("e_mail LIKE ?"), "{sql}");
+}
+
+#[tokio::test]
+async fn test_having_is_working() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let _ = create_alt_users(&pool, 10).await;
+    let res = AltUser::select()
+        .group_by_count()
+        .having_all_count(Having::Eq, 2)
+        .build(&pool)
+        .await
+        .unwrap();
+    assert_eq!(res.len(), 0);
+
+    let res = AltUser::select()
+        .group_by_count()
+        .having_count(Having::Eq, Function::Max, 1)
+        .build(&pool)
+        .await
+        .unwrap();
+    assert_eq!(res.len(), 1);
+}
+
+#[tokio::test]
+async fn test_seek_after_then_offset_is_working() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let _ = create_alt_users(&pool, 10).await;
+
+    let res = AltUser::select()
+        .seek_after_count(3)
+        .limit(3)
+        .then_offset(1)
+        .build(&pool)
+        .await
+        .unwrap();
+    let counts: Vec<_> = res.iter().map(|u| u.count).collect();
+    assert_eq!(counts, vec![Some(5), Some(6), Some(7)]);
+}
+
+#[tokio::test]
+async fn test_with_cte_filters_main_query() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let _ = create_alt_users(&pool, 10).await;
+
+    let low_counts = AltUser::select().where_count(Where::LesserThan, 3);
+    let res = AltUser::select()
+        .with_cte("low_counts", low_counts)
+        .raw_where("id IN (SELECT id FROM low_counts)")
+        .where_count(Where::GreaterThan, 0)
+        .order_by_count(OrderBy::Asc)
+        .build(&pool)
+        .await
+        .unwrap();
+    let counts: Vec<_> = res.iter().map(|u| u.count).collect();
+    assert_eq!(counts, vec![Some(1), Some(2)]);
+}
+
+#[tokio::test]
+async fn test_all_ordered_by_is_working() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let _ = create_users(&pool, 5, None).await;
+
+    let res = User::all_ordered_by_email(&pool, OrderBy::Desc)
+        .await
+        .unwrap();
+    assert_eq!(res.len(), 5);
+    let emails: Vec<_> = res.iter().map(|u| u.email.clone()).collect();
+    let mut expected = emails.clone();
+    expected.sort();
+    expected.reverse();
+    assert_eq!(emails, expected);
+}
+
+#[cfg(feature = "mysql")]
+#[tokio::test]
+async fn test_select_with_index_hint() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let users = create_users(&pool, 3, None).await;
+
+    let mut sql = String::new();
+    let res = User::select()
+        .use_index("email")
+        .where_email(Where::Eq, users[1].email.as_str())
+        .tap_sql(|s| sql = s.to_string())
+        .build(&pool)
+        .await
+        .unwrap();
+    assert!(sql.contains(" USE INDEX (email) WHERE"), "{sql}");
+    assert_eq!(res.len(), 1);
+
+    let res = User::select()
+        .with_hint("IGNORE INDEX (email)")
+        .build(&pool)
+        .await
+        .unwrap();
+    assert_eq!(res.len(), 3);
+}
+
+/// Window functions return `BIGINT UNSIGNED` on MySQL.
+#[cfg(not(feature = "mysql"))]
+type RankValue = i64;
+#[cfg(feature = "mysql")]
+type RankValue = u64;
+
+#[derive(Debug, sqlx::FromRow)]
+struct RankedAltUser {
+    #[sqlx(flatten)]
+    user: AltUser,
+    position: RankValue,
+}
+
+#[tokio::test]
+async fn test_window_dense_rank_is_working() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    for (i, count) in [30, 20, 20, 10].into_iter().enumerate() {
+        let u = AltUser {
+            email: format!("{i}-{}", SafeEmail().fake::<String>()),
+            count: Some(count),
+            ..Default::default()
+        };
+        u.save(&pool).await.unwrap();
+    }
+
+    let res: Vec<RankedAltUser> = AltUser::select()
+        .window_rank(
+            Rank::DenseRank,
+            &[],
+            &[("count", OrderBy::Desc)],
+            "position",
+        )
+        .order_by_count(OrderBy::Desc)
+        .build_as(&pool)
+        .await
+        .unwrap();
+    let ranks: Vec<_> = res.iter().map(|r| (r.user.count, r.position)).collect();
+    assert_eq!(
+        ranks,
+        vec![(Some(30), 1), (Some(20), 2), (Some(20), 2), (Some(10), 3)]
+    );
+}
+
+#[tokio::test]
+async fn test_where_group_is_working() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let _ = create_alt_users(&pool, 10).await;
+
+    let res = AltUser::select()
+        .begin_group()
+        .where_count(Where::LesserThan, 2)
+        .end_group()
+        .where_email(Where::Like, "%")
+        .build(&pool)
+        .await
+        .unwrap();
+    assert_eq!(res.len(), 2);
+}
+
+#[tokio::test]
+async fn test_or_where_group_is_working() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let _ = create_alt_users(&pool, 10).await;
+
+    let mut sql = String::new();
+    let res = AltUser::select()
+        .where_count(Where::Eq, 1)
+        .or_where_group(|g| {
+            g.where_count(Where::GreaterThan, 7)
+                .where_email(Where::Like, "9-%")
+        })
+        .tap_sql(|s| sql = s.to_string())
+        .build(&pool)
+        .await
+        .unwrap();
+    let mut counts: Vec<Option<i32>> = res.iter().map(|u| u.count).collect();
+    counts.sort();
+    assert_eq!(counts, vec![Some(1), Some(9)]);
+    #[cfg(feature = "postgres")]
+    assert!(
+        sql.ends_with("WHERE count = $1 OR ( count > $2 AND e_mail LIKE $3 )"),
+        "{sql}"
+    );
+    #[cfg(not(feature = "postgres"))]
+    assert!(
+        sql.ends_with("WHERE count = ? OR ( count > ? AND e_mail LIKE ? )"),
+        "{sql}"
+    );
+
+    let res = AltUser::select()
+        .where_group(|g| g.where_count(Where::LesserThan, 2))
+        .or_where_group(|g| g.where_count(Where::GreaterOrEqualTo, 8))
+        .build(&pool)
+        .await
+        .unwrap();
+    assert_eq!(res.len(), 4);
+}
+
+#[tokio::test]
+async fn test_count_matches_build_len() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let _ = create_alt_users(&pool, 10).await;
+
+    let filtered = || AltUser::select().where_count(Where::GreaterOrEqualTo, 6);
+    let count = filtered().count(&pool).await.unwrap();
+    assert_eq!(count, 4);
+    assert_eq!(count as usize, filtered().build(&pool).await.unwrap().len());
+
+    assert_eq!(AltUser::select().count(&pool).await.unwrap(), 10);
+    assert_eq!(
+        AltUser::select()
+            .where_count(Where::LesserThan, 5)
+            .group_by_count()
+            .count(&pool)
+            .await
+            .unwrap(),
+        5
+    );
+    assert_eq!(AltUser::select().limit(3).count(&pool).await.unwrap(), 3);
+}
+
+#[tokio::test]
+async fn test_or_joins_the_next_condition_with_or() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let users = create_alt_users(&pool, 10).await;
+
+    let mut counts: Vec<Option<i32>> = AltUser::select()
+        .where_group(|g| {
+            g.where_email(Where::Eq, users[1].email.as_str())
+                .or_where_email(Where::Eq, users[2].email.as_str())
+        })
+        .where_count(Where::GreaterThan, 0)
+        .build(&pool)
+        .await
+        .unwrap()
+        .iter()
+        .map(|u| u.count)
+        .collect();
+    counts.sort();
+    assert_eq!(counts, vec![Some(1), Some(2)]);
+
+    // Without the parentheses, the row with count 0 would match its email alone.
+    let mut sql = String::new();
+    let res = AltUser::select()
+        .where_group(|g| {
+            g.where_email(Where::Eq, users[0].email.as_str())
+                .or_where_email(Where::Eq, users[2].email.as_str())
+        })
+        .where_count(Where::GreaterThan, 0)
+        .tap_sql(|s| sql = s.to_string())
+        .build(&pool)
+        .await
+        .unwrap();
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0].id, users[2].id);
+    #[cfg(feature = "postgres")]
+    assert!(
+        sql.ends_with("WHERE ( e_mail = $1 OR e_mail = $2 ) AND count > $3"),
+        "{sql}"
+    );
+    #[cfg(not(feature = "postgres"))]
+    assert!(
+        sql.ends_with("WHERE ( e_mail = ? OR e_mail = ? ) AND count > ?"),
+        "{sql}"
+    );
+
+    // or() applies to any condition, and only to the next one.
+    let res = AltUser::select()
+        .where_count(Where::Eq, 1)
+        .or()
+        .where_in_count(&[3, 4])
+        .build(&pool)
+        .await
+        .unwrap();
+    assert_eq!(res.len(), 3);
+    let deleted = AltUser::delete_where()
+        .where_count(Where::Eq, 5)
+        .or_where_count(Where::Eq, 6)
+        .where_count(Where::Eq, 7)
+        .execute(&pool)
+        .await
+        .unwrap();
+    assert_eq!(deleted, 1);
+}
+
+#[tokio::test]
+#[cfg_attr(debug_assertions, should_panic(expected = "unbalanced"))]
+async fn test_unbalanced_group_is_rejected() {
+    let pool = get_pool().await.expect("Failed to create pool");
+
+    let res = AltUser::select()
+        .begin_group()
+        .where_count(Where::Eq, 1)
+        .build(&pool)
+        .await;
+    assert!(matches!(
+        res,
+        Err(lorm::errors::Error::QueryPreparationError(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_unmatched_end_group_is_rejected() {
+    let pool = get_pool().await.expect("Failed to create pool");
+
+    let res = AltUser::select()
+        .where_count(Where::Eq, 1)
+        .end_group()
+        .begin_group()
+        .where_count(Where::Eq, 2)
+        .build(&pool)
+        .await;
+    assert!(matches!(
+        res,
+        Err(lorm::errors::Error::QueryPreparationError(e)) if e.contains("end_group()")
+    ));
+}
+
+#[tokio::test]
+async fn test_empty_group_is_left_out() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let _ = create_alt_users(&pool, 10).await;
+
+    let mut sql = String::new();
+    let res = AltUser::select()
+        .where_count(Where::LesserThan, 2)
+        .or_where_group(|g| g)
+        .begin_group()
+        .end_group()
+        .where_email(Where::Like, "%")
+        .tap_sql(|s| sql = s.to_string())
+        .build(&pool)
+        .await
+        .unwrap();
+    assert_eq!(res.len(), 2);
+    #[cfg(feature = "postgres")]
+    assert!(
+        sql.ends_with("WHERE count < $1 AND e_mail LIKE $2"),
+        "{sql}"
+    );
+    #[cfg(not(feature = "postgres"))]
+    assert!(sql.ends_with("WHERE count < ? AND e_mail LIKE ?"), "{sql}");
+}
+
+#[tokio::test]
+async fn test_upsert_many_inserts_and_updates() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let existing = create_users(&pool, 2, None).await;
+
+    let mut items = existing.clone();
+    items[0].email = "upserted-0@example.com".to_string();
+    items.push(User {
+        email: "upserted-new@example.com".to_string(),
+        ..Default::default()
+    });
+
+    let res = User::upsert_many(&pool, &items).await.unwrap();
+    assert_eq!(res.len(), 3);
+
+    let updated = User::by_id(&pool, &existing[0].id).await.unwrap();
+    assert_eq!(updated.email, "upserted-0@example.com");
+    assert_eq!(updated.created_at, existing[0].created_at);
+
+    let created = User::by_email(&pool, "upserted-new@example.com")
+        .await
+        .unwrap();
+    assert_ne!(created.id, Uuid::nil());
+
+    let all = User::select().build(&pool).await.unwrap();
+    assert_eq!(all.len(), 3);
+}
+
+#[tokio::test]
+async fn test_save_batch_in_chunks_persists_every_row() {
+    let pool = get_pool().await.expect("Failed to create pool");
+
+    let items: Vec<User> = (0..5000)
+        .map(|i| User {
+            email: format!("batch-{i}@example.com"),
+            ..Default::default()
+        })
+        .collect();
+    let saved = User::save_batch_in_chunks(&pool, &items, 1000)
+        .await
+        .unwrap();
+    assert_eq!(saved.len(), 5000);
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(count, 5000);
+    let fetched = User::by_email(&pool, "batch-4999@example.com")
+        .await
+        .unwrap();
+    assert_ne!(fetched.id, Uuid::nil());
+
+    let res = User::save_batch_in_chunks(&pool, &items, 0).await;
+    assert!(matches!(
+        res,
+        Err(lorm::errors::Error::QueryPreparationError(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_insert_many_inserts_every_row() {
+    let pool = get_pool().await.expect("Failed to create pool");
+
+    let items: Vec<User> = (0..1000)
+        .map(|i| User {
+            email: format!("bulk-{i}@example.com"),
+            ..Default::default()
+        })
+        .collect();
+    let inserted = User::insert_many(&pool, &items).await.unwrap();
+    assert_eq!(inserted.len(), 1000);
+    assert!(inserted.iter().all(|u| !u.id.is_nil()));
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(count, 1000);
+    let fetched = User::by_email(&pool, "bulk-999@example.com").await.unwrap();
+    assert!(inserted.iter().any(|u| u.id == fetched.id));
+
+    assert!(User::insert_many(&pool, &[]).await.unwrap().is_empty());
+}
+
+#[cfg(any(feature = "sqlite", feature = "postgres"))]
+#[tokio::test]
+async fn test_insert_many_reads_back_database_generated_keys() {
+    let pool = get_pool().await.expect("Failed to create pool");
+
+    let items: Vec<AltUser> = (0..3)
+        .map(|i| AltUser {
+            email: format!("alt-bulk-{i}@example.com"),
+            count: Some(i),
+            ..Default::default()
+        })
+        .collect();
+    let inserted = AltUser::insert_many(&pool, &items).await.unwrap();
+    assert_eq!(inserted.len(), 3);
+    for item in &items {
+        let u = inserted.iter().find(|u| u.email == item.email).unwrap();
+        assert!(u.id > 0);
+        assert_eq!(u.count, item.count);
+    }
+}
+
+#[tokio::test]
+async fn test_upsert_on_unique_column_updates_the_existing_row() {
+    let pool = get_pool().await.expect("Failed to create pool");
+
+    let first = Subscriber {
+        email: "upsert-on@example.com".to_string(),
+        count: Some(1),
+        ..Default::default()
+    };
+    let inserted = first.upsert_on_email(&pool).await.unwrap();
+    assert_eq!(inserted.count, Some(1));
+
+    let second = Subscriber {
+        email: "upsert-on@example.com".to_string(),
+        count: Some(2),
+        ..Default::default()
+    };
+    let upserted = second.upsert_on_email(&pool).await.unwrap();
+    assert_eq!(upserted.id, inserted.id);
+    assert_eq!(upserted.count, Some(2));
+    assert_eq!(upserted.created_at, inserted.created_at);
+
+    let res = Subscriber::with_email(&pool, "upsert-on@example.com")
+        .await
+        .unwrap();
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0].count, Some(2));
+}
+
+#[tokio::test]
+async fn test_insert_on_taken_unique_value_is_a_unique_violation() {
+    let pool = get_pool().await.expect("Failed to create pool");
+
+    let taken = AltUser {
+        email: "taken@example.com".to_string(),
+        ..Default::default()
+    }
+    .save(&pool)
+    .await
+    .unwrap();
+
+    let res = AltUser {
+        email: "taken@example.com".to_string(),
+        ..Default::default()
+    }
+    .save(&pool)
+    .await;
+    match res {
+        Err(lorm::errors::Error::UniqueViolation { constraint, .. }) => {
+            // Only PostgreSQL names the violated constraint.
+            #[cfg(feature = "postgres")]
+            assert_eq!(constraint.as_deref(), Some("alt_users_e_mail_key"));
+            #[cfg(not(feature = "postgres"))]
+            assert_eq!(constraint, None);
+        }
+        res => panic!("expected a unique violation, got {res:?}"),
+    }
+
+    // The find-or-create helper returns the existing row instead.
+    let found = AltUser::by_email_or_insert(&pool, "taken@example.com", || AltUser {
+        email: "taken@example.com".to_string(),
+        ..Default::default()
+    })
+    .await
+    .unwrap();
+    assert_eq!(found.id, taken.id);
+    assert_eq!(
+        AltUser::by_email(&pool, "taken@example.com")
+            .await
+            .unwrap()
+            .id,
+        taken.id
+    );
+}
+
+// The MySQL tables declare no foreign keys.
+#[cfg(not(feature = "mysql"))]
+#[tokio::test]
+async fn test_insert_referencing_a_missing_row_is_a_foreign_key_violation() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let res = Post {
+        title: "orphan".to_string(),
+        user_id: Uuid::new_v4(),
+        ..Default::default()
+    }
+    .save(&pool)
+    .await;
+    assert!(matches!(
+        res,
+        Err(lorm::errors::Error::ForeignKeyViolation { .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_upsert_many_composite_key() {
+    let pool = get_pool().await.expect("Failed to create pool");
+
+    let first = UserRole {
+        user_id: "user_bulk".to_string(),
+        role_id: "reader".to_string(),
+        assigned_at: "2024-01-01".to_string(),
+    };
+    first.save(&pool).await.unwrap();
+
+    let items = vec![
+        UserRole {
+            assigned_at: "2024-02-01".to_string(),
+            ..first.clone()
+        },
+        UserRole {
+            user_id: "user_bulk".to_string(),
+            role_id: "writer".to_string(),
+            assigned_at: "2024-02-01".to_string(),
+        },
+    ];
+    let res = UserRole::upsert_many(&pool, &items).await.unwrap();
+    assert_eq!(res.len(), 2);
+    assert!(res.iter().all(|r| r.assigned_at == "2024-02-01"));
+
+    let empty = UserRole::upsert_many(&pool, &[]).await.unwrap();
+    assert!(empty.is_empty());
+}
+
+#[tokio::test]
+async fn test_save_without_writable_columns() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let visit = Visit::default().save(&pool).await.unwrap();
+    assert!(!visit.id.is_nil());
+
+    // Nothing to update, the row is read back unchanged.
+    let saved = visit.save(&pool).await.unwrap();
+    assert_eq!(saved.id, visit.id);
+    assert_eq!(saved.created_at, visit.created_at);
+    assert_eq!(Visit::pluck_pks(&pool).await.unwrap(), vec![visit.id]);
+}
+
+#[tokio::test]
+async fn test_save_reads_back_db_managed_columns() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let note = Note {
+        body: "draft".to_string(),
+        ..Default::default()
+    }
+    .save(&pool)
+    .await
+    .unwrap();
+    assert_ne!(note.updated_at, Note::default().updated_at);
+
+    // The trigger stamps the row with a later time than the insert.
+    let _ = sleep_until(Instant::now().add(Duration::from_millis(1100))).await;
+    let edited = Note {
+        body: "final".to_string(),
+        ..note.clone()
+    }
+    .save(&pool)
+    .await
+    .unwrap();
+    assert_eq!(edited.body, "final");
+    assert!(edited.updated_at > note.updated_at);
+
+    let fetched = Note::by_id(&pool, &note.id).await.unwrap();
+    assert_eq!(fetched.updated_at, edited.updated_at);
+}
+
+#[tokio::test]
+async fn test_soft_deleted_rows_are_hidden_unless_with_deleted() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let mut documents = Vec::new();
+    for title in ["kept", "deleted"] {
+        let document = Document {
+            title: title.to_string(),
+            ..Default::default()
+        }
+        .save(&pool)
+        .await
+        .unwrap();
+        documents.push(document);
+    }
+    let (kept, deleted) = (&documents[0], &documents[1]);
+    deleted.delete(&pool).await.unwrap();
+
+    assert!(matches!(
+        Document::by_id(&pool, &deleted.id).await,
+        Err(lorm::errors::Error::NotFound)
+    ));
+    assert!(!Document::exists_by_id(&pool, &deleted.id).await.unwrap());
+    assert!(
+        Document::with_title(&pool, "deleted")
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert_eq!(
+        Document::by_id(&pool, &kept.id).await.unwrap().title,
+        "kept"
+    );
+
+    // An OR cannot bring the deleted rows back.
+    let res = Document::select()
+        .where_title(Where::Eq, "kept")
+        .or_where_group(|g| g.where_title(Where::Eq, "deleted"))
+        .build(&pool)
+        .await
+        .unwrap();
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0].id, kept.id);
+
+    let res = Document::select()
+        .with_deleted()
+        .where_title(Where::Eq, "deleted")
+        .build(&pool)
+        .await
+        .unwrap();
+    assert_eq!(res.len(), 1);
+    assert!(res[0].deleted_at.is_some());
+
+    deleted.hard_delete(&pool).await.unwrap();
+    let res = Document::select()
+        .with_deleted()
+        .build(&pool)
+        .await
+        .unwrap();
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0].id, kept.id);
+}
+
+#[tokio::test]
+async fn test_saving_a_stale_copy_keeps_the_row_deleted() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let document = Document {
+        title: "draft".to_string(),
+        ..Default::default()
+    }
+    .save(&pool)
+    .await
+    .unwrap();
+
+    let mut stale = Document::by_id(&pool, &document.id).await.unwrap();
+    document.delete(&pool).await.unwrap();
+    stale.title = "edited".to_string();
+    let _ = stale.save(&pool).await;
+
+    assert!(matches!(
+        Document::by_id(&pool, &document.id).await,
+        Err(lorm::errors::Error::NotFound)
+    ));
+    let res = Document::select()
+        .with_deleted()
+        .where_id(Where::Eq, &document.id)
+        .build(&pool)
+        .await
+        .unwrap();
+    assert_eq!(res.len(), 1);
+    assert!(res[0].deleted_at.is_some());
+}
+
+#[tokio::test]
+async fn test_bulk_deletes_stamp_soft_deleted_rows() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let mut documents = Vec::new();
+    for title in ["by", "many", "where", "kept"] {
+        let document = Document {
+            title: title.to_string(),
+            ..Default::default()
+        }
+        .save(&pool)
+        .await
+        .unwrap();
+        documents.push(document);
+    }
+
+    assert_eq!(Document::delete_by_title(&pool, "by").await.unwrap(), 1);
+    assert_eq!(Document::delete_by_title(&pool, "by").await.unwrap(), 0);
+    assert_eq!(
+        Document::delete_many(&pool, &[documents[1].id])
+            .await
+            .unwrap(),
+        1
+    );
+    let deleted = Document::delete_where()
+        .where_title(Where::Eq, "where")
+        .or()
+        .where_title(Where::Eq, "by")
+        .execute(&pool)
+        .await
+        .unwrap();
+    assert_eq!(deleted, 1);
+
+    // The rows are only stamped, reload_many() skips them.
+    let res = Document::select()
+        .with_deleted()
+        .build(&pool)
+        .await
+        .unwrap();
+    assert_eq!(res.len(), 4);
+    assert_eq!(res.iter().filter(|d| d.deleted_at.is_some()).count(), 3);
+    let mut reloaded = documents.clone();
+    assert_eq!(
+        Document::reload_many(&pool, &mut reloaded).await.unwrap(),
+        1
+    );
+
+    assert_eq!(
+        Document::hard_delete_by_title(&pool, "by").await.unwrap(),
+        1
+    );
+    assert_eq!(
+        Document::hard_delete_many(&pool, &[documents[1].id])
+            .await
+            .unwrap(),
+        1
+    );
+    let deleted = Document::hard_delete_where()
+        .where_title(Where::Eq, "where")
+        .or()
+        .where_title(Where::Eq, "kept")
+        .execute(&pool)
+        .await
+        .unwrap();
+    assert_eq!(deleted, 2);
+    let res = Document::select()
+        .with_deleted()
+        .build(&pool)
+        .await
+        .unwrap();
+    assert!(res.is_empty());
+}
+
+#[tokio::test]
+async fn test_stale_save_is_an_optimistic_lock_conflict() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let page = Page {
+        body: "draft".to_string(),
+        ..Default::default()
+    }
+    .save(&pool)
+    .await
+    .unwrap();
+    assert_eq!(page.version, 0);
+
+    let mut stale = page.clone();
+    let mut fresh = page.clone();
+    fresh.body = "reviewed".to_string();
+    let fresh = fresh.save(&pool).await.unwrap();
+    assert_eq!(fresh.version, 1);
+
+    stale.body = "overwritten".to_string();
+    assert!(matches!(
+        stale.save(&pool).await,
+        Err(lorm::errors::Error::OptimisticLockConflict)
+    ));
+    assert_eq!(Page::by_id(&pool, &page.id).await.unwrap().body, "reviewed");
+
+    // Reloaded, the same change goes through.
+    let mut reloaded = Page::by_id(&pool, &page.id).await.unwrap();
+    reloaded.body = "overwritten".to_string();
+    let saved = reloaded.save(&pool).await.unwrap();
+    assert_eq!(saved.version, 2);
+    assert_eq!(saved.body, "overwritten");
+}
+
+#[tokio::test]
+async fn test_apply_patch_updates_only_set_fields() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let users = create_alt_users(&pool, 2).await;
+
+    // Needed for the updated_at bump assertion.
+    let _ = sleep_until(Instant::now().add(Duration::from_secs(1))).await;
+
+    let patch = AltUserPatch {
+        count: Some(None),
+        ..Default::default()
+    };
+    let affected = AltUser::apply_patch(&pool, users[0].id, &patch)
+        .await
+        .unwrap();
+    assert_eq!(affected, 1);
+    let patched = AltUser::by_id(&pool, users[0].id).await.unwrap();
+    assert_eq!(patched.count, None);
+    assert_eq!(patched.email, users[0].email);
+    assert!(patched.updated_at > users[0].updated_at);
+    let untouched = AltUser::by_id(&pool, users[1].id).await.unwrap();
+    assert_eq!(untouched.count, users[1].count);
+
+    let affected = AltUser::apply_patch(&pool, users[1].id, &AltUserPatch::default())
+        .await
+        .unwrap();
+    assert_eq!(affected, 0);
+}
+
+#[tokio::test]
+async fn test_integer_pk_is_assigned_by_the_database() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let first = Event {
+        name: "signup".to_string(),
+        ..Default::default()
+    }
+    .save(&pool)
+    .await
+    .unwrap();
+    let second = Event {
+        name: "login".to_string(),
+        ..Default::default()
+    }
+    .save(&pool)
+    .await
+    .unwrap();
+    assert!(first.id > 0);
+    assert!(second.id > first.id);
+    assert_eq!(Event::INSERT_COLUMNS, ["name"]);
+
+    // A set key updates the row.
+    let mut renamed = second.clone();
+    renamed.name = "logout".to_string();
+    let renamed = renamed.save(&pool).await.unwrap();
+    assert_eq!(renamed.id, second.id);
+    assert_eq!(Event::by_id(&pool, second.id).await.unwrap().name, "logout");
+    assert_eq!(Event::select().build(&pool).await.unwrap().len(), 2);
 }
 
+#[cfg(feature = "mysql")]
 #[tokio::test]
-async fn test_with_is_working() {
+async fn test_unsigned_integer_pk_is_assigned_by_the_database() {
     let pool = get_pool().await.expect("Failed to create pool");
-    let _ = create_alt_users(&pool, 10).await;
-    let _ = create_alt_users(&pool, 11).await;
-    let res = AltUser::with_count(&pool, 5).await.unwrap();
-    assert_eq!(res.len(), 2);
-    let res = AltUser::with_count(&pool, 10).await.unwrap();
-    assert_eq!(res.len(), 1);
-    let res = AltUser::with_count(&pool, 11).await.unwrap();
-    assert_eq!(res.len(), 0);
+    let tick = Tick {
+        name: "first".to_string(),
+        ..Default::default()
+    }
+    .save(&pool)
+    .await
+    .unwrap();
+    assert!(tick.id > 0);
+    assert_eq!(Tick::INSERT_COLUMNS, ["name"]);
+    assert_eq!(Tick::by_id(&pool, tick.id).await.unwrap().name, "first");
 }
 
 #[tokio::test]
-async fn test_offset_is_working() {
+async fn test_tracked_save_updates_only_changed_columns() {
     let pool = get_pool().await.expect("Failed to create pool");
-    let users = create_users(&pool, 10, None).await;
+    let users = create_alt_users(&pool, 1).await;
 
-    let res = User::select()
-        .order_by_email()
-        .desc()
-        .limit(2)
-        .build(&pool)
-        .await
-        .unwrap();
-    assert_eq!(res.is_empty(), false);
-    assert_eq!(res.len(), 2);
-    let u = res.last().unwrap();
-    assert_eq!(u.email, users.get(8).unwrap().email);
+    let mut tracked = AltUser::by_id(&pool, users[0].id).await.unwrap().tracked();
+    assert!(tracked.changed_columns().is_empty());
+    tracked.set_email("tracked@example.com".to_string());
+    assert_eq!(tracked.changed_columns(), ["e_mail"]);
+    assert_eq!(tracked.email, "tracked@example.com");
 
-    let res = User::select()
-        .order_by_email()
-        .desc()
-        .limit(2)
-        .offset(2)
-        .build(&pool)
+    // Written after the row was read, the count is not in the UPDATE and survives the save.
+    let patch = AltUserPatch {
+        count: Some(Some(42)),
+        ..Default::default()
+    };
+    AltUser::apply_patch(&pool, users[0].id, &patch)
         .await
         .unwrap();
-    assert_eq!(res.is_empty(), false);
-    assert_eq!(res.len(), 2);
-    let u = res.last().unwrap();
-    assert_eq!(u.email, users.get(6).unwrap().email);
+
+    let saved = tracked.save(&pool).await.unwrap();
+    assert_eq!(saved.email, "tracked@example.com");
+    assert_eq!(saved.count, Some(42));
+    let stored = AltUser::by_id(&pool, users[0].id).await.unwrap();
+    assert_eq!(stored.email, "tracked@example.com");
+    assert_eq!(stored.count, Some(42));
+
+    // Without any change, nothing is written.
+    let unchanged = stored.clone().tracked().save(&pool).await.unwrap();
+    assert_eq!(unchanged.updated_at, stored.updated_at);
+
+    // A versioned row is checked and bumped as by `save()`.
+    let page = Page {
+        body: "draft".to_string(),
+        ..Default::default()
+    }
+    .save(&pool)
+    .await
+    .unwrap();
+    let mut stale = page.clone().tracked();
+    let mut fresh = page.tracked();
+    fresh.set_body("reviewed".to_string());
+    assert_eq!(fresh.save(&pool).await.unwrap().version, 1);
+    stale.set_body("overwritten".to_string());
+    assert!(matches!(
+        stale.save(&pool).await,
+        Err(lorm::errors::Error::OptimisticLockConflict)
+    ));
 }
 
 #[tokio::test]
-async fn test_group_by_is_working() {
+async fn test_force_set_backfills_readonly_column() {
     let pool = get_pool().await.expect("Failed to create pool");
-    let _ = create_users(&pool, 11, None).await;
-    let other_users = create_users(&pool, 11, None).await;
+    let users = create_users(&pool, 1, None).await;
+    let before = User::by_id(&pool, &users[0].id).await.unwrap();
+    assert_eq!(before.count, None);
 
-    let res = User::select()
-        .group_by_email()
-        .order_by_created_at()
-        .desc()
-        .limit(2)
-        .build(&pool)
+    let affected = User::force_set_count(&pool, &users[0].id, 42)
         .await
         .unwrap();
-    assert_eq!(res.is_empty(), false);
-    assert_eq!(res.len(), 2);
-    let u = res.first().unwrap();
-    assert_eq!(u.email, other_users.get(10).unwrap().email);
+    assert_eq!(affected, 1);
+    let after = User::by_id(&pool, &users[0].id).await.unwrap();
+    assert_eq!(after.count, Some(42));
+
+    let alt_users = create_alt_users(&pool, 1).await;
+    let backfilled = alt_users[0].created_at - chrono::Duration::days(30);
+    AltUser::force_set_created_at(&pool, alt_users[0].id, &backfilled)
+        .await
+        .unwrap();
+    let alt_user = AltUser::by_id(&pool, alt_users[0].id).await.unwrap();
+    assert_eq!(alt_user.created_at, backfilled);
 }
 
+#[cfg(feature = "postgres")]
 #[tokio::test]
-async fn test_automatic_pk_and_ts_insertion_update_is_working() {
+async fn test_count_estimate_is_approximate_row_count() {
     let pool = get_pool().await.expect("Failed to create pool");
+    let _ = create_users(&pool, 50, None).await;
+    pool.execute("ANALYZE users").await.unwrap();
 
-    let mut u = AltUser::default();
-    u.email = SafeEmail().fake::<String>();
-    let u = u.save(&pool).await.unwrap();
-    let res = AltUser::by_id(&pool, u.id).await;
-    assert_eq!(res.is_err(), false);
+    let estimate = User::select().count_estimate(&pool).await.unwrap();
+    assert!((25..=100).contains(&estimate), "estimate was {estimate}");
 }
 
+#[cfg(feature = "postgres")]
 #[tokio::test]
-async fn test_where_is_working() {
+async fn test_ip_network_column_round_trip() {
+    use sqlx::types::ipnetwork::IpNetwork;
+
     let pool = get_pool().await.expect("Failed to create pool");
-    let users = create_alt_users(&pool, 10).await;
-    let u = users.get(2).unwrap();
+    let ip: IpNetwork = "10.0.0.1/32".parse().unwrap();
+    let device = Device {
+        id: Uuid::nil(),
+        ip,
+    }
+    .save(&pool)
+    .await
+    .unwrap();
+    Device {
+        id: Uuid::nil(),
+        ip: "10.0.0.2/32".parse().unwrap(),
+    }
+    .save(&pool)
+    .await
+    .unwrap();
 
-    let res = AltUser::select()
-        .where_id(Where::Eq, u.id)
+    let devices = Device::select()
+        .where_ip(Where::Eq, &ip)
         .build(&pool)
         .await
         .unwrap();
-    assert_eq!(res.len(), 1);
+    assert_eq!(devices.len(), 1);
+    assert_eq!(devices[0].id, device.id);
+
+    let found = Device::by_ip(&pool, &ip).await.unwrap();
+    assert_eq!(found.id, device.id);
+    assert_eq!(found.ip, ip);
 }
 
+#[cfg(feature = "postgres")]
 #[tokio::test]
-async fn test_between_is_working() {
+async fn test_for_update_comes_after_order_by_and_limit() {
     let pool = get_pool().await.expect("Failed to create pool");
-    let _ = create_alt_users(&pool, 10).await;
+    let users = create_users(&pool, 3, None).await;
+    let first = users.iter().map(|u| &u.email).min().unwrap();
+
+    let builder = User::select()
+        .for_update()
+        .order_by_email(OrderBy::Asc)
+        .limit(1);
+    let sql = builder.sql();
+    assert!(
+        sql.ends_with("ORDER BY email ASC LIMIT $1 FOR UPDATE"),
+        "{sql}"
+    );
 
-    let res = AltUser::select()
-        .where_between_count(2, 4)
-        .build(&pool)
-        .await
-        .unwrap();
-    assert_eq!(res.len(), 3);
+    let mut tx = pool.begin().await.unwrap();
+    let locked = builder.build(&mut *tx).await.unwrap();
+    assert_eq!(locked.len(), 1);
+    assert_eq!(&locked[0].email, first);
+    tx.rollback().await.unwrap();
 }
 
+#[cfg(feature = "postgres")]
 #[tokio::test]
-async fn test_like_is_working() {
+async fn test_lock_timeout_fails_on_locked_row() {
     let pool = get_pool().await.expect("Failed to create pool");
-    let _ = create_alt_users(&pool, 11).await;
-
-    let res = AltUser::select()
-        .where_email(Where::Like, "1%")
-        .build(&pool)
+    let users = create_users(&pool, 1, None).await;
+    let id = users[0].id;
+
+    let mut holder = pool.begin().await.unwrap();
+    let locked = User::select()
+        .where_id(Where::Eq, &id)
+        .for_update()
+        .build(&mut *holder)
         .await
         .unwrap();
-    assert_eq!(res.len(), 2);
+    assert_eq!(locked.len(), 1);
 
-    let res = AltUser::select()
-        .where_email(Where::Like, "%")
+    let mut waiter = pool.begin().await.unwrap();
+    let res = User::select()
+        .where_id(Where::Eq, &id)
+        .for_update()
+        .lock_timeout(Duration::from_millis(100))
+        .build_locked(&mut *waiter)
+        .await;
+    let code = match res {
+        Err(lorm::errors::Error::DatabaseError(e)) => e
+            .as_database_error()
+            .and_then(|e| e.code())
+            .map(|c| c.to_string()),
+        _ => None,
+    };
+    assert_eq!(code.as_deref(), Some("55P03"));
+    waiter.rollback().await.unwrap();
+
+    // On a pool, build_locked() opens the transaction SET LOCAL needs.
+    let res = User::select()
+        .where_id(Where::Eq, &id)
+        .for_update()
+        .lock_timeout(Duration::from_millis(100))
+        .build_locked(&pool)
+        .await;
+    let code = match res {
+        Err(lorm::errors::Error::DatabaseError(e)) => e
+            .as_database_error()
+            .and_then(|e| e.code())
+            .map(|c| c.to_string()),
+        _ => None,
+    };
+    assert_eq!(code.as_deref(), Some("55P03"));
+    holder.rollback().await.unwrap();
+
+    let res = User::select()
+        .lock_timeout(Duration::from_millis(100))
         .build(&pool)
-        .await
-        .unwrap();
-    assert_eq!(res.len(), 11);
+        .await;
+    assert!(matches!(
+        res,
+        Err(lorm::errors::Error::QueryPreparationError(_))
+    ));
 }
 
+#[cfg(feature = "postgres")]
 #[tokio::test]
-async fn test_having_is_working() {
+async fn test_by_pk_for_update_locks_the_row() {
     let pool = get_pool().await.expect("Failed to create pool");
-    let _ = create_alt_users(&pool, 10).await;
-    let res = AltUser::select()
-        .group_by_count()
-        .having_all_count(Having::Eq, 2)
-        .build(&pool)
+    let users = create_users(&pool, 1, None).await;
+    let id = users[0].id;
+
+    let mut holder = pool.begin().await.unwrap();
+    let locked = User::by_pk_for_update(&mut *holder, &id).await.unwrap();
+    assert_eq!(locked.id, id);
+
+    let lock_sql = "SELECT id FROM users WHERE id = $1 FOR UPDATE NOWAIT";
+    let mut waiter = pool.begin().await.unwrap();
+    let res = sqlx::query(lock_sql).bind(id).execute(&mut *waiter).await;
+    let code = res.err().and_then(|e| {
+        e.as_database_error()
+            .and_then(|e| e.code())
+            .map(|c| c.to_string())
+    });
+    assert_eq!(code.as_deref(), Some("55P03"));
+    waiter.rollback().await.unwrap();
+
+    holder.commit().await.unwrap();
+    let mut waiter = pool.begin().await.unwrap();
+    sqlx::query(lock_sql)
+        .bind(id)
+        .execute(&mut *waiter)
         .await
         .unwrap();
-    assert_eq!(res.len(), 0);
+    waiter.rollback().await.unwrap();
+}
 
-    let res = AltUser::select()
-        .group_by_count()
-        .having_count(Having::Eq, Function::Max, 1)
+#[cfg(feature = "postgres")]
+#[tokio::test]
+async fn test_statement_timeout_aborts_slow_query() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let _ = create_users(&pool, 1, None).await;
+
+    let mut tx = pool.begin().await.unwrap();
+    let res = User::select()
+        .raw_where("pg_sleep(2) IS NOT NULL")
+        .statement_timeout(Duration::from_millis(100))
+        .build_locked(&mut *tx)
+        .await;
+    let code = match res {
+        Err(lorm::errors::Error::DatabaseError(e)) => e
+            .as_database_error()
+            .and_then(|e| e.code())
+            .map(|c| c.to_string()),
+        _ => None,
+    };
+    assert_eq!(code.as_deref(), Some("57014"));
+    tx.rollback().await.unwrap();
+
+    let res = User::select()
+        .raw_where("pg_sleep(2) IS NOT NULL")
+        .statement_timeout(Duration::from_millis(100))
+        .build_locked(&pool)
+        .await;
+    let code = match res {
+        Err(lorm::errors::Error::DatabaseError(e)) => e
+            .as_database_error()
+            .and_then(|e| e.code())
+            .map(|c| c.to_string()),
+        _ => None,
+    };
+    assert_eq!(code.as_deref(), Some("57014"));
+
+    let res = User::select()
+        .statement_timeout(Duration::from_millis(100))
         .build(&pool)
-        .await
-        .unwrap();
-    assert_eq!(res.len(), 1);
+        .await;
+    assert!(matches!(
+        res,
+        Err(lorm::errors::Error::QueryPreparationError(_))
+    ));
 }
 
 #[cfg(feature = "sqlite")]
@@ -887,6 +4009,20 @@ async fn create_alt_users<'e, E: sqlx::MySqlExecutor<'e> + Copy>(
     users
 }
 
+/// Saves `count` tasks, every other one done starting with the first.
+async fn create_tasks(pool: &Pool, count: i32) -> Vec<Task> {
+    let mut tasks = vec![];
+    for i in 0..count {
+        let t = Task {
+            title: format!("Task {i}"),
+            done: i % 2 == 0,
+            ..Default::default()
+        };
+        tasks.push(t.save(pool).await.unwrap());
+    }
+    tasks
+}
+
 #[tokio::test]
 async fn test_profile_save_with_json() {
     let pool = get_pool().await.expect("Failed to create pool");
@@ -930,6 +4066,66 @@ async fn test_customer_save_with_flatten() {
     assert_eq!(fetched.address.zip, "90210");
 }
 
+/// MySQL binds `?` placeholders positionally: the SET values must be bound in column order,
+/// followed by the primary key of the WHERE clause.
+#[cfg(feature = "mysql")]
+#[tokio::test]
+async fn test_mysql_update_binds_values_in_placeholder_order() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let customer = Customer {
+        email: "before@example.com".to_string(),
+        address: Address {
+            street: "1 Old Road".to_string(),
+            zip: "11111".to_string(),
+        },
+        ..Default::default()
+    };
+    let mut customer = customer.save(&pool).await.unwrap();
+
+    customer.email = "after@example.com".to_string();
+    customer.address = Address {
+        street: "2 New Road".to_string(),
+        zip: "22222".to_string(),
+    };
+    let updated = customer.save(&pool).await.unwrap();
+    assert_eq!(updated.id, customer.id);
+
+    let fetched = Customer::by_id(&pool, &customer.id).await.unwrap();
+    assert_eq!(fetched.email, "after@example.com");
+    assert_eq!(fetched.address.street, "2 New Road");
+    assert_eq!(fetched.address.zip, "22222");
+}
+
+/// MySQL has no `RETURNING`: `save()` reads the row back by primary key, using
+/// `last_insert_id()` for auto-increment keys.
+#[cfg(feature = "mysql")]
+#[tokio::test]
+async fn test_mysql_save_reads_back_auto_increment_rows() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let first = AltUser {
+        email: "first@mysql.lorm.test".to_string(),
+        ..Default::default()
+    };
+    let first = first.save(&pool).await.unwrap();
+    let second = AltUser {
+        email: "second@mysql.lorm.test".to_string(),
+        count: Some(2),
+        ..Default::default()
+    };
+    let second = second.save(&pool).await.unwrap();
+    assert!(first.id > 0);
+    assert_ne!(first.id, second.id);
+    assert_eq!(second.email, "second@mysql.lorm.test");
+    assert_eq!(second.count, Some(2));
+
+    let mut updated = second.clone();
+    updated.count = Some(3);
+    let updated = updated.save(&pool).await.unwrap();
+    assert_eq!(updated.id, second.id);
+    assert_eq!(updated.count, Some(3));
+    assert_eq!(updated.created_at, second.created_at);
+}
+
 #[tokio::test]
 async fn test_customer_by_email_returns_flattened() {
     let pool = get_pool().await.expect("Failed to create pool");
@@ -1004,6 +4200,94 @@ async fn test_user_role_named_by_user_role() {
     assert_eq!(fetched.role_name, r.role_name);
 }
 
+#[tokio::test]
+async fn test_reload_many_refreshes_items_in_place() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let mut users = create_alt_users(&pool, 4).await;
+
+    AltUser::update_where()
+        .set_count(Some(42))
+        .where_count(Where::LesserThan, 2)
+        .execute(&pool)
+        .await
+        .unwrap();
+    users[3].clone().delete(&pool).await.unwrap();
+    users[3].count = Some(7);
+
+    let reloaded = AltUser::reload_many(&pool, &mut users).await.unwrap();
+    assert_eq!(reloaded, 3);
+    let counts: Vec<Option<i32>> = users.iter().map(|u| u.count).collect();
+    assert_eq!(counts, vec![Some(42), Some(42), Some(2), Some(7)]);
+
+    let mut roles = vec![
+        UserRole {
+            user_id: "reload".to_string(),
+            role_id: "a".to_string(),
+            assigned_at: "2024-01-01".to_string(),
+        },
+        UserRole {
+            user_id: "reload".to_string(),
+            role_id: "b".to_string(),
+            assigned_at: "2024-01-01".to_string(),
+        },
+    ];
+    for role in &roles {
+        role.save(&pool).await.unwrap();
+    }
+    roles[1].assigned_at = "stale".to_string();
+    assert_eq!(UserRole::reload_many(&pool, &mut roles).await.unwrap(), 2);
+    assert_eq!(roles[1].assigned_at, "2024-01-01");
+
+    assert_eq!(AltUser::reload_many(&pool, &mut []).await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn test_reload_many_splits_items_over_the_bind_limit() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let users = create_alt_users(&pool, 2).await;
+
+    // Only the first and last items, in different chunks, have a row.
+    let mut items: Vec<AltUser> = (0..MAX_BIND_PARAMETERS as i32 + 10)
+        .map(|i| AltUser {
+            id: 1_000_000 + i,
+            ..Default::default()
+        })
+        .collect();
+    items[0].id = users[0].id;
+    items.last_mut().unwrap().id = users[1].id;
+    let reloaded = AltUser::reload_many(&pool, &mut items).await.unwrap();
+    assert_eq!(reloaded, 2);
+    assert_eq!(items[0].email, users[0].email);
+    assert_eq!(items.last().unwrap().email, users[1].email);
+}
+
+#[tokio::test]
+async fn test_by_composite_key() {
+    let pool = get_pool().await.expect("Failed to create pool");
+
+    let r = UserRole {
+        user_id: Uuid::new_v4().to_string(),
+        role_id: "admin".to_string(),
+        assigned_at: "2024-01-01".to_string(),
+    };
+    r.save(&pool).await.unwrap();
+
+    let key = UserRoleKey {
+        user_id: r.user_id.clone(),
+        role_id: "admin".to_string(),
+    };
+    assert_eq!(r.key(), key);
+    let fetched = UserRole::by_composite_key(&pool, &key).await.unwrap();
+    assert_eq!(fetched.assigned_at, "2024-01-01");
+
+    let missing = UserRoleKey {
+        role_id: "editor".to_string(),
+        ..key
+    };
+    let res = UserRole::by_composite_key(&pool, &missing).await;
+    assert!(matches!(res, Err(lorm::errors::Error::NotFound)));
+}
+
 #[tokio::test]
 async fn test_user_role_delete_composite() {
     let pool = get_pool().await.expect("Failed to create pool");
@@ -1017,7 +4301,60 @@ async fn test_user_role_delete_composite() {
     r.delete(&pool).await.unwrap();
 
     let res = UserRole::by_key(&pool, &r.user_id, &r.role_id).await;
-    assert_eq!(res.is_err(), true);
+    assert!(matches!(res, Err(lorm::errors::Error::NotFound)));
+}
+
+#[cfg(any(feature = "sqlite", feature = "postgres"))]
+#[test]
+fn test_schema_qualified_table_name_is_kept_verbatim() {
+    let sql = QualifiedUser::select()
+        .where_email(Where::Eq, "schema@lorm.test")
+        .sql();
+    assert!(
+        sql.starts_with("SELECT id, email from public.users WHERE"),
+        "{sql}"
+    );
+    assert_eq!(
+        <QualifiedUser as lorm::query::JoinTarget<_>>::join_alias(),
+        "j_public_users"
+    );
+
+    let sql = QualifiedDocument::select().sql();
+    assert!(
+        sql.starts_with(
+            "SELECT id, title, deleted_at from (SELECT * FROM public.documents WHERE deleted_at IS NULL) lorm_rows"
+        ),
+        "{sql}"
+    );
+}
+
+#[cfg(feature = "postgres")]
+#[tokio::test]
+async fn test_schema_qualified_table_name_queries() {
+    let pool = get_pool().await.expect("Failed to create pool");
+    let users = create_users(&pool, 2, None).await;
+
+    let found = QualifiedUser::by_email(&pool, &users[1].email)
+        .await
+        .unwrap();
+    assert_eq!(found.id, users[1].id);
+    let res = QualifiedUser::select().build(&pool).await.unwrap();
+    assert_eq!(res.len(), 2);
+
+    let document = Document {
+        title: "qualified".to_string(),
+        ..Default::default()
+    }
+    .save(&pool)
+    .await
+    .unwrap();
+    let res = QualifiedDocument::select()
+        .where_title(Where::Eq, "qualified")
+        .build(&pool)
+        .await
+        .unwrap();
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0].id, document.id);
 }
 
 #[cfg(any(feature = "sqlite", feature = "postgres"))]
@@ -1248,6 +4585,60 @@ async fn test_backcompat_generated_pk_by_id_still_works() {
     assert_eq!(fetched.id, u.id);
 }
 
+#[cfg(any(feature = "sqlite", feature = "postgres"))]
+#[tokio::test]
+async fn test_i128_pk_stored_as_text() {
+    let pool = get_pool().await.expect("Failed to create pool");
+
+    let ledger = Ledger {
+        name: "main".to_string(),
+        ..Default::default()
+    };
+    let mut ledger = ledger.save(&pool).await.unwrap();
+    assert!(ledger.id > i128::from(i64::MAX));
+
+    let stored: String = sqlx::query_scalar("SELECT id FROM ledgers")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(stored, ledger.id.to_string());
+
+    ledger.name = "renamed".to_string();
+    let ledger = ledger.save(&pool).await.unwrap();
+    let fetched = Ledger::by_id(&pool, ledger.id).await.unwrap();
+    assert_eq!(fetched.id, ledger.id);
+    assert_eq!(fetched.name, "renamed");
+
+    let selected = Ledger::select()
+        .where_id(Where::Eq, ledger.id)
+        .build(&pool)
+        .await
+        .unwrap();
+    assert_eq!(selected.len(), 1);
+
+    // Decimal strings do not compare as numbers, range operators are rejected.
+    assert!(matches!(
+        Ledger::select()
+            .where_id(Where::GreaterThan, 9)
+            .build(&pool)
+            .await,
+        Err(lorm::errors::Error::QueryPreparationError(_))
+    ));
+    assert!(matches!(
+        Ledger::delete_where()
+            .where_id(Where::LesserThan, ledger.id)
+            .execute(&pool)
+            .await,
+        Err(lorm::errors::Error::QueryPreparationError(_))
+    ));
+
+    ledger.delete(&pool).await.unwrap();
+    assert!(matches!(
+        Ledger::by_id(&pool, ledger.id).await,
+        Err(lorm::errors::Error::NotFound)
+    ));
+}
+
 mod relations {
     use super::get_pool;
     use super::models::*;
@@ -1402,6 +4793,28 @@ mod relations {
         assert!(user_builder.is_none());
     }
 
+    #[tokio::test]
+    async fn test_nullable_fk_belongs_to_when_set() {
+        let pool = get_pool().await.expect("Failed to create pool");
+
+        let u = User {
+            email: "draft-author@test.com".to_string(),
+            ..Default::default()
+        };
+        let u = u.save(&pool).await.unwrap();
+
+        let d = Draft {
+            title: "Assigned Draft".to_string(),
+            user_id: Some(u.id),
+            ..Default::default()
+        };
+        let d = d.save(&pool).await.unwrap();
+
+        let users = d.user().unwrap().build(&pool).await.unwrap();
+        assert_eq!(users.len(), 1);
+        assert_eq!(users[0].id, u.id);
+    }
+
     #[tokio::test]
     async fn test_multiple_relations_one_struct() {
         let pool = get_pool().await.expect("Failed to create pool");
@@ -0,0 +1,4 @@
+CREATE TABLE IF NOT EXISTS ledgers (
+    id   VARCHAR(40) PRIMARY KEY NOT NULL,
+    name TEXT        NOT NULL
+);
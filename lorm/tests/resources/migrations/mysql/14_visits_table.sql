@@ -0,0 +1,4 @@
+CREATE TABLE IF NOT EXISTS visits (
+    id         BINARY(16)   PRIMARY KEY NOT NULL,
+    created_at TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6)
+);
@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS notes (
+    id         BINARY(16)   PRIMARY KEY NOT NULL,
+    body       TEXT         NOT NULL,
+    updated_at TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6)
+);
+CREATE TRIGGER notes_touch BEFORE UPDATE ON notes FOR EACH ROW SET NEW.updated_at = NOW(6);
@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS documents (
+    id         BINARY(16)   PRIMARY KEY NOT NULL,
+    title      TEXT         NOT NULL,
+    deleted_at TIMESTAMP(6) NULL
+);
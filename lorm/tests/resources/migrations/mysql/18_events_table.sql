@@ -0,0 +1,4 @@
+CREATE TABLE IF NOT EXISTS events (
+    id   BIGINT PRIMARY KEY AUTO_INCREMENT,
+    name TEXT   NOT NULL
+);
@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS reminders (
+    id         BINARY(16)   PRIMARY KEY NOT NULL,
+    title      TEXT         NOT NULL,
+    created_at TIMESTAMP(6) NOT NULL,
+    updated_at TIMESTAMP(6) NOT NULL
+);
@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS subscribers (
+    id         INTEGER PRIMARY KEY AUTO_INCREMENT,
+    email      VARCHAR(255) NOT NULL UNIQUE,
+    count      INTEGER,
+    created_at TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6)
+);
@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS tasks (
+    id    BINARY(16) PRIMARY KEY NOT NULL,
+    title TEXT       NOT NULL,
+    done  TINYINT(1) NOT NULL DEFAULT 0
+);
@@ -0,0 +1,4 @@
+CREATE TABLE IF NOT EXISTS ticks (
+    id   BIGINT UNSIGNED PRIMARY KEY AUTO_INCREMENT,
+    name TEXT            NOT NULL
+);
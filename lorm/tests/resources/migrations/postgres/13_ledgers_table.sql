@@ -0,0 +1,4 @@
+CREATE TABLE IF NOT EXISTS ledgers (
+    id   TEXT PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL
+);
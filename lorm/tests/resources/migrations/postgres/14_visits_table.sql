@@ -0,0 +1,4 @@
+CREATE TABLE IF NOT EXISTS visits (
+    id         UUID PRIMARY KEY NOT NULL,
+    created_at TIMESTAMPTZ      NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,12 @@
+CREATE TABLE IF NOT EXISTS notes (
+    id         UUID PRIMARY KEY NOT NULL,
+    body       TEXT             NOT NULL,
+    updated_at TIMESTAMPTZ      NOT NULL DEFAULT NOW()
+);
+CREATE OR REPLACE FUNCTION notes_touch() RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at = clock_timestamp();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+CREATE TRIGGER notes_touch BEFORE UPDATE ON notes FOR EACH ROW EXECUTE FUNCTION notes_touch();
@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS documents (
+    id         UUID PRIMARY KEY NOT NULL,
+    title      TEXT             NOT NULL,
+    deleted_at TIMESTAMPTZ
+);
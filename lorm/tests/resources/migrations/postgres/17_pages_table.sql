@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS pages (
+    id      UUID PRIMARY KEY NOT NULL,
+    body    TEXT             NOT NULL,
+    version BIGINT           NOT NULL DEFAULT 0
+);
@@ -0,0 +1,4 @@
+CREATE TABLE IF NOT EXISTS events (
+    id   BIGSERIAL PRIMARY KEY,
+    name TEXT      NOT NULL
+);
@@ -0,0 +1,4 @@
+CREATE TABLE IF NOT EXISTS devices (
+    id UUID PRIMARY KEY,
+    ip INET NOT NULL
+);
@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS reminders (
+    id         UUID PRIMARY KEY NOT NULL,
+    title      TEXT             NOT NULL,
+    created_at TIMESTAMPTZ      NOT NULL,
+    updated_at TIMESTAMPTZ      NOT NULL
+);
@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS subscribers (
+    id         SERIAL PRIMARY KEY,
+    email      VARCHAR     NOT NULL UNIQUE,
+    count      INTEGER,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
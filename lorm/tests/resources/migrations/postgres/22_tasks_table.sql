@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS tasks (
+    id    UUID    PRIMARY KEY NOT NULL,
+    title TEXT    NOT NULL,
+    done  BOOLEAN NOT NULL DEFAULT FALSE
+);
@@ -0,0 +1,4 @@
+CREATE TABLE IF NOT EXISTS visits (
+    id         TEXT PRIMARY KEY NOT NULL,
+    created_at DATETIME         NOT NULL DEFAULT (DATETIME('now'))
+);
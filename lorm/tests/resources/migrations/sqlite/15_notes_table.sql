@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS notes (
+    id         TEXT PRIMARY KEY NOT NULL,
+    body       TEXT             NOT NULL,
+    updated_at DATETIME         NOT NULL DEFAULT (DATETIME('now'))
+);
+CREATE TRIGGER IF NOT EXISTS notes_touch AFTER UPDATE OF body ON notes
+BEGIN
+    UPDATE notes SET updated_at = DATETIME('now') WHERE id = NEW.id;
+END;
@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS pages (
+    id      TEXT PRIMARY KEY NOT NULL,
+    body    TEXT             NOT NULL,
+    version INTEGER          NOT NULL DEFAULT 0
+);
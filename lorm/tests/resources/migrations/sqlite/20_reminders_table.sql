@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS reminders (
+    id         TEXT PRIMARY KEY NOT NULL,
+    title      TEXT             NOT NULL,
+    created_at DATETIME         NOT NULL,
+    updated_at DATETIME         NOT NULL
+);
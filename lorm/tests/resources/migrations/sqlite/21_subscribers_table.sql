@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS subscribers (
+    id         INTEGER PRIMARY KEY NOT NULL,
+    email      VARCHAR             NOT NULL UNIQUE,
+    count      INTEGER,
+    created_at DATETIME            NOT NULL DEFAULT (DATETIME('now'))
+);
@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS tasks (
+    id    TEXT    PRIMARY KEY NOT NULL,
+    title TEXT    NOT NULL,
+    done  INTEGER NOT NULL DEFAULT 0
+);